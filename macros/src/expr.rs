@@ -0,0 +1,266 @@
+//! Generation of Rust expressions which construct syntax tree nodes.
+
+use std::borrow::Cow;
+
+use tl_lang_syn::punctuated::{Pair, Punctuated};
+use tl_lang_syn::span::Span;
+use tl_lang_syn::spanned::Spanned;
+use tl_lang_syn::{BitIndex, Id, IdFormat, Ident};
+
+
+/// A syntax tree node which can be turned into Rust code constructing an
+/// equal node, spans included.
+///
+/// Invariants of identifiers, bit indices and id formats were checked when
+/// the node was parsed, so the generated code uses unchecked constructors.
+pub trait ToExpr {
+    /// Return a Rust expression which evaluates to a copy of this node.
+    fn to_expr(&self) -> String;
+}
+
+
+impl ToExpr for u32 {
+    fn to_expr(&self) -> String {
+        format!("{}u32", self)
+    }
+}
+
+impl ToExpr for usize {
+    fn to_expr(&self) -> String {
+        format!("{}usize", self)
+    }
+}
+
+impl<'a> ToExpr for Cow<'a, str> {
+    fn to_expr(&self) -> String {
+        // `Debug` escapes a string the same way Rust string literals do
+        format!("::std::borrow::Cow::Borrowed({:?})", self)
+    }
+}
+
+impl<T: ToExpr> ToExpr for Box<T> {
+    fn to_expr(&self) -> String {
+        format!("::std::boxed::Box::new({})", (**self).to_expr())
+    }
+}
+
+impl<T: ToExpr> ToExpr for Option<T> {
+    fn to_expr(&self) -> String {
+        match *self {
+            Some(ref value) => format!("::std::option::Option::Some({})", value.to_expr()),
+            None => "::std::option::Option::None".to_owned(),
+        }
+    }
+}
+
+impl<T: ToExpr> ToExpr for Vec<T> {
+    fn to_expr(&self) -> String {
+        let elements = self.iter().map(ToExpr::to_expr).collect::<Vec<_>>();
+
+        format!("vec![{}]", elements.join(", "))
+    }
+}
+
+impl<T: ToExpr, P: ToExpr> ToExpr for Punctuated<T, P> {
+    fn to_expr(&self) -> String {
+        let mut expr = "{ let mut punctuated = ::tl_lang_syn::punctuated::Punctuated::new(); ".to_owned();
+
+        for pair in self.pairs() {
+            expr += &format!("punctuated.push_value({}); ", pair.value().to_expr());
+
+            if let Pair::Punctuated(_, punct) = pair {
+                expr += &format!("punctuated.push_punct({}); ", punct.to_expr());
+            }
+        }
+
+        expr + "punctuated }"
+    }
+}
+
+impl ToExpr for Span {
+    fn to_expr(&self) -> String {
+        format!("::tl_lang_syn::span::Span::new({}, {})", self.begin(), self.end())
+    }
+}
+
+impl<'a> ToExpr for Ident<'a> {
+    fn to_expr(&self) -> String {
+        format!(
+            "unsafe {{ ::tl_lang_syn::Ident::new_unchecked({}, {:?}) }}",
+            self.span().to_expr(),
+            self.as_str(),
+        )
+    }
+}
+
+impl ToExpr for BitIndex {
+    fn to_expr(&self) -> String {
+        format!(
+            "unsafe {{ ::tl_lang_syn::BitIndex::new_unchecked({}, {}) }}",
+            self.span().to_expr(),
+            self.index(),
+        )
+    }
+}
+
+impl ToExpr for IdFormat {
+    fn to_expr(&self) -> String {
+        let uppercase_mask = (0..8)
+            .filter(|&index| self.is_uppercase_digit(index))
+            .fold(0u8, |mask, index| mask | 1 << index);
+
+        format!(
+            "unsafe {{ ::tl_lang_syn::IdFormat::new_unchecked({}, {}) }}",
+            self.digit_count(),
+            uppercase_mask,
+        )
+    }
+}
+
+impl ToExpr for Id {
+    fn to_expr(&self) -> String {
+        format!(
+            "::tl_lang_syn::Id {{ span: {}, id: {}, format: {} }}",
+            self.span.to_expr(),
+            self.id.to_expr(),
+            self.format.to_expr(),
+        )
+    }
+}
+
+
+macro_rules! token_to_expr {
+    ($($token:ident,)*) => {
+        $(
+            impl ToExpr for ::tl_lang_syn::token::$token {
+                fn to_expr(&self) -> String {
+                    format!(concat!("::tl_lang_syn::token::", stringify!($token), "({})"), self.0.to_expr())
+                }
+            }
+        )*
+    };
+}
+
+token_to_expr! {
+    Asterisk,
+    Colon,
+    Comma,
+    Dot,
+    Empty,
+    Equals,
+    Excl,
+    Final,
+    Hash,
+    LAngle,
+    Layer,
+    LayerTitleCase,
+    New,
+    Percent,
+    Plus,
+    Question,
+    RAngle,
+    Semicolon,
+    SlashSlash,
+    Brace,
+    Bracket,
+    Paren,
+    SlashAsterisk,
+}
+
+macro_rules! struct_to_expr {
+    ($($name:ident $(<$lt:lifetime>)* { $($field:ident,)* })*) => {
+        $(
+            impl<$($lt)*> ToExpr for ::tl_lang_syn::$name $(<$lt>)* {
+                fn to_expr(&self) -> String {
+                    let mut expr = concat!("::tl_lang_syn::", stringify!($name), " { ").to_owned();
+                    $(
+                        expr += &format!(concat!(stringify!($field), ": {}, "), self.$field.to_expr());
+                    )*
+
+                    expr + "}"
+                }
+            }
+        )*
+    };
+}
+
+struct_to_expr! {
+    AngleBracketedGenericArguments<'a> { langle_token, args, rangle_token, }
+    BitSelector { dot_token, bit_index, }
+    CombinatorId { hash_token, id, }
+    CommentMultiLine<'a> { slash_asterisk_token, content_span, content, }
+    CommentSingleLine<'a> { slash_slash_token, content_span, content, }
+    ConditionalParamDef<'a> { var_ident, bit_selector, question_token, }
+    DelimiterFunctions { span, }
+    DelimiterTypes { span, }
+    DocComment<'a> { tags, }
+    DocCommentTag<'a> { name_span, name, text_span, text, }
+    File<'a> { items, }
+    ItemBuiltin<'a> { name, combinator_id, question_token, equals_token, result_type, semicolon_token, }
+    ItemCombinator<'a> {
+        leading_comments, doc_comment, name, combinator_id, opt_params, opt_param_comments,
+        params, param_comments, equals_token, result_comments, result_type, semicolon_token,
+        trailing_comment,
+    }
+    ItemComment<'a> { comment, }
+    ItemDelimiter { delimiter, }
+    ItemError<'a> { span, text, }
+    ItemLayer { marker, layer_span, layer, }
+    ItemTypeDeclaration<'a> { kind, name, semicolon_token, }
+    LayerMarkerEquals { left_span, right_span, }
+    LayerMarkerMultiLine { slash_asterisk_token, layer_keyword, }
+    LayerMarkerSingleLine { slash_slash_token, layer_keyword, }
+    Multiplicity<'a> { term, asterisk_token, }
+    NatExprAdd<'a> { left, plus_token, right, }
+    NatExprConst { span, value, }
+    NatExprIdent<'a> { ident, }
+    NatExprParen<'a> { paren_token, expr, }
+    OptParam<'a> { brace_token, var_idents, colon_token, ty, }
+    ParamComment<'a> { index, comment, }
+    ParamConditional<'a> { var_ident, colon_token, conditional_param_def, ty, }
+    ParamRepeated<'a> { param_repeated_ident, multiplicity, bracket_token, params, }
+    ParamRepeatedIdent<'a> { var_ident, colon_token, }
+    ParamTypeOnly<'a> { ty, }
+    ParamWithParen<'a> { paren_token, var_idents, colon_token, ty, }
+    ParameterizedPath<'a> { path, args, }
+    Path<'a> { segments, }
+    SpaceSeparatedGenericArguments<'a> { args, }
+    TypeBare<'a> { percent_token, inner, }
+    TypeInt { hash_token, }
+    TypeParameterizedPath<'a> { parameterized_path, }
+    TypeParen<'a> { paren_token, inner, }
+    TypeTypeParameter<'a> { excl_token, ident, }
+}
+
+macro_rules! enum_to_expr {
+    ($($name:ident $(<$lt:lifetime>)* { $($variant:ident,)* })*) => {
+        $(
+            impl<$($lt)*> ToExpr for ::tl_lang_syn::$name $(<$lt>)* {
+                fn to_expr(&self) -> String {
+                    match *self {
+                        $(
+                            ::tl_lang_syn::$name::$variant(ref inner) => format!(
+                                concat!("::tl_lang_syn::", stringify!($name), "::", stringify!($variant), "({})"),
+                                inner.to_expr(),
+                            ),
+                        )*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+enum_to_expr! {
+    Comment<'a> { SingleLine, MultiLine, }
+    Delimiter { Types, Functions, }
+    GenericArgument<'a> { Type, Nat, }
+    GenericArguments<'a> { AngleBracketed, SpaceSeparated, }
+    Item<'a> { Combinator, Builtin, TypeDeclaration, Delimiter, Layer, Comment, Error, }
+    LayerKeyword { Upper, TitleCase, }
+    LayerMarker { SingleLine, MultiLine, Equals, }
+    NatExpr<'a> { Const, Ident, Add, Paren, }
+    Param<'a> { Conditional, Repeated, WithParen, TypeOnly, }
+    Type<'a> { Int, ParameterizedPath, TypeParameter, Bare, Paren, }
+    TypeDeclarationKind { New, Final, Empty, }
+}
@@ -0,0 +1,271 @@
+//! The `tl!` macro which parses a snippet of TL language schema into a syntax
+//! tree of [`tl-lang-syn`].
+//!
+//! The snippet is given as a string literal, because TL source text is not
+//! always made of valid Rust tokens: comments would be dropped and ids like
+//! `#0e16a8a5` can't be lexed at all. The snippet is parsed at compile time,
+//! so a malformed one is reported as a compilation error pointing to the
+//! macro invocation:
+//!
+//! ```text
+//! error: expected `;`, found end of input
+//!  --> tl!:1:21
+//!   |
+//! 1 | boolTrue#997275b5 = Bool
+//!   |                     ^ expected `;`
+//! ```
+//!
+//! The macro expands to an expression which constructs the parsed syntax
+//! tree directly, spans included, so nothing is parsed at runtime and the
+//! crate which uses it doesn't need the "parsing" feature of `tl-lang-syn`.
+//!
+//! [`tl-lang-syn`]: https://docs.rs/tl-lang-syn
+
+extern crate proc_macro;
+extern crate tl_lang_syn;
+
+mod expr;
+
+use proc_macro::{Delimiter, Group, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use tl_lang_syn::diagnostic::{ColorChoice, Diagnostic};
+use tl_lang_syn::error::ParseError;
+use tl_lang_syn::line_index::LineIndex;
+
+use expr::ToExpr;
+
+
+/// Parse a snippet of TL language schema into a syntax tree node.
+///
+/// Without a node type the snippet is parsed as a `File`. Any other node
+/// type which can be parsed by `tl_lang_syn::parse_str` is specified before
+/// the snippet followed by `:`. Leading and trailing whitespace of the
+/// snippet is ignored.
+///
+/// ```ignore
+/// let file = tl!("boolFalse#bc799737 = Bool;\nboolTrue#997275b5 = Bool;");
+/// let combinator = tl!(ItemCombinator: "vector#1cb5c415 {t:Type} # [ t ] = Vector t;");
+/// let ty = tl!(Type: "Vector<InputPeer>");
+/// ```
+#[proc_macro]
+pub fn tl(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(tokens) => tokens,
+        Err((span, message)) => compile_error(span, &message),
+    }
+}
+
+fn expand(input: TokenStream) -> Result<TokenStream, (Span, String)> {
+    let (node, literal) = parse_input(input)?;
+
+    let value = string_literal_value(&literal)
+        .ok_or_else(|| (literal.span(), "expected a string literal".to_owned()))?;
+    // Snippets usually start with a line break and end with indentation
+    // before the closing quote
+    let source = value.trim();
+
+    let expansion = match expand_node(&node, source) {
+        Some(Ok(expansion)) => expansion,
+        Some(Err(error)) => {
+            let diagnostic = Diagnostic::from(&error);
+            let rendered = diagnostic.render("tl!", &LineIndex::new(source), ColorChoice::Plain);
+            // `compile_error!` adds its own `error: ` prefix
+            let message = rendered.trim_start_matches("error: ").trim_end();
+
+            return Err((literal.span(), message.to_owned()));
+        },
+        None => {
+            return Err((Span::call_site(), format!("`{}` is not a node type which can be parsed", node)));
+        },
+    };
+
+    Ok(expansion.parse().expect("the expansion of `tl!` must be valid Rust code"))
+}
+
+/// Split the input into the node type and the string literal.
+fn parse_input(input: TokenStream) -> Result<(String, Literal), (Span, String)> {
+    const EXPECTED: &str = "expected a string literal, optionally preceded by a node type and `:`";
+
+    let tokens = input.into_iter().map(flatten_none_group).collect::<Vec<_>>();
+
+    match *tokens.as_slice() {
+        [TokenTree::Literal(ref literal)] => Ok(("File".to_owned(), literal.clone())),
+        [TokenTree::Ident(ref node), TokenTree::Punct(ref colon), TokenTree::Literal(ref literal)]
+            if colon.as_char() == ':' => Ok((node.to_string(), literal.clone())),
+        [] => Err((Span::call_site(), EXPECTED.to_owned())),
+        [ref first, ..] => Err((first.span(), EXPECTED.to_owned())),
+    }
+}
+
+/// Unwrap a group without delimiters, which is how a fragment forwarded by a
+/// `macro_rules!` macro arrives.
+fn flatten_none_group(token: TokenTree) -> TokenTree {
+    if let TokenTree::Group(ref group) = token {
+        if group.delimiter() == Delimiter::None {
+            let mut inner = group.stream().into_iter();
+
+            if let (Some(first), None) = (inner.next(), inner.next()) {
+                return flatten_none_group(first);
+            }
+        }
+    }
+
+    token
+}
+
+macro_rules! expand_node_types {
+    ($($node:ident,)*) => {
+        /// Parse `source` as the node type named `node` and return Rust code
+        /// which constructs the parsed node, or return `None` if there is no
+        /// such node type.
+        fn expand_node(node: &str, source: &str) -> Option<Result<String, ParseError>> {
+            match node {
+                $(
+                    stringify!($node) => {
+                        Some(tl_lang_syn::parse_str::<tl_lang_syn::$node>(source).map(|node| node.to_expr()))
+                    },
+                )*
+                _ => None,
+            }
+        }
+    };
+}
+
+expand_node_types! {
+    AngleBracketedGenericArguments,
+    BitIndex,
+    BitSelector,
+    CombinatorId,
+    Comment,
+    CommentMultiLine,
+    CommentSingleLine,
+    ConditionalParamDef,
+    Delimiter,
+    DelimiterFunctions,
+    DelimiterTypes,
+    File,
+    GenericArgument,
+    GenericArguments,
+    Id,
+    Ident,
+    Item,
+    ItemBuiltin,
+    ItemCombinator,
+    ItemComment,
+    ItemDelimiter,
+    ItemLayer,
+    ItemTypeDeclaration,
+    LayerKeyword,
+    Multiplicity,
+    NatExpr,
+    NatExprConst,
+    NatExprIdent,
+    NatExprParen,
+    OptParam,
+    Param,
+    ParamConditional,
+    ParamRepeated,
+    ParamRepeatedIdent,
+    ParamTypeOnly,
+    ParamWithParen,
+    ParameterizedPath,
+    Path,
+    SpaceSeparatedGenericArguments,
+    Type,
+    TypeBare,
+    TypeDeclarationKind,
+    TypeInt,
+    TypeParameterizedPath,
+    TypeParen,
+    TypeTypeParameter,
+}
+
+/// Return the value of a string literal, or `None` if `literal` is not one.
+fn string_literal_value(literal: &Literal) -> Option<String> {
+    let repr = literal.to_string();
+
+    if let Some(raw) = repr.strip_prefix('r') {
+        let hashes = raw.chars().take_while(|&c| c == '#').count();
+        let quoted = &raw[hashes..raw.len() - hashes];
+
+        if quoted.len() >= 2 && quoted.starts_with('"') && quoted.ends_with('"') {
+            Some(quoted[1..quoted.len() - 1].to_owned())
+        } else {
+            None
+        }
+    } else if repr.len() >= 2 && repr.starts_with('"') && repr.ends_with('"') {
+        unescape(&repr[1..repr.len() - 1])
+    } else {
+        None
+    }
+}
+
+/// Resolve escapes in the contents of a non-raw string literal.
+fn unescape(contents: &str) -> Option<String> {
+    let mut value = String::with_capacity(contents.len());
+    let mut chars = contents.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+
+        match chars.next()? {
+            'n' => value.push('\n'),
+            'r' => value.push('\r'),
+            't' => value.push('\t'),
+            '0' => value.push('\0'),
+            '\\' => value.push('\\'),
+            '\'' => value.push('\''),
+            '"' => value.push('"'),
+            'x' => {
+                let digits = chars.next()?.to_string() + &chars.next()?.to_string();
+                value.push(u8::from_str_radix(&digits, 16).ok()? as char);
+            },
+            'u' => {
+                if chars.next()? != '{' {
+                    return None;
+                }
+
+                let mut digits = String::new();
+                loop {
+                    match chars.next()? {
+                        '}' => break,
+                        '_' => (),
+                        digit => digits.push(digit),
+                    }
+                }
+
+                value.push(std::char::from_u32(u32::from_str_radix(&digits, 16).ok()?)?);
+            },
+            '\n' | '\r' => {
+                // A line continuation skips the line break and the leading
+                // whitespace of the next line
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+            },
+            _ => return None,
+        }
+    }
+
+    Some(value)
+}
+
+/// Build `compile_error!("message")` pointing to `span`.
+fn compile_error(span: Span, message: &str) -> TokenStream {
+    let mut message = Literal::string(message);
+    message.set_span(span);
+
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+
+    let mut args = Group::new(Delimiter::Parenthesis, TokenTree::Literal(message).into());
+    args.set_span(span);
+
+    vec![
+        TokenTree::Ident(proc_macro::Ident::new("compile_error", span)),
+        TokenTree::Punct(bang),
+        TokenTree::Group(args),
+    ].into_iter().collect()
+}
@@ -0,0 +1,120 @@
+extern crate tl_lang_macros;
+extern crate tl_lang_syn;
+
+use tl_lang_macros::tl;
+use tl_lang_syn::{Item, ItemCombinator, ParameterizedPath, Path, Type};
+use tl_lang_syn::print::Print;
+use tl_lang_syn::span::Span;
+use tl_lang_syn::spanned::Spanned;
+
+
+#[test]
+fn file() {
+    let file = tl!("
+        // Booleans
+        boolFalse#bc799737 = Bool;
+        boolTrue#997275b5 = Bool;
+    ");
+
+    assert_eq!(file.items.len(), 2);
+    match file.items[0] {
+        Item::Combinator(ref combinator) => {
+            assert_eq!(combinator.leading_comments.len(), 1);
+            assert_eq!(combinator.name, Path::new("boolFalse").unwrap());
+        },
+        ref item => panic!("expected a combinator, found {:?}", item),
+    }
+}
+
+#[test]
+fn doc_comment() {
+    let combinator = tl!(ItemCombinator: "
+        //@description Returns a file @id Unique file identifier
+        getFile id:int32 = File;
+    ");
+    let doc_comment = combinator.doc_comment().unwrap();
+
+    assert_eq!(doc_comment.description(), Some("Returns a file"));
+    assert_eq!(doc_comment.get("id"), Some("Unique file identifier"));
+    assert_ne!(doc_comment.tags[1].name_span, Span::zeroed());
+}
+
+#[test]
+fn node_types() {
+    let combinator = tl!(ItemCombinator: "vector#1cb5c415 {t:Type} # [t] = Vector t;");
+    assert_eq!(combinator.display_wrapper().to_string(), "vector#1cb5c415 {t:Type} # [t] = Vector t;");
+
+    let ty = tl!(Type: r#"Vector<InputPeer>"#);
+    let expected = Type::from(ParameterizedPath::with_args(
+        Path::new("Vector").unwrap(),
+        vec![Type::from(Path::new("InputPeer").unwrap())],
+    ));
+    assert_eq!(ty, expected);
+}
+
+#[test]
+fn escapes() {
+    let combinator = tl!(ItemCombinator: "\u{6e}ull#56730bcc = \
+                                          Null;");
+    assert_eq!(combinator.display_wrapper().to_string(), "null#56730bcc = Null;");
+}
+
+#[test]
+fn matches_builder() {
+    let parsed = tl!(ItemCombinator: "
+        messages.sendMessage#fa88427a flags:# silent:flags.5?true peer:InputPeer
+            entities:flags.3?Vector<MessageEntity> = Updates;
+    ");
+
+    let path = |s| Path::new(s).unwrap();
+    let built = ItemCombinator::builder("messages.sendMessage")
+        .id(0xfa88427a)
+        .param("flags", Type::int())
+        .conditional_param("silent", "flags", 5, path("true"))
+        .param("peer", path("InputPeer"))
+        .conditional_param("entities", "flags", 3,
+            ParameterizedPath::with_args(path("Vector"), vec![Type::from(path("MessageEntity"))]))
+        .result_type(path("Updates"))
+        .build();
+
+    assert_eq!(parsed, built);
+}
+
+macro_rules! forwarded {
+    ($source:expr) => { tl!(Path: $source) };
+}
+
+#[test]
+fn forwarded_literal() {
+    assert_eq!(forwarded!("contacts.Link"), Path::new("contacts.Link").unwrap());
+}
+
+#[test]
+fn preserves_source() {
+    let file = tl!("
+        ---types---
+        // LAYER 105
+        /* Vectors */
+        vector#1CB5c415 {t:Type} # [t] = Vector t;
+        int128 4*[int] = Int128;
+        pair {X:Type} flags:# (a b:X) c:%(Vector X) d:flags.31?(Pair X (n+1)) = Pair X; // pairs
+        int ? = Int;
+        New Foo;
+        ---functions---
+    ");
+
+    assert_eq!(file.display_wrapper().to_string(), "\
+        ---types---\n\
+        // LAYER 105\n\
+        /* Vectors */\n\
+        vector#1CB5c415 {t:Type} # [t] = Vector t;\n\
+        int128 4*[int] = Int128;\n\
+        pair {X:Type} flags:# (a b:X) c:%(Vector X) d:flags.31?(Pair X (n+1)) = Pair X; // pairs\n\
+        int ? = Int;\n\
+        New Foo;\n\
+        ---functions---\
+    ");
+
+    let combinator = tl!(ItemCombinator: "boolTrue#997275b5 = Bool;");
+    assert_eq!(combinator.span(), Span::new(1, 26));
+}
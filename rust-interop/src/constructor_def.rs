@@ -4,14 +4,13 @@ use tl_lang_syn as tlsn;
 
 use ::field::Field;
 use ::ident::Ident;
-use ::utils::TraversalMode;
 
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ConstructorDefNamespace {
     pub name: Ident,
     pub constructor_defs: Vec<ConstructorDef>,
-    pub namespaces: HashMap<tlsn::Ident, ConstructorDefNamespace>,
+    pub namespaces: HashMap<tlsn::Ident<'static>, ConstructorDefNamespace>,
 }
 
 impl ConstructorDefNamespace {
@@ -19,7 +18,7 @@ impl ConstructorDefNamespace {
         Ident::with_str(name).map(Self::with_ident)
     }
 
-    fn with_tl_ident(name: tlsn::Ident) -> Self {
+    fn with_tl_ident(name: tlsn::Ident<'static>) -> Self {
         Self::with_ident(Ident(name))
     }
 
@@ -31,66 +30,45 @@ impl ConstructorDefNamespace {
         }
     }
 
-    pub fn from_tl_items(items: &[tlsn::Item]) -> Self {
-        let mut mode = TraversalMode::Types;
+    /// Get the child namespace named `name`, creating it if it doesn't exist.
+    ///
+    /// The map is looked up by the symbol of `name`, so that the name is
+    /// only copied when the namespace is created. Returning the result of
+    /// `get_mut` directly on a hit isn't accepted by the borrow checker.
+    fn namespace_mut(&mut self, name: &tlsn::Ident) -> &mut Self {
+        if !self.namespaces.contains_key(&name.symbol()) {
+            let name = name.to_owned();
+            self.namespaces.insert(name.clone(), Self::with_tl_ident(name));
+        }
+
+        self.namespaces.get_mut(&name.symbol()).unwrap()
+    }
+
+    pub fn from_tl_sections(sections: &tlsn::Sections) -> Self {
         let mut constructor_def_ns = Self::with_str("constructors").unwrap();
 
-        for item in items {
-            match *item {
-                tlsn::Item::Combinator(ref combinator) => match mode {
-                    TraversalMode::Types => {
-                        let segments = &combinator.name.segments;
-
-                        if segments.len() == 1 {
-                            match segments[0].as_str() {
-                                "boolFalse" |
-                                "boolTrue"  |
-                                "true"      |
-                                "vector"    => continue,
-                                _ => (),
-                            }
-                        }
-
-                        let mut constructor_def_ns = &mut constructor_def_ns;
-
-                        for (i, name_segment) in segments.iter().enumerate() {
-                            if i == segments.len() - 1 {
-                                constructor_def_ns.constructor_defs
-                                    .push(ConstructorDef::from_tl_combinator(combinator));
-                            } else {
-                                // Avoid cloning `Ident`s. Otherwise it could've been done as:
-                                //
-                                //     constructor_def_ns = {constructor_def_ns}
-                                //         .namespaces
-                                //         .entry(name_segment.clone())
-                                //         .or_insert(Self::with_tl_ident(name_segment.clone()));
-                                if !constructor_def_ns.namespaces.contains_key(name_segment) {
-                                    constructor_def_ns.namespaces.insert(
-                                        name_segment.clone(),
-                                        Self::with_tl_ident(name_segment.clone()),
-                                    );
-                                }
-
-                                constructor_def_ns = {constructor_def_ns}
-                                    .namespaces
-                                    .get_mut(name_segment)
-                                    .unwrap();
-                            }
-                        }
-                    },
-                    TraversalMode::Functions => (),
-                },
-                tlsn::Item::Delimiter(ref delimiter) => match (mode, &delimiter.delimiter) {
-                    (TraversalMode::Types, &tlsn::Delimiter::Functions(_)) => {
-                        mode = TraversalMode::Functions;
-                    },
-                    (TraversalMode::Functions, &tlsn::Delimiter::Types(_)) => {
-                        mode = TraversalMode::Types;
-                    },
-                    _ => panic!("wrong delimiter"),  // FIXME
-                },
-                tlsn::Item::Layer(_)   |
-                tlsn::Item::Comment(_) => (),
+        for combinator in sections.combinators(tlsn::SectionKind::Types) {
+            let segments = &combinator.name.segments;
+
+            if segments.len() == 1 {
+                match segments[0].as_str() {
+                    "boolFalse" |
+                    "boolTrue"  |
+                    "true"      |
+                    "vector"    => continue,
+                    _ => (),
+                }
+            }
+
+            let mut constructor_def_ns = &mut constructor_def_ns;
+
+            for (i, name_segment) in segments.iter().enumerate() {
+                if i == segments.len() - 1 {
+                    constructor_def_ns.constructor_defs
+                        .push(ConstructorDef::from_tl_combinator(combinator));
+                } else {
+                    constructor_def_ns = {constructor_def_ns}.namespace_mut(name_segment);
+                }
             }
         }
 
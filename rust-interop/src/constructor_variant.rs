@@ -1,10 +1,7 @@
-use std::iter;
-
 use tl_lang_syn as tlsn;
 
 use ::ident::Ident;
 use ::path::Path;
-use ::utils;
 
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -27,15 +24,12 @@ impl ConstructorVariant {
             Ident::with_str(last_segment.as_str()).unwrap()  // FIXME
         };
         let id = combinator_id.as_ref().unwrap().id.id;  // FIXME
-        let struct_path = Path(tlsn::ParameterizedPath {
-            path: tlsn::Path {
-                segments: iter::once(utils::tl_ident_span_zeroed("schema").unwrap())
-                    .chain(iter::once(utils::tl_ident_span_zeroed("constructors").unwrap()))
-                    .chain(name.segments.iter().cloned())
-                    .collect(),
-            },
-            args: None,
-        });
+        let struct_path = {
+            let mut path = tlsn::Path::new("schema.constructors").unwrap();
+            path.segments.extend(name.segments.iter().map(tlsn::Ident::to_owned));
+
+            Path(tlsn::ParameterizedPath::from(path))
+        };
 
         Self { name: cs_name, id, struct_path }
     }
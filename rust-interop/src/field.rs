@@ -45,15 +45,32 @@ impl Field {
                 } = *conditional;
 
                 let res = match *conditional_param_def {
-                    None => Self::from_ident_tl_ty(Ident(var_ident.clone()), ty),
+                    None => Self::from_ident_tl_ty(Ident(var_ident.to_owned()), ty),
                     Some(_) => None,  // FIXME
                 };
 
                 Either::Left(res.into_iter())
             },
-            tlsn::Param::Repeated(_) => unimplemented!(),
+            tlsn::Param::Repeated(ref repeated) => {
+                let ty = Type::Repeated(Self::from_tl_params(&repeated.params));
+
+                let res = match repeated.param_repeated_ident {
+                    Some(ref repeated_ident) => Field::Named(FieldNamed {
+                        name: Ident(repeated_ident.var_ident.to_owned()),
+                        ty,
+                    }),
+                    None => {
+                        let index = unnamed_field_index;
+                        unnamed_field_index += 1;
+
+                        Field::Unnamed(FieldUnnamed { index, ty })
+                    },
+                };
+
+                Either::Left(Some(res).into_iter())
+            },
             tlsn::Param::WithParen(ref with_paren) => {
-                Either::Right(with_paren.var_idents.iter().cloned().filter_map(move |var_ident| {
+                Either::Right(with_paren.var_idents.iter().map(tlsn::Ident::to_owned).filter_map(move |var_ident| {
                     Self::from_ident_tl_ty(Ident(var_ident), &with_paren.ty)
                 }))
             },
@@ -5,14 +5,13 @@ use tl_lang_syn as tlsn;
 use ::field::Field;
 use ::ident::Ident;
 use ::path::Path;
-use ::utils::TraversalMode;
 
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FunctionDefNamespace {
     pub name: Ident,
     pub function_defs: Vec<FunctionDef>,
-    pub namespaces: HashMap<tlsn::Ident, FunctionDefNamespace>,
+    pub namespaces: HashMap<tlsn::Ident<'static>, FunctionDefNamespace>,
 }
 
 impl FunctionDefNamespace {
@@ -20,7 +19,7 @@ impl FunctionDefNamespace {
         Ident::with_str(name).map(Self::with_ident)
     }
 
-    fn with_tl_ident(name: tlsn::Ident) -> Self {
+    fn with_tl_ident(name: tlsn::Ident<'static>) -> Self {
         Self::with_ident(Ident(name))
     }
 
@@ -32,55 +31,30 @@ impl FunctionDefNamespace {
         }
     }
 
-    pub fn from_tl_items(items: &[tlsn::Item]) -> Self {
-        let mut mode = TraversalMode::Types;
+    /// Get the child namespace named `name`, creating it if it doesn't exist.
+    fn namespace_mut(&mut self, name: &tlsn::Ident) -> &mut Self {
+        if !self.namespaces.contains_key(&name.symbol()) {
+            let name = name.to_owned();
+            self.namespaces.insert(name.clone(), Self::with_tl_ident(name));
+        }
+
+        self.namespaces.get_mut(&name.symbol()).unwrap()
+    }
+
+    pub fn from_tl_sections(sections: &tlsn::Sections) -> Self {
         let mut function_def_ns = Self::with_str("functions").unwrap();  // FIXME
 
-        for item in items {
-            match *item {
-                tlsn::Item::Combinator(ref combinator) => match mode {
-                    TraversalMode::Types => (),
-                    TraversalMode::Functions => {
-                        let segments = &combinator.name.segments;
-                        let mut function_def_ns = &mut function_def_ns;
-
-                        for (i, name_segment) in segments.iter().enumerate() {
-                            if i == segments.len() - 1 {
-                                function_def_ns.function_defs
-                                    .push(FunctionDef::from_tl_combinator(combinator));
-                            } else {
-                                // Avoid cloning `Ident`s. Otherwise it could've been done as:
-                                //
-                                //     function_def_ns = {function_def_ns}
-                                //         .namespaces
-                                //         .entry(name_segment.clone())
-                                //         .or_insert(Self::with_tl_ident(name_segment.clone()));
-                                if !function_def_ns.namespaces.contains_key(name_segment) {
-                                    function_def_ns.namespaces.insert(
-                                        name_segment.clone(),
-                                        Self::with_tl_ident(name_segment.clone()),
-                                    );
-                                }
-
-                                function_def_ns = {function_def_ns}
-                                    .namespaces
-                                    .get_mut(name_segment)
-                                    .unwrap();
-                            }
-                        }
-                    },
-                },
-                tlsn::Item::Delimiter(ref delimiter) => match (mode, &delimiter.delimiter) {
-                    (TraversalMode::Types, &tlsn::Delimiter::Functions(_)) => {
-                        mode = TraversalMode::Functions;
-                    },
-                    (TraversalMode::Functions, &tlsn::Delimiter::Types(_)) => {
-                        mode = TraversalMode::Types;
-                    },
-                    _ => panic!("wrong delimiter"),  // FIXME
-                },
-                tlsn::Item::Layer(_)   |
-                tlsn::Item::Comment(_) => (),
+        for combinator in sections.combinators(tlsn::SectionKind::Functions) {
+            let segments = &combinator.name.segments;
+            let mut function_def_ns = &mut function_def_ns;
+
+            for (i, name_segment) in segments.iter().enumerate() {
+                if i == segments.len() - 1 {
+                    function_def_ns.function_defs
+                        .push(FunctionDef::from_tl_combinator(combinator));
+                } else {
+                    function_def_ns = {function_def_ns}.namespace_mut(name_segment);
+                }
             }
         }
 
@@ -111,27 +85,13 @@ impl FunctionDef {
 
         let name = Ident::from_path_last_segment(name).unwrap();  // FIXME
         let id = combinator_id.as_ref().unwrap().id.id;  // FIXME
-        let generics = opt_params.iter().filter_map(|opt_param| match opt_param.ty {
-            tlsn::Type::ParameterizedPath(ref parameterized_path) => {
-                match parameterized_path.safe_parameterized_path {
-                    tlsn::SafeParameterizedPath::SpaceImmune(ref space_immune) => {
-                        let var_idents = &opt_param.var_idents;
-                        let args = &space_immune.args;
-                        let segments = &space_immune.path.segments;
-
-                        if args.is_none() && segments.len() == 1 && segments[0].as_str() == "Type" {
-                            return Some(var_idents.iter().cloned().map(Ident));
-                        }
-
-                        None
-                    },
-                    _ => None,
-                }
-            },
-            _ => None,
-        }).flat_map(|idents| idents).collect();
+        let generics = opt_params.iter().filter_map(|opt_param| match opt_param.kind() {
+            tlsn::OptParamKind::Type => Some(opt_param.var_idents.iter().map(tlsn::Ident::to_owned).map(Ident)),
+            tlsn::OptParamKind::Nat |
+            tlsn::OptParamKind::Other => None,
+        }).flatten().collect();
         let fields = Field::from_tl_params(params);
-        let return_type = Path(result_type.clone());
+        let return_type = Path(result_type.to_owned());
 
         Self { name, id, generics, fields, return_type }
     }
@@ -4,7 +4,7 @@ use ::utils;
 
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Ident(pub tlsn::Ident);
+pub struct Ident(pub tlsn::Ident<'static>);
 
 impl Ident {
     pub(crate) fn with_str(string: &str) -> Option<Self> {
@@ -13,7 +13,7 @@ impl Ident {
 
     pub(crate) fn from_path_last_segment(path: &tlsn::Path) -> Option<Self> {
         path.segments.last().map(|pair| {
-            Ident(pair.into_value().clone())
+            Ident(pair.into_value().to_owned())
         })
     }
 
@@ -2,4 +2,4 @@ use tl_lang_syn as tlsn;
 
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Path(pub tlsn::ParameterizedPath);
+pub struct Path(pub tlsn::ParameterizedPath<'static>);
@@ -11,6 +11,9 @@ pub struct Schema {
     pub type_def_ns: TypeDefNamespace,
     pub constructor_def_ns: ConstructorDefNamespace,
     pub function_def_ns: FunctionDefNamespace,
+    /// Redundant section delimiters found in the file, which don't prevent
+    /// generating code but likely are mistakes in the schema.
+    pub section_diagnostics: Vec<tlsn::SectionDiagnostic>,
 }
 
 impl Schema {
@@ -27,11 +30,14 @@ impl Schema {
             None
         }
 
+        let sections = tl_file.sections();
+
         Schema {
             layer: get_layer(items).unwrap_or(0),
-            type_def_ns: TypeDefNamespace::from_tl_items(items),
-            constructor_def_ns: ConstructorDefNamespace::from_tl_items(items),
-            function_def_ns: FunctionDefNamespace::from_tl_items(items),
+            type_def_ns: TypeDefNamespace::from_tl_sections(&sections),
+            constructor_def_ns: ConstructorDefNamespace::from_tl_sections(&sections),
+            function_def_ns: FunctionDefNamespace::from_tl_sections(&sections),
+            section_diagnostics: sections.diagnostics,
         }
     }
 }
@@ -1,14 +1,8 @@
 use tl_lang_syn as tlsn;
 
 
-pub(crate) enum TraversalMode {
-    Types,
-    Functions,
-}
-
-
-pub(crate) fn tl_ident_span_zeroed(string: &str) -> Option<tlsn::Ident> {
-    tlsn::Ident::new(tlsn::span::Span::zeroed(), string)
+pub(crate) fn tl_ident_span_zeroed(string: &str) -> Option<tlsn::Ident<'static>> {
+    tlsn::Ident::new(tlsn::span::Span::zeroed(), string.to_owned())
 }
 
 
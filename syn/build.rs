@@ -13,5 +13,6 @@ fn query_print_cfg(version: &str, flag: &str) {
 }
 
 fn main() {
+    println!("cargo:rustc-check-cfg=cfg(stable_nonzero_types)");
     query_print_cfg("1.28.0", "stable_nonzero_types");
 }
@@ -5,7 +5,7 @@ macro_attr_many! {
     /// An index pointing to the n-th bit of a `#` value (or, an `u32` value).
     #[cfg_derive!(Clone, Debug)]
     pub struct BitIndex {
-        span: Span,
+        pub(crate) span: Span,
         index: u8,
     }
 }
@@ -71,6 +71,36 @@ mod hash_impls {
     }
 }
 
+#[cfg(feature = "serde-impls")]
+mod serde_impls {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::de::Error;
+
+    use super::*;
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(rename = "BitIndex")]
+    struct BitIndexRepr {
+        span: Span,
+        index: u8,
+    }
+
+    impl Serialize for BitIndex {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            BitIndexRepr { span: self.span, index: self.index }.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for BitIndex {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<BitIndex, D::Error> {
+            let BitIndexRepr { span, index } = BitIndexRepr::deserialize(deserializer)?;
+
+            BitIndex::new(span, index)
+                .ok_or_else(|| D::Error::custom(format!("bit index out of range: {}", index)))
+        }
+    }
+}
+
 mod spanned {
     use super::*;
     use span::Span;
@@ -89,23 +119,34 @@ mod spanned {
 #[cfg(feature = "parsing")]
 mod parsing {
     use super::*;
+    use nom;
+
     use cursor::Cursor;
+    use error::ErrorCode;
     use synom::Synom;
     use synom::private::Sealed;
     use utils::parsing::is_decimal_digit;
 
     impl Sealed for BitIndex {}
 
-    impl Synom for BitIndex {
-        named!(parse_cursor(Cursor) -> BitIndex, do_parse!(
-            index_str_cursor: take_while!(is_decimal_digit) >>
-            index_raw: map_res!(value!(index_str_cursor.to_str()), str::parse) >>
-            index: verify!(value!(index_raw), is_valid_nat_bit_index) >>
+    impl<'a> Synom<'a> for BitIndex {
+        named!(parse_cursor(Cursor<'a>) -> BitIndex, do_parse!(
+            index_str_cursor: take_while1!(is_decimal_digit) >>
+            index: call!(parse_bit_index, index_str_cursor) >>
             span: value!(index_str_cursor.span()) >>
 
             (BitIndex { span, index })
         ));
     }
+
+    // Fail hard on out-of-range indices, since nothing else can start with
+    // a digit after `.`
+    fn parse_bit_index<'a>(input: Cursor<'a>, index_str_cursor: Cursor<'a>) -> nom::IResult<Cursor<'a>, u8> {
+        match index_str_cursor.to_str().parse() {
+            Ok(index) if is_valid_nat_bit_index(index) => Ok((input, index)),
+            _ => ErrorCode::BitIndexOutOfRange.failure(index_str_cursor),
+        }
+    }
 }
 
 #[cfg(feature = "printing")]
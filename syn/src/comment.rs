@@ -1,29 +1,31 @@
+use std::borrow::Cow;
+
 use span::Span;
 use token::{SlashAsterisk, SlashSlash};
 
 
 macro_attr_many! {
     /// A single-line or multiline comment.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub enum Comment {
-        SingleLine(CommentSingleLine),
-        MultiLine(CommentMultiLine),
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub enum Comment<'a> {
+        SingleLine(CommentSingleLine<'a>),
+        MultiLine(CommentMultiLine<'a>),
     }
 
     /// A `//...` comment spanning a single line.
-    #[cfg_derive!(Clone, Debug)]
-    pub struct CommentSingleLine {
+    #[cfg_derive!(Clone, Debug, Deserialize, Serialize)]
+    pub struct CommentSingleLine<'a> {
         pub slash_slash_token: SlashSlash,
         pub content_span: Span,
-        pub content: String,
+        pub content: Cow<'a, str>,
     }
 
     /// A `/*...*/` comment spanning multiple lines.
-    #[cfg_derive!(Clone, Debug)]
-    pub struct CommentMultiLine {
+    #[cfg_derive!(Clone, Debug, Deserialize, Serialize)]
+    pub struct CommentMultiLine<'a> {
         pub slash_asterisk_token: SlashAsterisk,
         pub content_span: Span,
-        pub content: String,
+        pub content: Cow<'a, str>,
     }
 }
 
@@ -32,17 +34,17 @@ macro_attr_many! {
 mod eq_impls {
     use super::*;
 
-    impl Eq for CommentSingleLine {}
-    impl Eq for CommentMultiLine {}
+    impl<'a> Eq for CommentSingleLine<'a> {}
+    impl<'a> Eq for CommentMultiLine<'a> {}
 
-    impl PartialEq for CommentSingleLine {
-        fn eq(&self, other: &CommentSingleLine) -> bool {
+    impl<'a> PartialEq for CommentSingleLine<'a> {
+        fn eq(&self, other: &CommentSingleLine<'a>) -> bool {
             self.content == other.content
         }
     }
 
-    impl PartialEq for CommentMultiLine {
-        fn eq(&self, other: &CommentMultiLine) -> bool {
+    impl<'a> PartialEq for CommentMultiLine<'a> {
+        fn eq(&self, other: &CommentMultiLine<'a>) -> bool {
             self.content == other.content
         }
     }
@@ -55,13 +57,13 @@ mod hash_impls {
 
     use super::*;
 
-    impl Hash for CommentSingleLine {
+    impl<'a> Hash for CommentSingleLine<'a> {
         fn hash<H: Hasher>(&self, state: &mut H) {
             self.content.hash(state);
         }
     }
 
-    impl Hash for CommentMultiLine {
+    impl<'a> Hash for CommentMultiLine<'a> {
         fn hash<H: Hasher>(&self, state: &mut H) {
             self.content.hash(state);
         }
@@ -75,11 +77,11 @@ mod spanned {
     use spanned::Spanned;
     use spanned::private::Sealed;
 
-    impl Sealed for Comment {}
-    impl Sealed for CommentSingleLine {}
-    impl Sealed for CommentMultiLine {}
+    impl<'a> Sealed for Comment<'a> {}
+    impl<'a> Sealed for CommentSingleLine<'a> {}
+    impl<'a> Sealed for CommentMultiLine<'a> {}
 
-    impl Spanned for Comment {
+    impl<'a> Spanned for Comment<'a> {
         fn span(&self) -> Span {
             match *self {
                 Comment::SingleLine(ref t) => t.span(),
@@ -88,14 +90,14 @@ mod spanned {
         }
     }
 
-    impl Spanned for CommentSingleLine {
+    impl<'a> Spanned for CommentSingleLine<'a> {
         fn span(&self) -> Span {
             self.slash_slash_token.span()
                 .to(self.content_span)
         }
     }
 
-    impl Spanned for CommentMultiLine {
+    impl<'a> Spanned for CommentMultiLine<'a> {
         fn span(&self) -> Span {
             self.slash_asterisk_token.span()
                 .to(self.content_span)
@@ -104,48 +106,96 @@ mod spanned {
 }
 
 
+mod owned {
+    use super::*;
+    use owned::IntoOwned;
+    use owned::private::Sealed;
+
+    impl_to_owned!(Comment, CommentSingleLine, CommentMultiLine);
+
+    impl<'a> Sealed for Comment<'a> {}
+    impl<'a> Sealed for CommentSingleLine<'a> {}
+    impl<'a> Sealed for CommentMultiLine<'a> {}
+
+    impl<'a> IntoOwned for Comment<'a> {
+        type Owned = Comment<'static>;
+
+        fn into_owned(self) -> Comment<'static> {
+            match self {
+                Comment::SingleLine(t) => Comment::SingleLine(t.into_owned()),
+                Comment::MultiLine(t) => Comment::MultiLine(t.into_owned()),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for CommentSingleLine<'a> {
+        type Owned = CommentSingleLine<'static>;
+
+        fn into_owned(self) -> CommentSingleLine<'static> {
+            CommentSingleLine {
+                slash_slash_token: self.slash_slash_token,
+                content_span: self.content_span,
+                content: Cow::Owned(self.content.into_owned()),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for CommentMultiLine<'a> {
+        type Owned = CommentMultiLine<'static>;
+
+        fn into_owned(self) -> CommentMultiLine<'static> {
+            CommentMultiLine {
+                slash_asterisk_token: self.slash_asterisk_token,
+                content_span: self.content_span,
+                content: Cow::Owned(self.content.into_owned()),
+            }
+        }
+    }
+}
+
+
 #[cfg(feature = "parsing")]
 mod parsing {
-    use nom;
-
     use super::*;
     use cursor::Cursor;
     use synom::Synom;
     use synom::private::Sealed;
 
-    impl Sealed for Comment {}
-    impl Sealed for CommentSingleLine {}
-    impl Sealed for CommentMultiLine {}
+    impl<'a> Sealed for Comment<'a> {}
+    impl<'a> Sealed for CommentSingleLine<'a> {}
+    impl<'a> Sealed for CommentMultiLine<'a> {}
 
-    impl Synom for Comment {
-        named!(parse_cursor(Cursor) -> Comment, alt_complete!(
+    impl<'a> Synom<'a> for Comment<'a> {
+        named!(parse_cursor(Cursor<'a>) -> Comment<'a>, alt_complete!(
             tlsyn!(CommentSingleLine) => { Comment::SingleLine }
             |
             tlsyn!(CommentMultiLine) => { Comment::MultiLine }
         ));
     }
 
-    impl Synom for CommentSingleLine {
-        named!(parse_cursor(Cursor) -> CommentSingleLine, do_parse!(
+    impl<'a> Synom<'a> for CommentSingleLine<'a> {
+        named!(parse_cursor(Cursor<'a>) -> CommentSingleLine<'a>, do_parse!(
             slash_slash_token: tlsyn!(SlashSlash) >>
-            content: call!(nom::not_line_ending) >>
+            // Unlike `nom::not_line_ending`, this doesn't require a line
+            // ending to be present, so that a comment can end the input
+            content: take_till!(|c| c == '\r' || c == '\n') >>
 
             (CommentSingleLine {
                 slash_slash_token,
                 content_span: content.span(),
-                content: content.to_str().to_owned(),
+                content: Cow::Borrowed(content.to_str()),
             })
         ));
     }
 
-    impl Synom for CommentMultiLine {
-        named!(parse_cursor(Cursor) -> CommentMultiLine, do_parse!(
+    impl<'a> Synom<'a> for CommentMultiLine<'a> {
+        named!(parse_cursor(Cursor<'a>) -> CommentMultiLine<'a>, do_parse!(
             content: slash_asterisks!() >>
 
             (CommentMultiLine {
                 slash_asterisk_token: content.0,
                 content_span: content.1.span(),
-                content: content.1.to_str().to_owned(),
+                content: Cow::Borrowed(content.1.to_str()),
             })
         ));
     }
@@ -160,11 +210,11 @@ mod printing {
     use print::Print;
     use print::private::Sealed;
 
-    impl Sealed for Comment {}
-    impl Sealed for CommentSingleLine {}
-    impl Sealed for CommentMultiLine {}
+    impl<'a> Sealed for Comment<'a> {}
+    impl<'a> Sealed for CommentSingleLine<'a> {}
+    impl<'a> Sealed for CommentMultiLine<'a> {}
 
-    impl Print for Comment {
+    impl<'a> Print for Comment<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             match *self {
                 Comment::SingleLine(ref t) => t.print(f),
@@ -173,7 +223,7 @@ mod printing {
         }
     }
 
-    impl Print for CommentSingleLine {
+    impl<'a> Print for CommentSingleLine<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             f.write_str("//")?;
             fmt::Display::fmt(&self.content, f)?;
@@ -182,7 +232,7 @@ mod printing {
         }
     }
 
-    impl Print for CommentMultiLine {
+    impl<'a> Print for CommentMultiLine<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             SlashAsterisk::print(f, |f| {
                 fmt::Display::fmt(&self.content, f)
@@ -215,11 +265,11 @@ mod tests {
         FA1: Fn(&CommentSingleLine, &CommentSingleLine),
         FA2: Fn(&CommentSingleLine, &CommentSingleLine),
     {
-        fn new_comment_single_line(span: Span, content: &str) -> CommentSingleLine {
+        fn new_comment_single_line(span: Span, content: &str) -> CommentSingleLine<'_> {
             CommentSingleLine {
                 slash_slash_token: SlashSlash(span),
                 content_span: span,
-                content: content.to_owned(),
+                content: Cow::Borrowed(content),
             }
         }
 
@@ -228,8 +278,8 @@ mod tests {
         for content1 in &contents {
             for content2 in &contents {
                 test_span_permutations(
-                    |span1| new_comment_single_line(span1, *content1),
-                    |span2| new_comment_single_line(span2, *content2),
+                    |span1| new_comment_single_line(span1, content1),
+                    |span2| new_comment_single_line(span2, content2),
                     &test_eq,
                     &assert_when_eq,
                     &assert_when_ne,
@@ -249,11 +299,11 @@ mod tests {
         FA1: Fn(&CommentMultiLine, &CommentMultiLine),
         FA2: Fn(&CommentMultiLine, &CommentMultiLine),
     {
-        fn new_comment_multi_line(span: Span, content: &str) -> CommentMultiLine {
+        fn new_comment_multi_line(span: Span, content: &str) -> CommentMultiLine<'_> {
             CommentMultiLine {
                 slash_asterisk_token: SlashAsterisk(span),
                 content_span: span,
-                content: content.to_owned(),
+                content: Cow::Borrowed(content),
             }
         }
 
@@ -262,8 +312,8 @@ mod tests {
         for content1 in &contents {
             for content2 in &contents {
                 test_span_permutations(
-                    |span1| new_comment_multi_line(span1, *content1),
-                    |span2| new_comment_multi_line(span2, *content2),
+                    |span1| new_comment_multi_line(span1, content1),
+                    |span2| new_comment_multi_line(span2, content2),
                     &test_eq,
                     &assert_when_eq,
                     &assert_when_ne,
@@ -5,6 +5,7 @@ use std::str;
 
 use nom::{self, Offset};
 
+use id::IdValidation;
 use span::Span;
 
 
@@ -22,13 +23,30 @@ use span::Span;
 pub struct Cursor<'a> {
     offset: usize,
     remaining: &'a str,
+    id_validation: IdValidation,
 }
 
 impl<'a> Cursor<'a> {
     /// Create a new `Cursor` from the given `input`. The created cursor points
     /// to the first byte of the string.
     pub fn new(input: &'a str) -> Cursor<'a> {
-        Cursor { offset: 1, remaining: input }
+        Cursor::with_id_validation(input, IdValidation::Lenient)
+    }
+
+    /// Create a new `Cursor` from the given `input` which validates combinator
+    /// ids as specified by `id_validation`. The created cursor points to the
+    /// first byte of the string.
+    pub fn with_id_validation(input: &'a str, id_validation: IdValidation) -> Cursor<'a> {
+        Cursor { offset: 1, remaining: input, id_validation }
+    }
+
+    /// Create a new `Cursor` from the given `input` which is a part of a
+    /// larger source text starting at the given 1-based `offset`, so that
+    /// spans point into the larger text.
+    pub(crate) fn with_offset(input: &'a str, offset: usize, id_validation: IdValidation) -> Cursor<'a> {
+        assert!(offset >= 1);
+
+        Cursor { offset, remaining: input, id_validation }
     }
 
     /// Return the span of the whole cursor.
@@ -50,6 +68,11 @@ impl<'a> Cursor<'a> {
         self.remaining
     }
 
+    /// Get the way combinator ids are validated while parsing this cursor.
+    pub fn id_validation(self) -> IdValidation {
+        self.id_validation
+    }
+
     // ===== Common implementation details ===== //
 
     fn impl_take_split(&self, count: usize) -> (Cursor<'a>, Cursor<'a>) {
@@ -58,11 +81,13 @@ impl<'a> Cursor<'a> {
         let cursor_before = Cursor {
             offset: self.offset,
             remaining: before,
+            id_validation: self.id_validation,
         };
 
         let cursor_after = Cursor {
             offset: self.offset + count,
             remaining: after,
+            id_validation: self.id_validation,
         };
 
         (cursor_after, cursor_before)  // Notice the swapped order here
@@ -151,6 +176,7 @@ impl<'a> nom::InputTake for Cursor<'a> {
         Cursor {
             offset: self.offset,
             remaining: &self.remaining[0..count],
+            id_validation: self.id_validation,
         }
     }
 
@@ -192,6 +218,7 @@ macro_rules! slice_range_impl {
                 Cursor {
                     offset: slice_offset,
                     remaining: sliced_remaining,
+                    id_validation: self.id_validation,
                 }
             }
         }
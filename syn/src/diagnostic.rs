@@ -0,0 +1,373 @@
+use std::fmt::{self, Write};
+
+use line_index::{LineColumn, LineIndex};
+use section::SectionDiagnostic;
+use source_database::FileId;
+use span::{Span, SpanState};
+
+
+/// How serious a diagnostic is.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+    Help,
+}
+
+/// Whether a label marks the cause of a diagnostic or provides context.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LabelStyle {
+    /// Underlined with `^`.
+    Primary,
+    /// Underlined with `-`.
+    Secondary,
+}
+
+/// Whether rendered diagnostics are coloured with ANSI escape codes.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ColorChoice {
+    Plain,
+    Ansi,
+}
+
+/// A message attached to a span of source text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Label {
+    pub style: LabelStyle,
+    /// The file `span` points into. `None` stands for the file the whole
+    /// diagnostic is rendered against.
+    pub file: Option<FileId>,
+    pub span: Span,
+    pub message: String,
+}
+
+/// An error, a warning or any other message about source text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+}
+
+
+impl Label {
+    /// Create a new primary label.
+    pub fn primary<S: Into<String>>(span: Span, message: S) -> Label {
+        Label { style: LabelStyle::Primary, file: None, span, message: message.into() }
+    }
+
+    /// Create a new secondary label.
+    pub fn secondary<S: Into<String>>(span: Span, message: S) -> Label {
+        Label { style: LabelStyle::Secondary, file: None, span, message: message.into() }
+    }
+
+    /// Make this label point into the file with the given `file_id`.
+    pub fn with_file(mut self, file_id: FileId) -> Label {
+        self.file = Some(file_id);
+        self
+    }
+}
+
+impl Diagnostic {
+    /// Create a new `Diagnostic` without labels and notes.
+    pub fn new<S: Into<String>>(severity: Severity, message: S) -> Diagnostic {
+        Diagnostic { severity, message: message.into(), labels: Vec::new(), notes: Vec::new() }
+    }
+
+    /// Create a new error `Diagnostic`.
+    pub fn error<S: Into<String>>(message: S) -> Diagnostic {
+        Diagnostic::new(Severity::Error, message)
+    }
+
+    /// Create a new warning `Diagnostic`.
+    pub fn warning<S: Into<String>>(message: S) -> Diagnostic {
+        Diagnostic::new(Severity::Warning, message)
+    }
+
+    /// Add a label to this diagnostic.
+    pub fn with_label(mut self, label: Label) -> Diagnostic {
+        self.labels.push(label);
+        self
+    }
+
+    /// Add a note to this diagnostic.
+    pub fn with_note<S: Into<String>>(mut self, note: S) -> Diagnostic {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Render this diagnostic against the source text of `index` which is
+    /// referred to as `source_name`.
+    ///
+    /// Labels with zeroed spans, spans which don't fit in the source text or
+    /// spans in other files are skipped. A label spanning several lines is
+    /// only shown on its first line.
+    pub fn render(&self, source_name: &str, index: &LineIndex, color: ColorChoice) -> String {
+        self.render_sources(&[(None, source_name, index)], color)
+    }
+
+    /// Render this diagnostic against several named source texts. Labels
+    /// without a file refer to the first one.
+    pub(crate) fn render_sources(
+        &self,
+        sources: &[(Option<FileId>, &str, &LineIndex)],
+        color: ColorChoice,
+    ) -> String {
+        let mut out = String::new();
+        self.write(&mut out, sources, color).unwrap();  // Writing to `String` never fails
+        out
+    }
+
+    fn write(
+        &self,
+        out: &mut String,
+        sources: &[(Option<FileId>, &str, &LineIndex)],
+        color: ColorChoice,
+    ) -> fmt::Result {
+        let painter = Painter { color };
+
+        let mut labels = self.labels.iter()
+            .filter_map(|label| {
+                let source_index = match label.file {
+                    None => 0,
+                    Some(file) => sources.iter().position(|&(source_file, _, _)| source_file == Some(file))?,
+                };
+                let (begin, end) = match label.span.state() {
+                    SpanState::Zeroed => return None,
+                    SpanState::Normal => sources[source_index].2.span_line_columns(label.span)?,
+                };
+
+                Some((source_index, begin, end, label))
+            })
+            .collect::<Vec<_>>();
+        labels.sort_by_key(|&(source_index, begin, _, label)| {
+            (source_index, begin.line, label.style == LabelStyle::Secondary, begin.column)
+        });
+
+        let gutter_width = labels.iter()
+            .map(|&(_, begin, _, _)| begin.line.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let gutter = " ".repeat(gutter_width);
+
+        writeln!(out, "{}{}", painter.paint(self.severity.color_code(), &self.severity.to_string()),
+            painter.paint(BOLD, &format!(": {}", self.message)))?;
+
+        let mut previous_location = None;
+        for &(source_index, begin, end, label) in &labels {
+            let (_, source_name, index) = sources[source_index];
+            let line = index.line(begin.line).unwrap();  // Must exist since the span fits
+
+            if previous_location.map(|(source_index, _)| source_index) != Some(source_index) {
+                let arrow = if previous_location.is_none() {
+                    "-->"
+                } else {
+                    writeln!(out, "{} {}", gutter, painter.paint(GUTTER, "|"))?;
+                    ":::"
+                };
+
+                // Point to the primary label of this source, if there is one
+                let location = labels.iter()
+                    .filter(|&&(other_source_index, _, _, _)| other_source_index == source_index)
+                    .find(|&&(_, _, _, label)| label.style == LabelStyle::Primary)
+                    .map_or(begin, |&(_, begin, _, _)| begin);
+
+                writeln!(out, "{}{} {}:{}:{}", gutter, painter.paint(GUTTER, arrow),
+                    source_name, location.line, location.column)?;
+                writeln!(out, "{} {}", gutter, painter.paint(GUTTER, "|"))?;
+            }
+
+            if previous_location != Some((source_index, begin.line)) {
+                let line_number = format!("{:>width$} |", begin.line, width = gutter_width);
+                writeln!(out, "{} {}", painter.paint(GUTTER, &line_number), line)?;
+            }
+            previous_location = Some((source_index, begin.line));
+
+            let (underline_char, code) = match label.style {
+                LabelStyle::Primary => ('^', self.severity.color_code()),
+                LabelStyle::Secondary => ('-', SECONDARY),
+            };
+
+            let mut underline = (0..underline_width(line, begin, end))
+                .map(|_| underline_char)
+                .collect::<String>();
+            if !label.message.is_empty() {
+                underline.push(' ');
+                underline.push_str(&label.message);
+            }
+
+            writeln!(out, "{} {} {}{}", gutter, painter.paint(GUTTER, "|"),
+                underline_padding(line, begin), painter.paint(code, &underline))?;
+        }
+
+        if !labels.is_empty() && !self.notes.is_empty() {
+            writeln!(out, "{} {}", gutter, painter.paint(GUTTER, "|"))?;
+        }
+
+        for note in &self.notes {
+            writeln!(out, "{} {} {} {}", gutter, painter.paint(GUTTER, "="), painter.paint(BOLD, "note:"), note)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Severity {
+    fn color_code(self) -> &'static str {
+        match self {
+            Severity::Error => "1;31",
+            Severity::Warning => "1;33",
+            Severity::Note => "1;32",
+            Severity::Help => "1;36",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+            Severity::Help => "help",
+        })
+    }
+}
+
+impl<'a> From<&'a SectionDiagnostic> for Diagnostic {
+    fn from(diagnostic: &'a SectionDiagnostic) -> Diagnostic {
+        Diagnostic::warning(diagnostic.to_string())
+            .with_label(Label::primary(diagnostic.span, "redundant delimiter"))
+            .with_label(Label::secondary(diagnostic.previous_span, "section starts here"))
+    }
+}
+
+#[cfg(feature = "parsing")]
+mod parsing {
+    use super::*;
+    use error::{ParseError, ParseErrorKind};
+
+    impl<'a> From<&'a ParseError> for Diagnostic {
+        fn from(error: &'a ParseError) -> Diagnostic {
+            let label = match *error.kind() {
+                ParseErrorKind::UnexpectedToken { found: Some(_) } => "unexpected token".to_owned(),
+                ParseErrorKind::UnexpectedToken { found: None } => "unexpected end of input".to_owned(),
+                ParseErrorKind::ExpectedOneOf { ref expected, .. } => format!("expected {}", expected.join(" or ")),
+                ParseErrorKind::InvalidId { .. } => "invalid id".to_owned(),
+                ParseErrorKind::BitIndexOutOfRange { .. } => "out of range".to_owned(),
+                ParseErrorKind::UnterminatedComment => "comment starts here".to_owned(),
+                ParseErrorKind::TrailingInput { .. } => "unexpected input".to_owned(),
+            };
+
+            Diagnostic::error(error.to_string())
+                .with_label(Label::primary(error.span(), label))
+        }
+    }
+}
+
+
+const BOLD: &str = "1";
+const GUTTER: &str = "1;34";
+const SECONDARY: &str = "1;34";
+
+struct Painter {
+    color: ColorChoice,
+}
+
+impl Painter {
+    fn paint(&self, code: &str, text: &str) -> String {
+        match self.color {
+            ColorChoice::Plain => text.to_owned(),
+            ColorChoice::Ansi => format!("\x1b[{}m{}\x1b[0m", code, text),
+        }
+    }
+}
+
+/// Return the whitespace which puts an underline right below `begin` in
+/// `line`, keeping tabs so that the alignment is the same as in the line.
+fn underline_padding(line: &str, begin: LineColumn) -> String {
+    line.chars()
+        .take(begin.column - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect()
+}
+
+/// Return the number of characters to underline, which is at least one so
+/// that empty spans are visible as well.
+fn underline_width(line: &str, begin: LineColumn, end: LineColumn) -> usize {
+    let end_column = if end.line == begin.line {
+        end.column
+    } else {
+        line.chars().count() + 1
+    };
+
+    end_column.saturating_sub(begin.column).max(1)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_plain() {
+        let source = "foo a:int = Bar\nbaz = Qux;\n";
+        let index = LineIndex::new(source);
+        let diagnostic = Diagnostic::error("expected `;`, found `=`")
+            .with_label(Label::primary(Span::new(21, 22), "expected `;`"))
+            .with_label(Label::secondary(Span::new(13, 16), "result type ends here"))
+            .with_note("combinators must end with `;`");
+
+        assert_eq!(diagnostic.render("schema.tl", &index, ColorChoice::Plain), "\
+error: expected `;`, found `=`
+ --> schema.tl:2:5
+  |
+1 | foo a:int = Bar
+  |             --- result type ends here
+2 | baz = Qux;
+  |     ^ expected `;`
+  |
+  = note: combinators must end with `;`
+");
+    }
+
+    #[test]
+    fn render_multi_byte_and_tabs() {
+        let source = "\t// é\tx = Y;";
+        let index = LineIndex::new(source);
+        // `x`
+        let diagnostic = Diagnostic::warning("unused")
+            .with_label(Label::primary(Span::new(8, 9), ""));
+
+        assert_eq!(diagnostic.render("a.tl", &index, ColorChoice::Plain), "\
+warning: unused
+ --> a.tl:1:7
+  |
+1 | \t// é\tx = Y;
+  | \t    \t^
+");
+    }
+
+    #[test]
+    fn render_without_labels() {
+        let index = LineIndex::new("");
+        let diagnostic = Diagnostic::error("no layer").with_note("add `// LAYER N`");
+
+        assert_eq!(diagnostic.render("a.tl", &index, ColorChoice::Plain), "\
+error: no layer
+ = note: add `// LAYER N`
+");
+    }
+
+    #[test]
+    fn render_ansi() {
+        let index = LineIndex::new("x");
+        let diagnostic = Diagnostic::error("oops").with_label(Label::primary(Span::new(1, 2), "here"));
+        let rendered = diagnostic.render("a.tl", &index, ColorChoice::Ansi);
+
+        assert!(rendered.starts_with("\x1b[1;31merror\x1b[0m\x1b[1m: oops\x1b[0m\n"));
+        assert!(rendered.contains("\x1b[1;31m^ here\x1b[0m"));
+    }
+}
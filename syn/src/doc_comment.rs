@@ -0,0 +1,309 @@
+use std::borrow::Cow;
+
+use super::Comment;
+use span::{Span, SpanState};
+
+
+macro_attr_many! {
+    /// A TDLib-style documentation comment:
+    /// `//@description Sends a message @chat_id Target chat`.
+    ///
+    /// A documentation comment is built on top of a sequence of single-line
+    /// comments. Each `@name` starts a new tag which runs until the next tag
+    /// and `//-` lines continue the text of the preceding tag.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct DocComment<'a> {
+        pub tags: Vec<DocCommentTag<'a>>,
+    }
+
+    /// A single `@name text` entry of a documentation comment.
+    #[cfg_derive!(Clone, Debug, Deserialize, Serialize)]
+    pub struct DocCommentTag<'a> {
+        pub name_span: Span,
+        pub name: Cow<'a, str>,
+        pub text_span: Span,
+        pub text: Cow<'a, str>,
+    }
+}
+
+
+impl<'a> DocComment<'a> {
+    /// Build a documentation comment from the given `comments`.
+    ///
+    /// Comments that are neither `//@...` nor `//-...` are ignored. Return
+    /// `None` if there are no tags at all.
+    ///
+    /// Names and texts of tags borrow from the comments where possible. Only
+    /// texts continued with `//-` lines are allocated.
+    pub fn from_comments(comments: &[Comment<'a>]) -> Option<DocComment<'a>> {
+        let mut tags: Vec<DocCommentTag<'a>> = Vec::new();
+
+        for comment in comments {
+            let comment = match *comment {
+                Comment::SingleLine(ref comment) => comment,
+                Comment::MultiLine(_) => continue,
+            };
+
+            let content = &comment.content;
+            let span = comment.content_span;
+
+            if content.starts_with('@') {
+                for (start, end) in tag_ranges(content) {
+                    tags.push(DocCommentTag::from_str_range(content, span, start, end));
+                }
+            } else if let (Some(last_tag), Some(rest)) = (tags.last_mut(), content.strip_prefix('-')) {
+                let text = rest.trim();
+
+                if !text.is_empty() {
+                    let last_text = last_tag.text.to_mut();
+                    if !last_text.is_empty() {
+                        last_text.push(' ');
+                    }
+                    last_text.push_str(text);
+                    last_tag.text_span = last_tag.text_span.to(subspan(span, 1, content.len()));
+                }
+            }
+        }
+
+        if tags.is_empty() {
+            None
+        } else {
+            Some(DocComment { tags })
+        }
+    }
+
+    /// Return the text of the first tag named `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.tags.iter()
+            .find(|tag| tag.name == name)
+            .map(|tag| &*tag.text)
+    }
+
+    /// Return the text of the `@description` tag.
+    pub fn description(&self) -> Option<&str> {
+        self.get("description")
+    }
+}
+
+impl<'a> DocCommentTag<'a> {
+    fn from_str_range(content: &Cow<'a, str>, span: Span, start: usize, end: usize) -> DocCommentTag<'a> {
+        // Skip the `@`
+        let tag = &content[start + 1..end];
+        let name_len = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let text_len = tag[name_len..].trim().len();
+
+        let name_begin = start + 1;
+        let text_begin = end - tag[name_len..].trim_start().len();
+
+        DocCommentTag {
+            name_span: subspan(span, name_begin, name_begin + name_len),
+            name: subslice(content, name_begin, name_begin + name_len),
+            text_span: subspan(span, text_begin, text_begin + text_len),
+            text: subslice(content, text_begin, text_begin + text_len),
+        }
+    }
+}
+
+/// Split `content` into byte ranges each starting with an `@` which is either
+/// at the beginning or preceded by whitespace.
+fn tag_ranges(content: &str) -> Vec<(usize, usize)> {
+    let mut starts = content.char_indices()
+        .filter(|&(i, c)| c == '@' && content[..i].chars().next_back().map_or(true, char::is_whitespace))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    starts.push(content.len());
+
+    starts.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
+/// Return `content[start..end]`, borrowed if `content` is.
+fn subslice<'a>(content: &Cow<'a, str>, start: usize, end: usize) -> Cow<'a, str> {
+    match *content {
+        Cow::Borrowed(content) => Cow::Borrowed(&content[start..end]),
+        Cow::Owned(ref content) => Cow::Owned(content[start..end].to_owned()),
+    }
+}
+
+fn subspan(span: Span, start: usize, end: usize) -> Span {
+    match span.state() {
+        SpanState::Zeroed => Span::zeroed(),
+        SpanState::Normal => Span::new(span.begin() + start, span.begin() + end),
+    }
+}
+
+
+#[cfg(feature = "eq-impls")]
+mod eq_impls {
+    use super::*;
+
+    impl<'a> Eq for DocCommentTag<'a> {}
+
+    impl<'a> PartialEq for DocCommentTag<'a> {
+        fn eq(&self, other: &DocCommentTag<'a>) -> bool {
+            self.name == other.name && self.text == other.text
+        }
+    }
+}
+
+
+#[cfg(feature = "hash-impls")]
+mod hash_impls {
+    use std::hash::{Hash, Hasher};
+
+    use super::*;
+
+    impl<'a> Hash for DocCommentTag<'a> {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.name.hash(state);
+            self.text.hash(state);
+        }
+    }
+}
+
+
+mod spanned {
+    use super::*;
+    use span::Span;
+    use spanned::Spanned;
+    use spanned::private::Sealed;
+
+    impl<'a> Sealed for DocComment<'a> {}
+    impl<'a> Sealed for DocCommentTag<'a> {}
+
+    impl<'a> Spanned for DocComment<'a> {
+        fn span(&self) -> Span {
+            self.tags.span()
+        }
+    }
+
+    impl<'a> Spanned for DocCommentTag<'a> {
+        fn span(&self) -> Span {
+            self.name_span
+                .to(self.text_span)
+        }
+    }
+}
+
+
+mod owned {
+    use super::*;
+    use owned::IntoOwned;
+    use owned::private::Sealed;
+
+    impl_to_owned!(DocComment, DocCommentTag);
+
+    impl<'a> Sealed for DocComment<'a> {}
+    impl<'a> Sealed for DocCommentTag<'a> {}
+
+    impl<'a> IntoOwned for DocComment<'a> {
+        type Owned = DocComment<'static>;
+
+        fn into_owned(self) -> DocComment<'static> {
+            DocComment {
+                tags: self.tags.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for DocCommentTag<'a> {
+        type Owned = DocCommentTag<'static>;
+
+        fn into_owned(self) -> DocCommentTag<'static> {
+            DocCommentTag {
+                name_span: self.name_span,
+                name: Cow::Owned(self.name.into_owned()),
+                text_span: self.text_span,
+                text: Cow::Owned(self.text.into_owned()),
+            }
+        }
+    }
+}
+
+
+#[cfg(feature = "printing")]
+mod printing {
+    use std::fmt;
+
+    use super::*;
+    use print::Print;
+    use print::private::Sealed;
+
+    impl<'a> Sealed for DocComment<'a> {}
+    impl<'a> Sealed for DocCommentTag<'a> {}
+
+    impl<'a> Print for DocComment<'a> {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            for (i, tag) in self.tags.iter().enumerate() {
+                if i > 0 {
+                    f.write_str("\n")?;
+                }
+                f.write_str("//")?;
+                tag.print(f)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    impl<'a> Print for DocCommentTag<'a> {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("@")?;
+            fmt::Display::fmt(&self.name, f)?;
+            if !self.text.is_empty() {
+                f.write_str(" ")?;
+                fmt::Display::fmt(&self.text, f)?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use comment::CommentSingleLine;
+    use token::SlashSlash;
+
+
+    fn new_comment(content: &str) -> Comment<'_> {
+        Comment::SingleLine(CommentSingleLine {
+            slash_slash_token: SlashSlash(Span::zeroed()),
+            content_span: Span::zeroed(),
+            content: content.into(),
+        })
+    }
+
+    #[test]
+    fn from_comments() {
+        let comments = [
+            new_comment("@description Sends a message @chat_id Target chat"),
+            new_comment(" An unrelated comment"),
+            new_comment("@text Text of the message, e.g. mail to user@example.com"),
+            new_comment("-which may be long"),
+        ];
+
+        let doc_comment = DocComment::from_comments(&comments).unwrap();
+        let tags = doc_comment.tags.iter()
+            .map(|tag| (&*tag.name, &*tag.text))
+            .collect::<Vec<_>>();
+
+        assert_eq!(tags, [
+            ("description", "Sends a message"),
+            ("chat_id", "Target chat"),
+            ("text", "Text of the message, e.g. mail to user@example.com which may be long"),
+        ]);
+        assert!(match doc_comment.tags[0].text { Cow::Borrowed(_) => true, Cow::Owned(_) => false });
+        assert_eq!(doc_comment.description(), Some("Sends a message"));
+        assert_eq!(doc_comment.get("chat_id"), Some("Target chat"));
+        assert_eq!(doc_comment.get("user_id"), None);
+    }
+
+    #[test]
+    fn from_comments_without_tags() {
+        let comments = [new_comment(" Just a comment"), new_comment("-dangling")];
+
+        assert!(DocComment::from_comments(&comments).is_none());
+    }
+}
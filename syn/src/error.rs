@@ -10,46 +10,118 @@ mod parsing {
     use std::error;
     use std::fmt;
 
-    use nom;
+    use nom::{self, InputTake};
 
     use cursor::Cursor;
+    use id::IdValidation;
     use span::Span;
 
-    #[derive(Debug)]
+    /// An error which occurred while parsing TL language source text.
+    #[derive(Clone, Debug, Eq, PartialEq)]
     pub struct ParseError {
         pub(crate) span: Span,
-        pub(crate) error_kind: nom::ErrorKind<u32>,
+        pub(crate) kind: ParseErrorKind,
+    }
+
+    /// The reason why parsing failed.
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum ParseErrorKind {
+        /// A token which doesn't fit the grammar at its position.
+        ///
+        /// `found` is `None` if the input ended unexpectedly, and a line
+        /// break if the line did.
+        UnexpectedToken { found: Option<String> },
+        /// A token other than the ones which must follow at its position.
+        ExpectedOneOf { expected: Vec<&'static str>, found: Option<String> },
+        /// A combinator id which is not a 32-bit hexadecimal number, or which
+        /// doesn't have exactly 8 digits when validated strictly.
+        InvalidId { id: String, strict: bool },
+        /// A bit index which is not in the `0..=31` range.
+        BitIndexOutOfRange { index: String },
+        /// A `/*` comment without the closing `*/`.
+        UnterminatedComment,
+        /// Input left after a complete syntax tree node.
+        TrailingInput { found: String },
     }
 
     impl fmt::Display for ParseError {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            fmt::Debug::fmt(self, f)
+            fmt::Display::fmt(&self.kind, f)
+        }
+    }
+
+    impl error::Error for ParseError {}
+
+    impl fmt::Display for ParseErrorKind {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match *self {
+                ParseErrorKind::UnexpectedToken { ref found } => {
+                    f.write_str("unexpected ")?;
+                    fmt_found(found.as_deref(), f)
+                },
+                ParseErrorKind::ExpectedOneOf { ref expected, ref found } => {
+                    match expected.split_last() {
+                        Some((last, [])) => write!(f, "expected {}", last)?,
+                        Some((last, rest)) => write!(f, "expected one of {} or {}", rest.join(", "), last)?,
+                        None => f.write_str("expected nothing")?,
+                    }
+                    f.write_str(", found ")?;
+                    fmt_found(found.as_deref(), f)
+                },
+                ParseErrorKind::InvalidId { ref id, strict } => {
+                    if id.is_empty() {
+                        f.write_str("expected a combinator id after `#`")
+                    } else if strict {
+                        write!(f, "invalid combinator id `{}`, expected exactly 8 hexadecimal digits", id)
+                    } else {
+                        write!(f, "invalid combinator id `{}`, expected at most 8 hexadecimal digits", id)
+                    }
+                },
+                ParseErrorKind::BitIndexOutOfRange { ref index } => {
+                    write!(f, "bit index `{}` is out of range, expected a number from 0 to 31", index)
+                },
+                ParseErrorKind::UnterminatedComment => {
+                    f.write_str("unterminated multi-line comment, expected `*/`")
+                },
+                ParseErrorKind::TrailingInput { ref found } => {
+                    f.write_str("expected end of input, found ")?;
+                    fmt_found(Some(found), f)
+                },
+            }
         }
     }
 
-    impl error::Error for ParseError {
-        fn description(&self) -> &str {
-            self.error_kind.description()
+    fn fmt_found(found: Option<&str>, f: &mut fmt::Formatter) -> fmt::Result {
+        match found {
+            Some("\n") | Some("\r\n") => f.write_str("end of line"),
+            Some(found) => write!(f, "`{}`", found),
+            None => f.write_str("end of input"),
         }
     }
 
     impl ParseError {
+        /// Get the span of the source text where the error occurred.
+        pub fn span(&self) -> Span {
+            self.span
+        }
+
+        /// Get the reason why parsing failed.
+        pub fn kind(&self) -> &ParseErrorKind {
+            &self.kind
+        }
+
         pub fn slice_into<'a>(&self, input: &'a str) -> &'a str {
             let begin = self.span.begin();
             let end = self.span.end();
 
-            &input[begin..end]
+            &input[begin - 1..end - 1]
         }
 
         pub fn from_nom_err_cursor<'a>(error: nom::Err<Cursor<'a>, u32>) -> Option<ParseError> {
             fn from_error_kind<'a>(context: nom::Context<Cursor<'a>, u32>) -> ParseError {
-                match context {
-                    nom::Context::Code(cursor, error_kind) => {
-                        let span = cursor.span();
-
-                        ParseError { span, error_kind }
-                    },
-                    _ => unreachable!(),
+                match error_code_at(&context) {
+                    (cursor, Some(code)) => code.to_parse_error(cursor),
+                    (cursor, None) => ParseError::unexpected_token(cursor),
                 }
             }
 
@@ -59,9 +131,165 @@ mod parsing {
                 nom::Err::Failure(context) => Some(from_error_kind(context)),
             }
         }
+
+        pub(crate) fn unexpected_token(cursor: Cursor) -> ParseError {
+            let (span, found) = token_at(cursor);
+
+            ParseError { span, kind: ParseErrorKind::UnexpectedToken { found } }
+        }
+
+        pub(crate) fn unexpected_end_of_input(cursor: Cursor) -> ParseError {
+            let end = cursor.span().end();
+
+            ParseError {
+                span: Span::new(end, end),
+                kind: ParseErrorKind::UnexpectedToken { found: None },
+            }
+        }
+
+        pub(crate) fn trailing_input(cursor: Cursor) -> ParseError {
+            // Trailing input goes past the end of line the node ends on
+            let s = cursor.to_str();
+            let cursor = cursor.take_split(s.len() - s.trim_start().len()).0;
+            let (span, found) = token_at(cursor);
+            let kind = match found {
+                Some(found) => ParseErrorKind::TrailingInput { found },
+                None => ParseErrorKind::UnexpectedToken { found: None },
+            };
+
+            ParseError { span, kind }
+        }
     }
 
     pub type ParseResult<T> = Result<T, ParseError>;
+
+
+    /// Return the position of the innermost error in `context` and its
+    /// `ErrorCode`, if it has one.
+    pub(crate) fn error_code_at<'a>(context: &nom::Context<Cursor<'a>, u32>) -> (Cursor<'a>, Option<ErrorCode>) {
+        let (cursor, error_kind) = match *context {
+            nom::Context::Code(cursor, ref error_kind) => (cursor, error_kind),
+            // The innermost error goes first
+            nom::Context::List(ref errors) => (errors[0].0, &errors[0].1),
+        };
+
+        match *error_kind {
+            nom::ErrorKind::Custom(code) => (cursor, ErrorCode::from_u32(code)),
+            _ => (cursor, None),
+        }
+    }
+
+
+    /// Codes of `nom::ErrorKind::Custom` errors which are raised by parsers of
+    /// this crate to be turned into a specific `ParseErrorKind` later.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub(crate) enum ErrorCode {
+        InvalidId = 1,
+        BitIndexOutOfRange,
+        UnterminatedComment,
+        ExpectedResultType,
+        ExpectedEquals,
+        ExpectedSemicolon,
+        ExpectedDelimiterKind,
+        ExpectedItem,
+        ExpectedType,
+        ExpectedParamOrEquals,
+        ExpectedParamOrRightBracket,
+    }
+
+    impl ErrorCode {
+        const ALL: &'static [ErrorCode] = &[
+            ErrorCode::InvalidId,
+            ErrorCode::BitIndexOutOfRange,
+            ErrorCode::UnterminatedComment,
+            ErrorCode::ExpectedResultType,
+            ErrorCode::ExpectedEquals,
+            ErrorCode::ExpectedSemicolon,
+            ErrorCode::ExpectedDelimiterKind,
+            ErrorCode::ExpectedItem,
+            ErrorCode::ExpectedType,
+            ErrorCode::ExpectedParamOrEquals,
+            ErrorCode::ExpectedParamOrRightBracket,
+        ];
+
+        fn from_u32(code: u32) -> Option<ErrorCode> {
+            ErrorCode::ALL.iter().cloned().find(|&c| c as u32 == code)
+        }
+
+        /// Fail hard at the given `cursor` so that no other alternatives are
+        /// tried.
+        pub(crate) fn failure<'a, O>(self, cursor: Cursor<'a>) -> nom::IResult<Cursor<'a>, O> {
+            Err(nom::Err::Failure(error_position!(cursor, nom::ErrorKind::Custom(self as u32))))
+        }
+
+        /// Fail at the given `cursor`, letting other alternatives be tried.
+        pub(crate) fn error<'a, O>(self, cursor: Cursor<'a>) -> nom::IResult<Cursor<'a>, O> {
+            Err(nom::Err::Error(error_position!(cursor, nom::ErrorKind::Custom(self as u32))))
+        }
+
+        fn to_parse_error(self, cursor: Cursor) -> ParseError {
+            let expected: &'static [&'static str] = match self {
+                ErrorCode::InvalidId => {
+                    let (span, id) = word_at(cursor);
+                    let strict = cursor.id_validation() == IdValidation::Strict;
+
+                    return ParseError { span, kind: ParseErrorKind::InvalidId { id, strict } };
+                },
+                ErrorCode::BitIndexOutOfRange => {
+                    let (span, index) = word_at(cursor);
+
+                    return ParseError { span, kind: ParseErrorKind::BitIndexOutOfRange { index } };
+                },
+                ErrorCode::UnterminatedComment => {
+                    return ParseError { span: cursor.span(), kind: ParseErrorKind::UnterminatedComment };
+                },
+                ErrorCode::ExpectedResultType => &["a result type"],
+                ErrorCode::ExpectedEquals => &["`=`"],
+                ErrorCode::ExpectedSemicolon => &["`;`"],
+                ErrorCode::ExpectedDelimiterKind => &["`types`", "`functions`"],
+                ErrorCode::ExpectedItem => &["an identifier", "a comment", "`---`"],
+                ErrorCode::ExpectedType => &["a type"],
+                ErrorCode::ExpectedParamOrEquals => &["a field", "`=`"],
+                ErrorCode::ExpectedParamOrRightBracket => &["a field", "`]`"],
+            };
+
+            let (span, found) = token_at(cursor);
+
+            ParseError { span, kind: ParseErrorKind::ExpectedOneOf { expected: expected.to_vec(), found } }
+        }
+    }
+
+    fn is_word_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '_'
+    }
+
+    /// Return the span and the text of the word `cursor` points at, which may
+    /// be empty.
+    fn word_at(cursor: Cursor) -> (Span, String) {
+        let s = cursor.to_str();
+        let len = s.find(|c| !is_word_char(c)).unwrap_or(s.len());
+        let begin = cursor.offset();
+
+        (Span::new(begin, begin + len), s[..len].to_owned())
+    }
+
+    /// Return the span and the text of the first token after spaces `cursor`
+    /// points at: either a whole word, a line break or a single character.
+    fn token_at(cursor: Cursor) -> (Span, Option<String>) {
+        let s = cursor.to_str();
+        let start = s.len() - s.trim_start_matches([' ', '\t']).len();
+        let rest = &s[start..];
+        let begin = cursor.offset() + start;
+
+        let len = match rest.chars().next() {
+            None => return (Span::new(begin, begin), None),
+            Some('\r') if rest.starts_with("\r\n") => 2,
+            Some(c) if is_word_char(c) => rest.find(|c| !is_word_char(c)).unwrap_or(rest.len()),
+            Some(c) => c.len_utf8(),
+        };
+
+        (Span::new(begin, begin + len), Some(rest[..len].to_owned()))
+    }
 }
 
 #[cfg(feature = "parsing")]
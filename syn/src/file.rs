@@ -1,52 +1,218 @@
-use super::Item;
+use super::{Item, ItemCombinator, ItemLayer, Sections};
 
 
 macro_attr_many! {
     /// A complete file of TL language source text.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct File {
-        pub items: Vec<Item>,
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct File<'a> {
+        pub items: Vec<Item<'a>>,
     }
 }
 
+impl<'a> File<'a> {
+    /// Group the items of this file into type and function sections.
+    pub fn sections(&self) -> Sections<'_> {
+        Sections::new(&self.items)
+    }
+
+    /// Return an iterator over the combinators of this file paired with the
+    /// layer they belong to, assuming that layer markers follow the
+    /// combinators of their layer as in upstream `scheme.tl` files.
+    pub fn combinator_layers(&self) -> impl Iterator<Item = (&ItemCombinator<'a>, Option<&ItemLayer>)> {
+        self.combinator_layers_with(LayerMarkerPlacement::After)
+    }
+
+    /// Return an iterator over the combinators of this file paired with the
+    /// layer they belong to, with layer markers placed as specified by
+    /// `placement`.
+    ///
+    /// A file concatenated from several schemas may switch layers in the
+    /// middle. Combinators which have no marker on the side given by
+    /// `placement` have no layer.
+    pub fn combinator_layers_with(
+        &self,
+        placement: LayerMarkerPlacement,
+    ) -> impl Iterator<Item = (&ItemCombinator<'a>, Option<&ItemLayer>)> {
+        let layers = match placement {
+            LayerMarkerPlacement::After => {
+                let mut layers = closest_layers(self.items.iter().rev());
+                layers.reverse();
+                layers
+            },
+            LayerMarkerPlacement::Before => closest_layers(self.items.iter()),
+        };
+
+        layers.into_iter()
+    }
+}
+
+/// Where layer markers are placed relative to the combinators of their layer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LayerMarkerPlacement {
+    /// A marker ends the combinators of its layer, as `// LAYER 105` does in
+    /// upstream `scheme.tl` files.
+    After,
+    /// A marker starts the combinators of its layer, as a `===105===` header
+    /// does.
+    Before,
+}
+
+// Pair every combinator with the closest layer marker which precedes it in
+// `items`.
+fn closest_layers<'b, 'a: 'b, I>(items: I) -> Vec<(&'b ItemCombinator<'a>, Option<&'b ItemLayer>)>
+where
+    I: Iterator<Item = &'b Item<'a>>,
+{
+    let mut layer = None;
+
+    items.filter_map(|item| match *item {
+        Item::Combinator(ref combinator) => Some((combinator, layer)),
+        Item::Layer(ref item_layer) => {
+            layer = Some(item_layer);
+            None
+        },
+        _ => None,
+    }).collect()
+}
+
 mod spanned {
     use super::*;
     use span::Span;
     use spanned::Spanned;
     use spanned::private::Sealed;
 
-    impl Sealed for File {}
+    impl<'a> Sealed for File<'a> {}
 
-    impl Spanned for File {
+    impl<'a> Spanned for File<'a> {
         fn span(&self) -> Span {
             self.items.span()
         }
     }
 }
 
+mod owned {
+    use super::*;
+    use owned::IntoOwned;
+    use owned::private::Sealed;
+
+    impl_to_owned!(File);
+
+    impl<'a> Sealed for File<'a> {}
+
+    impl<'a> IntoOwned for File<'a> {
+        type Owned = File<'static>;
+
+        fn into_owned(self) -> File<'static> {
+            File {
+                items: self.items.into_owned(),
+            }
+        }
+    }
+}
+
 #[cfg(feature = "parsing")]
 mod parsing {
-    use nom;
+    use std::borrow::Cow;
+
+    use nom::{self, InputTake};
 
     use super::*;
     use cursor::Cursor;
+    use error::ParseError;
+    use item::ItemError;
+    use span::Span;
     use synom::Synom;
     use synom::private::Sealed;
 
-    impl Sealed for File {}
+    impl<'a> Sealed for File<'a> {}
 
-    impl Synom for File {
-        named!(parse_cursor(Cursor) -> File, do_parse!(
+    impl<'a> Synom<'a> for File<'a> {
+        named!(parse_cursor(Cursor<'a>) -> File<'a>, do_parse!(
             items: many0!(do_parse!(
                 complete!(take_while!(char::is_whitespace)) >>
                 item: tlsyn!(Item) >>
                 opt!(call!(nom::line_ending)) >>
                 (item)
             )) >>
+            complete!(take_while!(char::is_whitespace)) >>
+            call!(end_of_items) >>
 
             (File { items })
         ));
     }
+
+    // Report why the item after the last parsed one fails to parse instead of
+    // leaving it as trailing input.
+    fn end_of_items(input: Cursor) -> nom::IResult<Cursor, ()> {
+        if input.to_str().is_empty() {
+            return Ok((input, ()));
+        }
+
+        Item::parse_cursor(input).map(|_| (input, ()))
+    }
+
+    impl<'a> File<'a> {
+        /// Parse a file, skipping items which fail to parse instead of
+        /// stopping at the first one.
+        ///
+        /// Input of a failed item is skipped up to and including the next `;`
+        /// or up to the next line ending, whichever comes first, and is
+        /// recorded as an `Item::Error`.
+        pub(crate) fn parse_cursor_recovering(mut input: Cursor<'a>) -> (File<'a>, Vec<ParseError>) {
+            let mut items = Vec::new();
+            let mut errors = Vec::new();
+
+            loop {
+                let s = input.to_str();
+                input = input.take_split(s.len() - s.trim_start().len()).0;
+
+                if input.to_str().is_empty() {
+                    break;
+                }
+
+                match Item::parse_cursor(input) {
+                    Ok((rest, item)) => {
+                        items.push(item);
+                        input = rest;
+                    },
+                    Err(e) => {
+                        let error = ParseError::from_nom_err_cursor(e)
+                            .unwrap_or_else(|| ParseError::unexpected_end_of_input(input));
+                        errors.push(error);
+
+                        let (rest, item_error) = ItemError::skip_cursor(input);
+                        items.push(Item::Error(item_error));
+                        input = rest;
+                    },
+                }
+            }
+
+            (File { items }, errors)
+        }
+    }
+
+    impl<'a> ItemError<'a> {
+        /// Skip the input of an item which failed to parse up to and
+        /// including the next `;` or up to the next line ending, whichever
+        /// comes first.
+        pub(crate) fn skip_cursor(input: Cursor<'a>) -> (Cursor<'a>, ItemError<'a>) {
+            let s = input.to_str();
+            let skip_len = match s.find([';', '\n']) {
+                Some(i) if s[i..].starts_with(';') => i + 1,
+                Some(i) => i,
+                None => s.len(),
+            };
+            let text = s[..skip_len].trim_end();
+            let begin = input.offset();
+
+            let item_error = ItemError {
+                span: Span::new(begin, begin + text.len()),
+                text: Cow::Borrowed(text),
+            };
+
+            (input.take_split(skip_len).0, item_error)
+        }
+    }
 }
 
 #[cfg(feature = "printing")]
@@ -54,14 +220,89 @@ mod printing {
     use std::fmt;
 
     use super::*;
-    use print::{Print, print_slice_with_separator};
+    use print::Print;
     use print::private::Sealed;
 
-    impl Sealed for File {}
+    impl<'a> Sealed for File<'a> {}
 
-    impl Print for File {
+    impl<'a> Print for File<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            print_slice_with_separator(&self.items, "\n", f).map(|_| ())
+            for (i, item) in self.items.iter().enumerate() {
+                if i > 0 {
+                    f.write_str("\n")?;
+                }
+                item.print(f)?;
+
+                // Keep a standalone comment apart from the next combinator so
+                // that it doesn't become the combinator's leading comment
+                if let (&Item::Comment(_), Some(&Item::Combinator(_))) = (item, self.items.get(i + 1)) {
+                    f.write_str("\n")?;
+                }
+            }
+
+            Ok(())
         }
     }
 }
+
+
+#[cfg(all(test, feature = "parsing"))]
+mod tests {
+    use super::*;
+
+    fn combinator_layers_of<'a>(file: &'a File, placement: LayerMarkerPlacement) -> Vec<(&'a str, Option<u32>)> {
+        file.combinator_layers_with(placement)
+            .map(|(combinator, layer)| (combinator.name.segments.iter().next().unwrap().as_str(), layer.map(|layer| layer.layer)))
+            .collect()
+    }
+
+    #[test]
+    fn combinator_layers_after() {
+        let input = "\
+            boolFalse#bc799737 = Bool;\n\
+            boolTrue#997275b5 = Bool;\n\
+            // LAYER 105\n\
+            inputPeerEmpty#7f3b18ea = InputPeer;\n\
+            // LAYER 106\n\
+            inputPeerSelf#7da07ec9 = InputPeer;\n\
+        ";
+
+        let file = ::parse_file_str(input).unwrap();
+        let layers = file.combinator_layers()
+            .map(|(_, layer)| layer.map(|layer| layer.layer))
+            .collect::<Vec<_>>();
+
+        assert_eq!(layers, [Some(105), Some(105), Some(106), None]);
+        assert_eq!(combinator_layers_of(&file, LayerMarkerPlacement::After), [
+            ("boolFalse", Some(105)),
+            ("boolTrue", Some(105)),
+            ("inputPeerEmpty", Some(106)),
+            ("inputPeerSelf", None),
+        ]);
+    }
+
+    #[test]
+    fn combinator_layers_before() {
+        let input = "\
+            boolTrue#997275b5 = Bool;\n\
+            // LAYER 78\n\
+            inputPeerEmpty#7f3b18ea = InputPeer;\n\
+            /* Layer 100 */\n\
+            inputPeerSelf#7da07ec9 = InputPeer;\n\
+            ===105===\n\
+            inputPeerChat#179be863 chat_id:int = InputPeer;\n\
+            // LAYER 78\n\
+            inputPeerUser#7b8e7de6 user_id:int access_hash:long = InputPeer;\n\
+        ";
+
+        let file = ::parse_file_str(input).unwrap();
+
+        assert_eq!(combinator_layers_of(&file, LayerMarkerPlacement::Before), [
+            ("boolTrue", None),
+            ("inputPeerEmpty", Some(78)),
+            ("inputPeerSelf", Some(100)),
+            ("inputPeerChat", Some(105)),
+            ("inputPeerUser", Some(78)),
+        ]);
+    }
+}
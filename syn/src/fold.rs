@@ -0,0 +1,845 @@
+//! Syntax tree traversal to transform the nodes of an owned syntax tree.
+//!
+//! Each method of the [`Fold`] trait is a hook that can be overridden to
+//! customize the behavior when transforming the corresponding type of node.
+//! By default, every method recursively transforms the substructure of the
+//! input by invoking the right folder method of each of its fields.
+//!
+//! Spans of tokens and nodes are transformed with [`Fold::fold_span`].
+
+use punctuated::{Pair, Punctuated};
+use span::Span;
+use token;
+use {
+    AngleBracketedGenericArguments, BitIndex, BitSelector, CombinatorId, Comment, CommentMultiLine,
+    CommentSingleLine, ConditionalParamDef, Delimiter, DelimiterFunctions, DelimiterTypes,
+    DocComment, DocCommentTag, File, GenericArgument, GenericArguments, Id, Ident, Item,
+    ItemBuiltin, ItemCombinator, ItemComment, ItemDelimiter, ItemError, ItemLayer,
+    ItemTypeDeclaration, LayerKeyword, LayerMarker, LayerMarkerEquals, LayerMarkerMultiLine,
+    LayerMarkerSingleLine, Multiplicity, NatExpr, NatExprAdd, NatExprConst, NatExprIdent,
+    NatExprParen, OptParam, Param, ParamComment, ParamConditional, ParamRepeated,
+    ParamRepeatedIdent, ParamTypeOnly, ParamWithParen, ParameterizedPath, Path,
+    SpaceSeparatedGenericArguments, Type, TypeBare, TypeDeclarationKind, TypeInt,
+    TypeParameterizedPath, TypeParen, TypeTypeParameter,
+};
+
+
+/// Syntax tree traversal to transform the nodes of an owned syntax tree.
+///
+/// See the [module documentation] for details.
+///
+/// [module documentation]: index.html
+pub trait Fold<'a> {
+    fn fold_angle_bracketed_generic_arguments(
+        &mut self,
+        node: AngleBracketedGenericArguments<'a>,
+    ) -> AngleBracketedGenericArguments<'a> {
+        fold_angle_bracketed_generic_arguments(self, node)
+    }
+
+    fn fold_bit_index(&mut self, node: BitIndex) -> BitIndex {
+        fold_bit_index(self, node)
+    }
+
+    fn fold_bit_selector(&mut self, node: BitSelector) -> BitSelector {
+        fold_bit_selector(self, node)
+    }
+
+    fn fold_combinator_id(&mut self, node: CombinatorId) -> CombinatorId {
+        fold_combinator_id(self, node)
+    }
+
+    fn fold_comment(&mut self, node: Comment<'a>) -> Comment<'a> {
+        fold_comment(self, node)
+    }
+
+    fn fold_comment_multi_line(&mut self, node: CommentMultiLine<'a>) -> CommentMultiLine<'a> {
+        fold_comment_multi_line(self, node)
+    }
+
+    fn fold_comment_single_line(&mut self, node: CommentSingleLine<'a>) -> CommentSingleLine<'a> {
+        fold_comment_single_line(self, node)
+    }
+
+    fn fold_conditional_param_def(
+        &mut self,
+        node: ConditionalParamDef<'a>,
+    ) -> ConditionalParamDef<'a> {
+        fold_conditional_param_def(self, node)
+    }
+
+    fn fold_delimiter(&mut self, node: Delimiter) -> Delimiter {
+        fold_delimiter(self, node)
+    }
+
+    fn fold_delimiter_functions(&mut self, node: DelimiterFunctions) -> DelimiterFunctions {
+        fold_delimiter_functions(self, node)
+    }
+
+    fn fold_delimiter_types(&mut self, node: DelimiterTypes) -> DelimiterTypes {
+        fold_delimiter_types(self, node)
+    }
+
+    fn fold_doc_comment(&mut self, node: DocComment<'a>) -> DocComment<'a> {
+        fold_doc_comment(self, node)
+    }
+
+    fn fold_doc_comment_tag(&mut self, node: DocCommentTag<'a>) -> DocCommentTag<'a> {
+        fold_doc_comment_tag(self, node)
+    }
+
+    fn fold_file(&mut self, node: File<'a>) -> File<'a> {
+        fold_file(self, node)
+    }
+
+    fn fold_generic_argument(&mut self, node: GenericArgument<'a>) -> GenericArgument<'a> {
+        fold_generic_argument(self, node)
+    }
+
+    fn fold_generic_arguments(&mut self, node: GenericArguments<'a>) -> GenericArguments<'a> {
+        fold_generic_arguments(self, node)
+    }
+
+    fn fold_id(&mut self, node: Id) -> Id {
+        fold_id(self, node)
+    }
+
+    fn fold_ident(&mut self, node: Ident<'a>) -> Ident<'a> {
+        fold_ident(self, node)
+    }
+
+    fn fold_item(&mut self, node: Item<'a>) -> Item<'a> {
+        fold_item(self, node)
+    }
+
+    fn fold_item_builtin(&mut self, node: ItemBuiltin<'a>) -> ItemBuiltin<'a> {
+        fold_item_builtin(self, node)
+    }
+
+    fn fold_item_combinator(&mut self, node: ItemCombinator<'a>) -> ItemCombinator<'a> {
+        fold_item_combinator(self, node)
+    }
+
+    fn fold_item_comment(&mut self, node: ItemComment<'a>) -> ItemComment<'a> {
+        fold_item_comment(self, node)
+    }
+
+    fn fold_item_delimiter(&mut self, node: ItemDelimiter) -> ItemDelimiter {
+        fold_item_delimiter(self, node)
+    }
+
+    fn fold_item_error(&mut self, node: ItemError<'a>) -> ItemError<'a> {
+        fold_item_error(self, node)
+    }
+
+    fn fold_item_layer(&mut self, node: ItemLayer) -> ItemLayer {
+        fold_item_layer(self, node)
+    }
+
+    fn fold_item_type_declaration(
+        &mut self,
+        node: ItemTypeDeclaration<'a>,
+    ) -> ItemTypeDeclaration<'a> {
+        fold_item_type_declaration(self, node)
+    }
+
+    fn fold_layer_keyword(&mut self, node: LayerKeyword) -> LayerKeyword {
+        fold_layer_keyword(self, node)
+    }
+
+    fn fold_layer_marker(&mut self, node: LayerMarker) -> LayerMarker {
+        fold_layer_marker(self, node)
+    }
+
+    fn fold_layer_marker_equals(&mut self, node: LayerMarkerEquals) -> LayerMarkerEquals {
+        fold_layer_marker_equals(self, node)
+    }
+
+    fn fold_layer_marker_multi_line(&mut self, node: LayerMarkerMultiLine) -> LayerMarkerMultiLine {
+        fold_layer_marker_multi_line(self, node)
+    }
+
+    fn fold_layer_marker_single_line(
+        &mut self,
+        node: LayerMarkerSingleLine,
+    ) -> LayerMarkerSingleLine {
+        fold_layer_marker_single_line(self, node)
+    }
+
+    fn fold_multiplicity(&mut self, node: Multiplicity<'a>) -> Multiplicity<'a> {
+        fold_multiplicity(self, node)
+    }
+
+    fn fold_nat_expr(&mut self, node: NatExpr<'a>) -> NatExpr<'a> {
+        fold_nat_expr(self, node)
+    }
+
+    fn fold_nat_expr_add(&mut self, node: NatExprAdd<'a>) -> NatExprAdd<'a> {
+        fold_nat_expr_add(self, node)
+    }
+
+    fn fold_nat_expr_const(&mut self, node: NatExprConst) -> NatExprConst {
+        fold_nat_expr_const(self, node)
+    }
+
+    fn fold_nat_expr_ident(&mut self, node: NatExprIdent<'a>) -> NatExprIdent<'a> {
+        fold_nat_expr_ident(self, node)
+    }
+
+    fn fold_nat_expr_paren(&mut self, node: NatExprParen<'a>) -> NatExprParen<'a> {
+        fold_nat_expr_paren(self, node)
+    }
+
+    fn fold_opt_param(&mut self, node: OptParam<'a>) -> OptParam<'a> {
+        fold_opt_param(self, node)
+    }
+
+    fn fold_param(&mut self, node: Param<'a>) -> Param<'a> {
+        fold_param(self, node)
+    }
+
+    fn fold_param_comment(&mut self, node: ParamComment<'a>) -> ParamComment<'a> {
+        fold_param_comment(self, node)
+    }
+
+    fn fold_param_conditional(&mut self, node: ParamConditional<'a>) -> ParamConditional<'a> {
+        fold_param_conditional(self, node)
+    }
+
+    fn fold_param_repeated(&mut self, node: ParamRepeated<'a>) -> ParamRepeated<'a> {
+        fold_param_repeated(self, node)
+    }
+
+    fn fold_param_repeated_ident(
+        &mut self,
+        node: ParamRepeatedIdent<'a>,
+    ) -> ParamRepeatedIdent<'a> {
+        fold_param_repeated_ident(self, node)
+    }
+
+    fn fold_param_type_only(&mut self, node: ParamTypeOnly<'a>) -> ParamTypeOnly<'a> {
+        fold_param_type_only(self, node)
+    }
+
+    fn fold_param_with_paren(&mut self, node: ParamWithParen<'a>) -> ParamWithParen<'a> {
+        fold_param_with_paren(self, node)
+    }
+
+    fn fold_parameterized_path(&mut self, node: ParameterizedPath<'a>) -> ParameterizedPath<'a> {
+        fold_parameterized_path(self, node)
+    }
+
+    fn fold_path(&mut self, node: Path<'a>) -> Path<'a> {
+        fold_path(self, node)
+    }
+
+    fn fold_space_separated_generic_arguments(
+        &mut self,
+        node: SpaceSeparatedGenericArguments<'a>,
+    ) -> SpaceSeparatedGenericArguments<'a> {
+        fold_space_separated_generic_arguments(self, node)
+    }
+
+    fn fold_type(&mut self, node: Type<'a>) -> Type<'a> {
+        fold_type(self, node)
+    }
+
+    fn fold_type_bare(&mut self, node: TypeBare<'a>) -> TypeBare<'a> {
+        fold_type_bare(self, node)
+    }
+
+    fn fold_type_declaration_kind(&mut self, node: TypeDeclarationKind) -> TypeDeclarationKind {
+        fold_type_declaration_kind(self, node)
+    }
+
+    fn fold_type_int(&mut self, node: TypeInt) -> TypeInt {
+        fold_type_int(self, node)
+    }
+
+    fn fold_type_parameterized_path(
+        &mut self,
+        node: TypeParameterizedPath<'a>,
+    ) -> TypeParameterizedPath<'a> {
+        fold_type_parameterized_path(self, node)
+    }
+
+    fn fold_type_paren(&mut self, node: TypeParen<'a>) -> TypeParen<'a> {
+        fold_type_paren(self, node)
+    }
+
+    fn fold_type_type_parameter(&mut self, node: TypeTypeParameter<'a>) -> TypeTypeParameter<'a> {
+        fold_type_type_parameter(self, node)
+    }
+
+    fn fold_span(&mut self, span: Span) -> Span {
+        span
+    }
+}
+
+
+pub fn fold_angle_bracketed_generic_arguments<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: AngleBracketedGenericArguments<'a>,
+) -> AngleBracketedGenericArguments<'a> {
+    AngleBracketedGenericArguments {
+        langle_token: token::LAngle(v.fold_span(node.langle_token.0)),
+        args: {
+            let mut args = Punctuated::new();
+            for pair in node.args.into_pairs() {
+                match pair {
+                    Pair::Punctuated(value, punct) => {
+                        args.push_value(v.fold_generic_argument(value));
+                        args.push_punct(token::Comma(v.fold_span(punct.0)));
+                    },
+                    Pair::End(value) => args.push_value(v.fold_generic_argument(value)),
+                }
+            }
+            args
+        },
+        rangle_token: token::RAngle(v.fold_span(node.rangle_token.0)),
+    }
+}
+
+pub fn fold_bit_index<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: BitIndex) -> BitIndex {
+    let mut node = node;
+    node.span = v.fold_span(node.span);
+    node
+}
+
+pub fn fold_bit_selector<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: BitSelector) -> BitSelector {
+    BitSelector {
+        dot_token: token::Dot(v.fold_span(node.dot_token.0)),
+        bit_index: v.fold_bit_index(node.bit_index),
+    }
+}
+
+pub fn fold_combinator_id<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: CombinatorId) -> CombinatorId {
+    CombinatorId {
+        hash_token: token::Hash(v.fold_span(node.hash_token.0)),
+        id: v.fold_id(node.id),
+    }
+}
+
+pub fn fold_comment<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: Comment<'a>) -> Comment<'a> {
+    match node {
+        Comment::SingleLine(inner) => Comment::SingleLine(v.fold_comment_single_line(inner)),
+        Comment::MultiLine(inner) => Comment::MultiLine(v.fold_comment_multi_line(inner)),
+    }
+}
+
+pub fn fold_comment_multi_line<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: CommentMultiLine<'a>,
+) -> CommentMultiLine<'a> {
+    CommentMultiLine {
+        slash_asterisk_token: token::SlashAsterisk(v.fold_span(node.slash_asterisk_token.0)),
+        content_span: v.fold_span(node.content_span),
+        content: node.content,
+    }
+}
+
+pub fn fold_comment_single_line<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: CommentSingleLine<'a>,
+) -> CommentSingleLine<'a> {
+    CommentSingleLine {
+        slash_slash_token: token::SlashSlash(v.fold_span(node.slash_slash_token.0)),
+        content_span: v.fold_span(node.content_span),
+        content: node.content,
+    }
+}
+
+pub fn fold_conditional_param_def<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: ConditionalParamDef<'a>,
+) -> ConditionalParamDef<'a> {
+    ConditionalParamDef {
+        var_ident: v.fold_ident(node.var_ident),
+        bit_selector: node.bit_selector.map(|it| v.fold_bit_selector(it)),
+        question_token: token::Question(v.fold_span(node.question_token.0)),
+    }
+}
+
+pub fn fold_delimiter<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: Delimiter) -> Delimiter {
+    match node {
+        Delimiter::Types(inner) => Delimiter::Types(v.fold_delimiter_types(inner)),
+        Delimiter::Functions(inner) => Delimiter::Functions(v.fold_delimiter_functions(inner)),
+    }
+}
+
+pub fn fold_delimiter_functions<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: DelimiterFunctions,
+) -> DelimiterFunctions {
+    DelimiterFunctions {
+        span: v.fold_span(node.span),
+    }
+}
+
+pub fn fold_delimiter_types<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: DelimiterTypes,
+) -> DelimiterTypes {
+    DelimiterTypes {
+        span: v.fold_span(node.span),
+    }
+}
+
+pub fn fold_doc_comment<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: DocComment<'a>) -> DocComment<'a> {
+    DocComment {
+        tags: node.tags.into_iter().map(|it| v.fold_doc_comment_tag(it)).collect(),
+    }
+}
+
+pub fn fold_doc_comment_tag<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: DocCommentTag<'a>,
+) -> DocCommentTag<'a> {
+    DocCommentTag {
+        name_span: v.fold_span(node.name_span),
+        name: node.name,
+        text_span: v.fold_span(node.text_span),
+        text: node.text,
+    }
+}
+
+pub fn fold_file<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: File<'a>) -> File<'a> {
+    File {
+        items: node.items.into_iter().map(|it| v.fold_item(it)).collect(),
+    }
+}
+
+pub fn fold_generic_argument<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: GenericArgument<'a>,
+) -> GenericArgument<'a> {
+    match node {
+        GenericArgument::Type(inner) => GenericArgument::Type(v.fold_type(inner)),
+        GenericArgument::Nat(inner) => GenericArgument::Nat(v.fold_nat_expr(inner)),
+    }
+}
+
+pub fn fold_generic_arguments<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: GenericArguments<'a>,
+) -> GenericArguments<'a> {
+    match node {
+        GenericArguments::AngleBracketed(inner) => GenericArguments::AngleBracketed(v.fold_angle_bracketed_generic_arguments(inner)),
+        GenericArguments::SpaceSeparated(inner) => GenericArguments::SpaceSeparated(v.fold_space_separated_generic_arguments(inner)),
+    }
+}
+
+pub fn fold_id<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: Id) -> Id {
+    Id {
+        span: v.fold_span(node.span),
+        id: node.id,
+        format: node.format,
+    }
+}
+
+pub fn fold_ident<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: Ident<'a>) -> Ident<'a> {
+    let mut node = node;
+    node.span = v.fold_span(node.span);
+    node
+}
+
+pub fn fold_item<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: Item<'a>) -> Item<'a> {
+    match node {
+        Item::Combinator(inner) => Item::Combinator(v.fold_item_combinator(inner)),
+        Item::Builtin(inner) => Item::Builtin(v.fold_item_builtin(inner)),
+        Item::TypeDeclaration(inner) => Item::TypeDeclaration(v.fold_item_type_declaration(inner)),
+        Item::Delimiter(inner) => Item::Delimiter(v.fold_item_delimiter(inner)),
+        Item::Layer(inner) => Item::Layer(v.fold_item_layer(inner)),
+        Item::Comment(inner) => Item::Comment(v.fold_item_comment(inner)),
+        Item::Error(inner) => Item::Error(v.fold_item_error(inner)),
+    }
+}
+
+pub fn fold_item_builtin<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: ItemBuiltin<'a>,
+) -> ItemBuiltin<'a> {
+    ItemBuiltin {
+        name: v.fold_path(node.name),
+        combinator_id: node.combinator_id.map(|it| v.fold_combinator_id(it)),
+        question_token: token::Question(v.fold_span(node.question_token.0)),
+        equals_token: token::Equals(v.fold_span(node.equals_token.0)),
+        result_type: v.fold_path(node.result_type),
+        semicolon_token: token::Semicolon(v.fold_span(node.semicolon_token.0)),
+    }
+}
+
+pub fn fold_item_combinator<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: ItemCombinator<'a>,
+) -> ItemCombinator<'a> {
+    ItemCombinator {
+        leading_comments: node.leading_comments.into_iter().map(|it| v.fold_comment(it)).collect(),
+        doc_comment: node.doc_comment.map(|it| v.fold_doc_comment(it)),
+        name: v.fold_path(node.name),
+        combinator_id: node.combinator_id.map(|it| v.fold_combinator_id(it)),
+        opt_params: node.opt_params.into_iter().map(|it| v.fold_opt_param(it)).collect(),
+        opt_param_comments: node.opt_param_comments.into_iter().map(|it| v.fold_param_comment(it)).collect(),
+        params: node.params.into_iter().map(|it| v.fold_param(it)).collect(),
+        param_comments: node.param_comments.into_iter().map(|it| v.fold_param_comment(it)).collect(),
+        equals_token: token::Equals(v.fold_span(node.equals_token.0)),
+        result_comments: node.result_comments.into_iter().map(|it| v.fold_comment(it)).collect(),
+        result_type: v.fold_parameterized_path(node.result_type),
+        semicolon_token: token::Semicolon(v.fold_span(node.semicolon_token.0)),
+        trailing_comment: node.trailing_comment.map(|it| v.fold_comment(it)),
+    }
+}
+
+pub fn fold_item_comment<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: ItemComment<'a>,
+) -> ItemComment<'a> {
+    ItemComment {
+        comment: v.fold_comment(node.comment),
+    }
+}
+
+pub fn fold_item_delimiter<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: ItemDelimiter,
+) -> ItemDelimiter {
+    ItemDelimiter {
+        delimiter: v.fold_delimiter(node.delimiter),
+    }
+}
+
+pub fn fold_item_error<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: ItemError<'a>) -> ItemError<'a> {
+    ItemError {
+        span: v.fold_span(node.span),
+        text: node.text,
+    }
+}
+
+pub fn fold_item_layer<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: ItemLayer) -> ItemLayer {
+    ItemLayer {
+        marker: v.fold_layer_marker(node.marker),
+        layer_span: v.fold_span(node.layer_span),
+        layer: node.layer,
+    }
+}
+
+pub fn fold_item_type_declaration<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: ItemTypeDeclaration<'a>,
+) -> ItemTypeDeclaration<'a> {
+    ItemTypeDeclaration {
+        kind: v.fold_type_declaration_kind(node.kind),
+        name: v.fold_path(node.name),
+        semicolon_token: token::Semicolon(v.fold_span(node.semicolon_token.0)),
+    }
+}
+
+pub fn fold_layer_keyword<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: LayerKeyword) -> LayerKeyword {
+    match node {
+        LayerKeyword::Upper(token) => LayerKeyword::Upper(token::Layer(v.fold_span(token.0))),
+        LayerKeyword::TitleCase(token) => LayerKeyword::TitleCase(token::LayerTitleCase(v.fold_span(token.0))),
+    }
+}
+
+pub fn fold_layer_marker<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: LayerMarker) -> LayerMarker {
+    match node {
+        LayerMarker::SingleLine(inner) => LayerMarker::SingleLine(v.fold_layer_marker_single_line(inner)),
+        LayerMarker::MultiLine(inner) => LayerMarker::MultiLine(v.fold_layer_marker_multi_line(inner)),
+        LayerMarker::Equals(inner) => LayerMarker::Equals(v.fold_layer_marker_equals(inner)),
+    }
+}
+
+pub fn fold_layer_marker_equals<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: LayerMarkerEquals,
+) -> LayerMarkerEquals {
+    LayerMarkerEquals {
+        left_span: v.fold_span(node.left_span),
+        right_span: v.fold_span(node.right_span),
+    }
+}
+
+pub fn fold_layer_marker_multi_line<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: LayerMarkerMultiLine,
+) -> LayerMarkerMultiLine {
+    LayerMarkerMultiLine {
+        slash_asterisk_token: token::SlashAsterisk(v.fold_span(node.slash_asterisk_token.0)),
+        layer_keyword: v.fold_layer_keyword(node.layer_keyword),
+    }
+}
+
+pub fn fold_layer_marker_single_line<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: LayerMarkerSingleLine,
+) -> LayerMarkerSingleLine {
+    LayerMarkerSingleLine {
+        slash_slash_token: token::SlashSlash(v.fold_span(node.slash_slash_token.0)),
+        layer_keyword: v.fold_layer_keyword(node.layer_keyword),
+    }
+}
+
+pub fn fold_multiplicity<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: Multiplicity<'a>,
+) -> Multiplicity<'a> {
+    Multiplicity {
+        term: v.fold_nat_expr(node.term),
+        asterisk_token: token::Asterisk(v.fold_span(node.asterisk_token.0)),
+    }
+}
+
+pub fn fold_nat_expr<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: NatExpr<'a>) -> NatExpr<'a> {
+    match node {
+        NatExpr::Const(inner) => NatExpr::Const(v.fold_nat_expr_const(inner)),
+        NatExpr::Ident(inner) => NatExpr::Ident(v.fold_nat_expr_ident(inner)),
+        NatExpr::Add(inner) => NatExpr::Add(v.fold_nat_expr_add(inner)),
+        NatExpr::Paren(inner) => NatExpr::Paren(v.fold_nat_expr_paren(inner)),
+    }
+}
+
+pub fn fold_nat_expr_add<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: NatExprAdd<'a>,
+) -> NatExprAdd<'a> {
+    NatExprAdd {
+        left: Box::new(v.fold_nat_expr(*node.left)),
+        plus_token: token::Plus(v.fold_span(node.plus_token.0)),
+        right: Box::new(v.fold_nat_expr(*node.right)),
+    }
+}
+
+pub fn fold_nat_expr_const<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: NatExprConst,
+) -> NatExprConst {
+    NatExprConst {
+        span: v.fold_span(node.span),
+        value: node.value,
+    }
+}
+
+pub fn fold_nat_expr_ident<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: NatExprIdent<'a>,
+) -> NatExprIdent<'a> {
+    NatExprIdent {
+        ident: v.fold_ident(node.ident),
+    }
+}
+
+pub fn fold_nat_expr_paren<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: NatExprParen<'a>,
+) -> NatExprParen<'a> {
+    NatExprParen {
+        paren_token: token::Paren(v.fold_span(node.paren_token.0)),
+        expr: Box::new(v.fold_nat_expr(*node.expr)),
+    }
+}
+
+pub fn fold_opt_param<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: OptParam<'a>) -> OptParam<'a> {
+    OptParam {
+        brace_token: token::Brace(v.fold_span(node.brace_token.0)),
+        var_idents: node.var_idents.into_iter().map(|it| v.fold_ident(it)).collect(),
+        colon_token: token::Colon(v.fold_span(node.colon_token.0)),
+        ty: v.fold_type(node.ty),
+    }
+}
+
+pub fn fold_param<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: Param<'a>) -> Param<'a> {
+    match node {
+        Param::Conditional(inner) => Param::Conditional(v.fold_param_conditional(inner)),
+        Param::Repeated(inner) => Param::Repeated(v.fold_param_repeated(inner)),
+        Param::WithParen(inner) => Param::WithParen(v.fold_param_with_paren(inner)),
+        Param::TypeOnly(inner) => Param::TypeOnly(v.fold_param_type_only(inner)),
+    }
+}
+
+pub fn fold_param_comment<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: ParamComment<'a>,
+) -> ParamComment<'a> {
+    ParamComment {
+        index: node.index,
+        comment: v.fold_comment(node.comment),
+    }
+}
+
+pub fn fold_param_conditional<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: ParamConditional<'a>,
+) -> ParamConditional<'a> {
+    ParamConditional {
+        var_ident: v.fold_ident(node.var_ident),
+        colon_token: token::Colon(v.fold_span(node.colon_token.0)),
+        conditional_param_def: node.conditional_param_def.map(|it| v.fold_conditional_param_def(it)),
+        ty: v.fold_type(node.ty),
+    }
+}
+
+pub fn fold_param_repeated<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: ParamRepeated<'a>,
+) -> ParamRepeated<'a> {
+    ParamRepeated {
+        param_repeated_ident: node.param_repeated_ident.map(|it| v.fold_param_repeated_ident(it)),
+        multiplicity: node.multiplicity.map(|it| v.fold_multiplicity(it)),
+        bracket_token: token::Bracket(v.fold_span(node.bracket_token.0)),
+        params: node.params.into_iter().map(|it| v.fold_param(it)).collect(),
+    }
+}
+
+pub fn fold_param_repeated_ident<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: ParamRepeatedIdent<'a>,
+) -> ParamRepeatedIdent<'a> {
+    ParamRepeatedIdent {
+        var_ident: v.fold_ident(node.var_ident),
+        colon_token: token::Colon(v.fold_span(node.colon_token.0)),
+    }
+}
+
+pub fn fold_param_type_only<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: ParamTypeOnly<'a>,
+) -> ParamTypeOnly<'a> {
+    ParamTypeOnly {
+        ty: v.fold_type(node.ty),
+    }
+}
+
+pub fn fold_param_with_paren<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: ParamWithParen<'a>,
+) -> ParamWithParen<'a> {
+    ParamWithParen {
+        paren_token: token::Paren(v.fold_span(node.paren_token.0)),
+        var_idents: node.var_idents.into_iter().map(|it| v.fold_ident(it)).collect(),
+        colon_token: token::Colon(v.fold_span(node.colon_token.0)),
+        ty: v.fold_type(node.ty),
+    }
+}
+
+pub fn fold_parameterized_path<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: ParameterizedPath<'a>,
+) -> ParameterizedPath<'a> {
+    ParameterizedPath {
+        path: v.fold_path(node.path),
+        args: node.args.map(|it| v.fold_generic_arguments(it)),
+    }
+}
+
+pub fn fold_path<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: Path<'a>) -> Path<'a> {
+    Path {
+        segments: {
+            let mut segments = Punctuated::new();
+            for pair in node.segments.into_pairs() {
+                match pair {
+                    Pair::Punctuated(value, punct) => {
+                        segments.push_value(v.fold_ident(value));
+                        segments.push_punct(token::Dot(v.fold_span(punct.0)));
+                    },
+                    Pair::End(value) => segments.push_value(v.fold_ident(value)),
+                }
+            }
+            segments
+        },
+    }
+}
+
+pub fn fold_space_separated_generic_arguments<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: SpaceSeparatedGenericArguments<'a>,
+) -> SpaceSeparatedGenericArguments<'a> {
+    SpaceSeparatedGenericArguments {
+        args: node.args.into_iter().map(|it| v.fold_generic_argument(it)).collect(),
+    }
+}
+
+pub fn fold_type<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: Type<'a>) -> Type<'a> {
+    match node {
+        Type::Int(inner) => Type::Int(v.fold_type_int(inner)),
+        Type::ParameterizedPath(inner) => Type::ParameterizedPath(v.fold_type_parameterized_path(inner)),
+        Type::TypeParameter(inner) => Type::TypeParameter(v.fold_type_type_parameter(inner)),
+        Type::Bare(inner) => Type::Bare(v.fold_type_bare(inner)),
+        Type::Paren(inner) => Type::Paren(v.fold_type_paren(inner)),
+    }
+}
+
+pub fn fold_type_bare<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: TypeBare<'a>) -> TypeBare<'a> {
+    TypeBare {
+        percent_token: token::Percent(v.fold_span(node.percent_token.0)),
+        inner: Box::new(v.fold_type(*node.inner)),
+    }
+}
+
+pub fn fold_type_declaration_kind<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: TypeDeclarationKind,
+) -> TypeDeclarationKind {
+    match node {
+        TypeDeclarationKind::New(token) => TypeDeclarationKind::New(token::New(v.fold_span(token.0))),
+        TypeDeclarationKind::Final(token) => TypeDeclarationKind::Final(token::Final(v.fold_span(token.0))),
+        TypeDeclarationKind::Empty(token) => TypeDeclarationKind::Empty(token::Empty(v.fold_span(token.0))),
+    }
+}
+
+pub fn fold_type_int<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: TypeInt) -> TypeInt {
+    TypeInt {
+        hash_token: token::Hash(v.fold_span(node.hash_token.0)),
+    }
+}
+
+pub fn fold_type_parameterized_path<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: TypeParameterizedPath<'a>,
+) -> TypeParameterizedPath<'a> {
+    TypeParameterizedPath {
+        parameterized_path: v.fold_parameterized_path(node.parameterized_path),
+    }
+}
+
+pub fn fold_type_paren<'a, V: Fold<'a> + ?Sized>(v: &mut V, node: TypeParen<'a>) -> TypeParen<'a> {
+    TypeParen {
+        paren_token: token::Paren(v.fold_span(node.paren_token.0)),
+        inner: Box::new(v.fold_type(*node.inner)),
+    }
+}
+
+pub fn fold_type_type_parameter<'a, V: Fold<'a> + ?Sized>(
+    v: &mut V,
+    node: TypeTypeParameter<'a>,
+) -> TypeTypeParameter<'a> {
+    TypeTypeParameter {
+        excl_token: token::Excl(v.fold_span(node.excl_token.0)),
+        ident: v.fold_ident(node.ident),
+    }
+}
+
+
+#[cfg(all(test, feature = "parsing"))]
+mod tests {
+    use super::*;
+    use spanned::Spanned;
+
+    struct ShiftSpans(usize);
+
+    impl<'a> Fold<'a> for ShiftSpans {
+        fn fold_span(&mut self, span: Span) -> Span {
+            Span::new(span.begin() + self.0, span.end() + self.0)
+        }
+    }
+
+    #[test]
+    fn shift_spans() {
+        let input = "boolTrue#997275b5 = Bool;";
+        let file = ::parse_file_str(input).unwrap();
+        let span = file.items[0].span();
+
+        let file = ShiftSpans(10).fold_file(file);
+
+        assert_eq!(file.items[0].span(), Span::new(span.begin() + 10, span.end() + 10));
+    }
+}
@@ -3,13 +3,91 @@ use span::Span;
 
 macro_attr_many! {
     /// A 32-bit number which identifies a TL combinator.
-    #[cfg_derive!(Clone, Debug)]
+    #[cfg_derive!(Clone, Debug, Deserialize, Serialize)]
     pub struct Id {
         pub span: Span,
         pub id: u32,
+        pub format: IdFormat,
     }
 }
 
+/// The way an `Id` is written in the source text: how many hex digits it has
+/// and which of them are uppercase letters.
+///
+/// The format does not participate in equality and hashing of `Id`s.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde-impls", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct IdFormat {
+    digit_count: u8,
+    // Bit `i` is set if the `i`-th digit counting from the least significant
+    // one is an uppercase letter
+    uppercase_mask: u8,
+}
+
+// 8 lowercase hex digits, as in `vector#1cb5c415`
+impl Default for IdFormat {
+    fn default() -> IdFormat {
+        IdFormat { digit_count: 8, uppercase_mask: 0 }
+    }
+}
+
+impl IdFormat {
+    /// Create a new `IdFormat` with `digit_count` digits which are either all
+    /// lowercase or all uppercase.
+    ///
+    /// Return `None` if `digit_count` is not in the `1..=8` range.
+    pub fn new(digit_count: u8, uppercase: bool) -> Option<IdFormat> {
+        if digit_count == 0 || digit_count > 8 {
+            return None;
+        }
+
+        let uppercase_mask = if uppercase { 0xFF } else { 0 };
+
+        Some(IdFormat { digit_count, uppercase_mask })
+    }
+
+    /// Create a new `IdFormat` with `digit_count` digits, where the `i`-th
+    /// digit counting from the least significant one is an uppercase letter
+    /// if bit `i` of `uppercase_mask` is set.
+    ///
+    /// # Safety
+    ///
+    /// `digit_count` must be in the `1..=8` range.
+    ///
+    /// If conditions are not met, it is a violation of safety guarantees.
+    pub unsafe fn new_unchecked(digit_count: u8, uppercase_mask: u8) -> IdFormat {
+        IdFormat { digit_count, uppercase_mask }
+    }
+
+    /// Return the number of hex digits, including leading zeros.
+    pub fn digit_count(&self) -> u8 {
+        self.digit_count
+    }
+
+    /// Return true if the `index`-th digit, counting from the least
+    /// significant one, is printed as an uppercase letter.
+    pub fn is_uppercase_digit(&self, index: u8) -> bool {
+        index < 8 && self.uppercase_mask & (1 << index) != 0
+    }
+
+    /// Return true if this format has 8 digits and no uppercase letters.
+    pub fn is_canonical(&self) -> bool {
+        *self == IdFormat::default()
+    }
+}
+
+/// A configuration option that specifies which combinator ids are accepted
+/// while parsing.
+#[cfg(feature = "parsing")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IdValidation {
+    /// Accept ids with 1 to 8 hex digits, as in
+    /// `storage.fileJpeg#7efe0e = storage.FileType;`.
+    Lenient,
+    /// Accept only ids with exactly 8 hex digits.
+    Strict,
+}
+
 #[cfg(feature = "eq-impls")]
 mod eq_impls {
     use super::*;
@@ -53,6 +131,8 @@ mod spanned {
 
 #[cfg(feature = "parsing")]
 mod parsing {
+    use nom;
+
     use super::*;
     use cursor::Cursor;
     use synom::Synom;
@@ -61,19 +141,56 @@ mod parsing {
 
     impl Sealed for Id {}
 
-    impl Synom for Id {
-        named!(parse_cursor(Cursor) -> Id, do_parse!(
+    // `take_while_m_n!` expands to a manual range check
+    #[allow(clippy::manual_range_contains)]
+    impl<'a> Synom<'a> for Id {
+        named!(parse_cursor(Cursor<'a>) -> Id, do_parse!(
             // (8, 8) doesn't work for `storage.fileJpeg#7efe0e = storage.FileType;`
             //id_cursor: take_while_m_n!(8, 8, is_hex_digit) >>
             // Cap at 8 hex digits, because ids are 32-bit numbers, but there
             // must be at least one
             id_cursor: take_while_m_n!(1, 8, is_hex_digit) >>
+            call!(check_id_end) >>
+            call!(check_id_validation, id_cursor) >>
             id: map_res!(value!(id_cursor.to_str()), u32_from_hex_str) >>
             span: value!(id_cursor.span()) >>
+            format: value!(IdFormat::from_hex_str(id_cursor.to_str())) >>
 
-            (Id { span, id })
+            (Id { span, id, format })
         ));
     }
+
+    impl IdFormat {
+        fn from_hex_str(s: &str) -> IdFormat {
+            let digit_count = s.len() as u8;
+            let uppercase_mask = s.chars().rev().enumerate()
+                .filter(|&(_, c)| c.is_ascii_uppercase())
+                .fold(0, |mask, (i, _)| mask | (1 << i));
+
+            IdFormat { digit_count, uppercase_mask }
+        }
+    }
+
+    // An id must not run into more digits or letters, like `#0e16a8a5f` or
+    // `#1bz`
+    fn check_id_end(input: Cursor) -> nom::IResult<Cursor, ()> {
+        match input.to_str().chars().next() {
+            Some(c) if c.is_alphanumeric() || c == '_' => {
+                Err(nom::Err::Error(error_position!(input, nom::ErrorKind::Verify)))
+            },
+            _ => Ok((input, ())),
+        }
+    }
+
+    fn check_id_validation<'a>(input: Cursor<'a>, id_cursor: Cursor<'a>) -> nom::IResult<Cursor<'a>, ()> {
+        match id_cursor.id_validation() {
+            IdValidation::Strict if id_cursor.to_str().len() != 8 => {
+                Err(nom::Err::Error(error_position!(id_cursor, nom::ErrorKind::Verify)))
+            },
+            IdValidation::Lenient |
+            IdValidation::Strict => Ok((input, ())),
+        }
+    }
 }
 
 #[cfg(feature = "printing")]
@@ -88,7 +205,21 @@ mod printing {
 
     impl Print for Id {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            fmt::LowerHex::fmt(&self.id, f)
+            let width = self.format.digit_count() as usize;
+            let digits = format!("{:0width$x}", self.id, width = width);
+            let len = digits.len();
+
+            for (i, c) in digits.chars().enumerate() {
+                let index = (len - 1 - i) as u8;
+
+                if self.format.is_uppercase_digit(index) {
+                    fmt::Write::write_char(f, c.to_ascii_uppercase())?;
+                } else {
+                    fmt::Write::write_char(f, c)?;
+                }
+            }
+
+            Ok(())
         }
     }
 }
@@ -120,8 +251,8 @@ mod tests {
         for id1 in &ids {
             for id2 in &ids {
                 test_span_permutations(
-                    |span1| Id { span: span1, id: *id1 },
-                    |span2| Id { span: span2, id: *id2 },
+                    |span1| Id { span: span1, id: *id1, format: IdFormat::default() },
+                    |span2| Id { span: span2, id: *id2, format: IdFormat::default() },
                     &test_eq,
                     &assert_when_eq,
                     &assert_when_ne,
@@ -140,6 +271,25 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "eq-impls")]
+    #[test]
+    fn eq_does_not_depend_on_format() {
+        let formats = [
+            IdFormat::default(),
+            IdFormat::new(6, false).unwrap(),
+            IdFormat::new(8, true).unwrap(),
+        ];
+
+        for format1 in &formats {
+            for format2 in &formats {
+                let id1 = Id { span: Span::zeroed(), id: 0x7efe0e, format: *format1 };
+                let id2 = Id { span: Span::zeroed(), id: 0x7efe0e, format: *format2 };
+
+                any_debug_assert_eq!(id1, id2);
+            }
+        }
+    }
+
     #[cfg(all(feature = "eq-impls", feature = "hash-impls"))]
     #[test]
     fn eq_hash_property() {
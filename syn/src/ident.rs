@@ -1,24 +1,39 @@
+#[cfg(feature = "interning")]
+use std::borrow::Borrow;
+use std::borrow::Cow;
+
 use span::Span;
+#[cfg(feature = "interning")]
+use symbol::Symbol;
 
 
 macro_attr_many! {
     /// An identifier: `channels`, `SendMessageAction`, `X`, etc..
+    ///
+    /// A parsed identifier borrows its string from the source text; use
+    /// `IntoOwned` to detach it.
+    ///
+    /// With the "interning" feature enabled, an identifier also holds the
+    /// `Symbol` of its string, which makes comparing and hashing identifiers
+    /// take constant time. Maps keyed by identifiers can then be looked up by
+    /// a `Symbol`.
     #[cfg_derive!(Clone, Debug)]
-    pub struct Ident {
-        span: Span,
-        string: String,
+    pub struct Ident<'a> {
+        pub(crate) span: Span,
+        string: Cow<'a, str>,
+        #[cfg(feature = "interning")]
+        symbol: Symbol,
     }
 }
 
-impl Ident {
+impl<'a> Ident<'a> {
     /// Create a new `Ident` with the given `span` and the given `string` if
     /// the string is a valid TL language identifier.
-    pub fn new(span: Span, string: &str) -> Option<Ident> {
-        if is_valid_ident(string) {
-            Some(Ident {
-                span,
-                string: string.to_owned(),
-            })
+    pub fn new<S: Into<Cow<'a, str>>>(span: Span, string: S) -> Option<Ident<'a>> {
+        let string = string.into();
+
+        if is_valid_ident(&string) {
+            Some(Ident::from_cow(span, string))
         } else {
             None
         }
@@ -32,10 +47,16 @@ impl Ident {
     /// The string must be a valid TL language identifier.
     ///
     /// If conditions are not met, it is a violation of safety guarantees.
-    pub unsafe fn new_unchecked(span: Span, string: &str) -> Ident {
+    pub unsafe fn new_unchecked<S: Into<Cow<'a, str>>>(span: Span, string: S) -> Ident<'a> {
+        Ident::from_cow(span, string.into())
+    }
+
+    fn from_cow(span: Span, string: Cow<'a, str>) -> Ident<'a> {
         Ident {
             span,
-            string: string.to_owned(),
+            #[cfg(feature = "interning")]
+            symbol: Symbol::intern(&string),
+            string,
         }
     }
 
@@ -44,6 +65,12 @@ impl Ident {
         &self.string
     }
 
+    /// Get the interned string of this `Ident`.
+    #[cfg(feature = "interning")]
+    pub fn symbol(&self) -> Symbol {
+        self.symbol
+    }
+
     /// Return true if the first character of this `Ident` is lowercase, and
     /// false otherwise.
     pub fn is_lowercase(&self) -> bool {
@@ -63,14 +90,31 @@ impl Ident {
     }
 }
 
+// With interning enabled, `Eq` and `Hash` of `Ident` only depend on its
+// symbol, so that an `Ident` can be looked up by a `Symbol` in maps.
+// `Borrow<str>` isn't implemented in any configuration, since it would
+// require hashing by the string and enabling a feature must not remove it.
+#[cfg(feature = "interning")]
+impl<'a> Borrow<Symbol> for Ident<'a> {
+    fn borrow(&self) -> &Symbol {
+        &self.symbol
+    }
+}
+
 #[cfg(feature = "eq-impls")]
 mod eq_impls {
     use super::*;
 
-    impl Eq for Ident {}
+    impl<'a> Eq for Ident<'a> {}
+
+    impl<'a> PartialEq for Ident<'a> {
+        #[cfg(feature = "interning")]
+        fn eq(&self, other: &Ident<'a>) -> bool {
+            self.symbol == other.symbol
+        }
 
-    impl PartialEq for Ident {
-        fn eq(&self, other: &Ident) -> bool {
+        #[cfg(not(feature = "interning"))]
+        fn eq(&self, other: &Ident<'a>) -> bool {
             self.string == other.string
         }
     }
@@ -82,28 +126,97 @@ mod hash_impls {
 
     use super::*;
 
-    impl Hash for Ident {
+    impl<'a> Hash for Ident<'a> {
+        #[cfg(feature = "interning")]
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.symbol.hash(state)
+        }
+
+        #[cfg(not(feature = "interning"))]
         fn hash<H: Hasher>(&self, state: &mut H) {
             self.string.hash(state)
         }
     }
 }
 
+#[cfg(feature = "serde-impls")]
+mod serde_impls {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::de::Error;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    #[serde(rename = "Ident")]
+    struct IdentRef<'r> {
+        span: Span,
+        string: &'r str,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "Ident")]
+    struct IdentOwned {
+        span: Span,
+        string: String,
+    }
+
+    impl<'a> Serialize for Ident<'a> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            IdentRef { span: self.span, string: &self.string }.serialize(serializer)
+        }
+    }
+
+    impl<'de, 'a> Deserialize<'de> for Ident<'a> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Ident<'a>, D::Error> {
+            let IdentOwned { span, string } = IdentOwned::deserialize(deserializer)?;
+
+            if is_valid_ident(&string) {
+                Ok(Ident::from_cow(span, Cow::Owned(string)))
+            } else {
+                Err(D::Error::custom(format!("invalid identifier: `{}`", string)))
+            }
+        }
+    }
+}
+
 mod spanned {
     use super::*;
     use span::Span;
     use spanned::Spanned;
     use spanned::private::Sealed;
 
-    impl Sealed for Ident {}
+    impl<'a> Sealed for Ident<'a> {}
 
-    impl Spanned for Ident {
+    impl<'a> Spanned for Ident<'a> {
         fn span(&self) -> Span {
             self.span
         }
     }
 }
 
+mod owned {
+    use super::*;
+    use owned::IntoOwned;
+    use owned::private::Sealed;
+
+    impl_to_owned!(Ident);
+
+    impl<'a> Sealed for Ident<'a> {}
+
+    impl<'a> IntoOwned for Ident<'a> {
+        type Owned = Ident<'static>;
+
+        fn into_owned(self) -> Ident<'static> {
+            Ident {
+                span: self.span,
+                string: Cow::Owned(self.string.into_owned()),
+                #[cfg(feature = "interning")]
+                symbol: self.symbol,
+            }
+        }
+    }
+}
+
 #[cfg(feature = "parsing")]
 mod parsing {
     use super::*;
@@ -111,17 +224,14 @@ mod parsing {
     use synom::Synom;
     use synom::private::Sealed;
 
-    impl Sealed for Ident {}
+    impl<'a> Sealed for Ident<'a> {}
 
-    impl Synom for Ident {
-        named!(parse_cursor(Cursor) -> Ident, do_parse!(
+    impl<'a> Synom<'a> for Ident<'a> {
+        named!(parse_cursor(Cursor<'a>) -> Ident<'a>, do_parse!(
             ident_str_cursor: take_while!(is_ident_char) >>
             ident_str: verify!(value!(ident_str_cursor.to_str()), is_valid_ident) >>
 
-            (Ident {
-                span: ident_str_cursor.span(),
-                string: ident_str.to_owned(),
-            })
+            (Ident::from_cow(ident_str_cursor.span(), Cow::Borrowed(ident_str)))
         ));
     }
 }
@@ -134,9 +244,9 @@ mod printing {
     use print::Print;
     use print::private::Sealed;
 
-    impl Sealed for Ident {}
+    impl<'a> Sealed for Ident<'a> {}
 
-    impl Print for Ident {
+    impl<'a> Print for Ident<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             fmt::Display::fmt(&self.string, f)
         }
@@ -150,24 +260,14 @@ mod printing {
 // * decimal digit
 // * underscore
 fn is_ident_char(c: char) -> bool {
-    match c {
-        'A' ... 'Z' |
-        'a' ... 'z' |
-        '0' ... '9' |
-        '_'         => true,
-        _           => false,
-    }
+    c.is_ascii_alphanumeric() || c == '_'
 }
 
 // A valid identifier beginning char must be either of:
 // * uppercase Latin letter
 // * lowercase Latin letter
 fn is_ident_beginning_char(c: char) -> bool {
-    match c {
-        'A' ... 'Z' |
-        'a' ... 'z' => true,
-        _           => false,
-    }
+    c.is_ascii_alphabetic()
 }
 
 // A valid identifier must:
@@ -187,7 +287,7 @@ fn is_valid_ident(s: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    #[cfg(feature = "eq-impls")]
+    #[cfg(any(feature = "eq-impls", feature = "interning", feature = "serde-impls"))]
     use super::*;
     #[cfg(feature = "eq-impls")]
     use utils::tests::test_span_permutations;
@@ -213,8 +313,8 @@ mod tests {
         for ident1 in &idents {
             for ident2 in &idents {
                 test_span_permutations(
-                    |span1| Ident { span: span1, string: (*ident1).to_owned() },
-                    |span2| Ident { span: span2, string: (*ident2).to_owned() },
+                    |span1| Ident::from_cow(span1, Cow::Borrowed(*ident1)),
+                    |span2| Ident::from_cow(span2, Cow::Borrowed(*ident2)),
                     &test_eq,
                     &assert_when_eq,
                     &assert_when_ne,
@@ -242,4 +342,51 @@ mod tests {
             |x, y| any_debug_assert_ne!(get_hasher_state(x), get_hasher_state(y)),
         );
     }
+
+    #[cfg(feature = "serde-impls")]
+    #[test]
+    fn serde() {
+        use span::Span;
+
+        let ident = Ident::new(Span::new(1, 4), "foo").unwrap();
+        let json = ::serde_json::to_string(&ident).unwrap();
+
+        assert_eq!(json, r#"{"span":{"begin":1,"end":4},"string":"foo"}"#);
+        assert_eq!(::serde_json::from_str::<Ident>(&json).unwrap().as_str(), "foo");
+        assert!(::serde_json::from_str::<Ident>(r#"{"span":{"begin":1,"end":4},"string":"4oo"}"#).is_err());
+    }
+
+    #[cfg(feature = "interning")]
+    #[test]
+    fn symbol() {
+        use owned::IntoOwned;
+        use span::Span;
+        use symbol::Symbol;
+
+        let source = String::from("flags");
+        let borrowed = Ident::new(Span::new(1, 6), source.as_str()).unwrap();
+        let created = Ident::new(Span::zeroed(), "flags").unwrap();
+
+        assert_eq!(borrowed.symbol(), created.symbol());
+        assert_eq!(borrowed.symbol(), Symbol::intern("flags"));
+        assert_ne!(borrowed.symbol(), Ident::new(Span::zeroed(), "int").unwrap().symbol());
+        assert_eq!(borrowed.into_owned().symbol(), created.symbol());
+    }
+
+    #[cfg(all(feature = "interning", feature = "eq-impls", feature = "hash-impls"))]
+    #[test]
+    fn symbol_keyed_map() {
+        use std::collections::HashMap;
+
+        use span::Span;
+        use symbol::Symbol;
+
+        let mut map = HashMap::new();
+        map.insert(Ident::new(Span::new(1, 6), "flags").unwrap(), 1);
+        map.insert(Ident::new(Span::zeroed(), "int").unwrap(), 2);
+
+        assert_eq!(map.get(&Symbol::intern("flags")), Some(&1));
+        assert_eq!(map.get(&Ident::new(Span::zeroed(), "int").unwrap()), Some(&2));
+        assert_eq!(map.get(&Symbol::intern("Vector")), None);
+    }
 }
@@ -1,154 +1,474 @@
-use super::{BitIndex, Comment, Id, Ident, ParameterizedPath, Path, Type};
+use std::borrow::Cow;
+
+use super::{BitIndex, Comment, DocComment, Id, IdFormat, Ident, NatExpr, ParameterizedPath, Path, Type};
 use span::Span;
-use token::{Brace, Bracket, Paren, SlashSlash};
+use token::{Brace, Bracket, Paren, SlashAsterisk, SlashSlash};
 
 
 macro_attr_many! {
     /// Top-level entities in TL schema that occupy whole lines.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub enum Item {
-        Combinator(ItemCombinator),
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub enum Item<'a> {
+        Combinator(ItemCombinator<'a>),
+        Builtin(ItemBuiltin<'a>),
+        TypeDeclaration(ItemTypeDeclaration<'a>),
         Delimiter(ItemDelimiter),
         Layer(ItemLayer),
-        Comment(ItemComment),
+        Comment(ItemComment<'a>),
+        Error(ItemError<'a>),
     }
 
     /// A TL combinator item: `inputMediaPhoto#8f2ab2ec id:InputPhoto = InputMedia;`.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct ItemCombinator {
-        pub name: Path,
+    ///
+    /// Comments on the lines directly above the combinator are its leading
+    /// comments and a comment following the `;` on the same line is its
+    /// trailing comment. Comments between `=` and `;` are its result
+    /// comments.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct ItemCombinator<'a> {
+        pub leading_comments: Vec<Comment<'a>>,
+        /// The TDLib-style documentation comment made of `leading_comments`.
+        pub doc_comment: Option<DocComment<'a>>,
+        pub name: Path<'a>,
         pub combinator_id: Option<CombinatorId>,
-        pub opt_params: Vec<OptParam>,
-        pub params: Vec<Param>,
+        pub opt_params: Vec<OptParam<'a>>,
+        pub opt_param_comments: Vec<ParamComment<'a>>,
+        pub params: Vec<Param<'a>>,
+        pub param_comments: Vec<ParamComment<'a>>,
         pub equals_token: TLToken![=],
-        pub result_type: ParameterizedPath,
+        pub result_comments: Vec<Comment<'a>>,
+        pub result_type: ParameterizedPath<'a>,
         pub semicolon_token: TLToken![;],
+        pub trailing_comment: Option<Comment<'a>>,
+    }
+
+    /// A comment in a list of fields: `a:int /* the first */ b:int`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct ParamComment<'a> {
+        /// The index of the field the comment precedes. Equals the number of
+        /// fields if the comment goes after the last one.
+        pub index: usize,
+        pub comment: Comment<'a>,
     }
 
     /// A TL combinator id: `#1cb5c415`.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
     pub struct CombinatorId {
         pub hash_token: TLToken![#],
         pub id: Id,
     }
 
     /// An optional field declaration: `{X:Type}`.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct OptParam {
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct OptParam<'a> {
         pub brace_token: Brace,
-        pub var_idents: Vec<Ident>,
+        pub var_idents: Vec<Ident<'a>>,
         pub colon_token: TLToken![:],
-        pub ty: Type,
+        pub ty: Type<'a>,
+    }
+
+    /// What the variables of an optional field declaration stand for.
+    #[derive(Clone, Copy)]
+    #[cfg_derive!(Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub enum OptParamKind {
+        /// Type variables: `{X:Type}`.
+        Type,
+        /// Natural number variables: `{n:#}`.
+        Nat,
+        /// Variables of any other type: `{flags:Flags}`.
+        Other,
     }
 
     /// A required field declaration.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub enum Param {
-        Conditional(ParamConditional),
-        Repeated(ParamRepeated),
-        WithParen(ParamWithParen),
-        TypeOnly(ParamTypeOnly),
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub enum Param<'a> {
+        Conditional(ParamConditional<'a>),
+        Repeated(ParamRepeated<'a>),
+        WithParen(ParamWithParen<'a>),
+        TypeOnly(ParamTypeOnly<'a>),
     }
 
     /// A possibly conditional field: `bg_color:int`, `report_spam:flags.0?true`.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct ParamConditional {
-        pub var_ident: Ident,
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct ParamConditional<'a> {
+        pub var_ident: Ident<'a>,
         pub colon_token: TLToken![:],
-        pub conditional_param_def: Option<ConditionalParamDef>,
-        pub ty: Type,
+        pub conditional_param_def: Option<ConditionalParamDef<'a>>,
+        pub ty: Type<'a>,
     }
 
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct ConditionalParamDef {
-        pub var_ident: Ident,
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct ConditionalParamDef<'a> {
+        pub var_ident: Ident<'a>,
         pub bit_selector: Option<BitSelector>,
         pub question_token: TLToken![?],
     }
 
     /// Selects a bit from a `#` parameter.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
     pub struct BitSelector {
         pub dot_token: TLToken![.],
         pub bit_index: BitIndex,
     }
 
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct ParamRepeated {
-        pub param_repeated_ident: Option<ParamRepeatedIdent>,
-        pub multiplicity: Option<Multiplicity>,
+    /// A repeated block of fields: `# [ t ]`, `users:n*[ id:int name:string ]`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct ParamRepeated<'a> {
+        pub param_repeated_ident: Option<ParamRepeatedIdent<'a>>,
+        pub multiplicity: Option<Multiplicity<'a>>,
         pub bracket_token: Bracket,
-        pub params: Vec<Param>,
+        pub params: Vec<Param<'a>>,
     }
 
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct ParamRepeatedIdent {
-        pub var_ident: Ident,
+    /// The name of a repeated block of fields: `users:`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct ParamRepeatedIdent<'a> {
+        pub var_ident: Ident<'a>,
         pub colon_token: TLToken![:],
     }
 
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct Multiplicity {
-        pub term: Ident,  // FIXME: actually, it can be any term here
+    /// The number of repetitions of a repeated parameter: `n*`, `(n+1)*`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct Multiplicity<'a> {
+        pub term: NatExpr<'a>,
         pub asterisk_token: TLToken![*],
     }
 
     /// A declaration enclosed in parentheses that may have multiple fields.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct ParamWithParen {
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct ParamWithParen<'a> {
         pub paren_token: Paren,
-        pub var_idents: Vec<Ident>,
+        pub var_idents: Vec<Ident<'a>>,
         pub colon_token: TLToken![:],
-        pub ty: Type,
+        pub ty: Type<'a>,
     }
 
     /// A field with a bare type.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct ParamTypeOnly {
-        pub ty: Type,
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct ParamTypeOnly<'a> {
+        pub ty: Type<'a>,
+    }
+
+    /// A builtin type declaration item: `int ? = Int;`, `string#b5286e24 ? = String;`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct ItemBuiltin<'a> {
+        pub name: Path<'a>,
+        pub combinator_id: Option<CombinatorId>,
+        pub question_token: TLToken![?],
+        pub equals_token: TLToken![=],
+        pub result_type: Path<'a>,
+        pub semicolon_token: TLToken![;],
+    }
+
+    /// A final type declaration item: `New Foo;`, `Final Bar;`, `Empty Baz;`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct ItemTypeDeclaration<'a> {
+        pub kind: TypeDeclarationKind,
+        pub name: Path<'a>,
+        pub semicolon_token: TLToken![;],
+    }
+
+    /// The keyword that determines the meaning of a final type declaration.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub enum TypeDeclarationKind {
+        /// A `New` declaration: the type is declared, but has no constructors yet.
+        New(TLToken![New]),
+        /// A `Final` declaration: the type must not get any more constructors.
+        Final(TLToken![Final]),
+        /// An `Empty` declaration: the type has no constructors at all.
+        Empty(TLToken![Empty]),
     }
 
     /// A delimiter item.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
     pub struct ItemDelimiter {
         pub delimiter: Delimiter,
     }
 
     /// Divides sections of declarations of TL combinators.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
     pub enum Delimiter {
         Types(DelimiterTypes),
         Functions(DelimiterFunctions),
     }
 
     /// A `---types---` delimiter.
-    #[cfg_derive!(Clone, Debug)]
+    #[cfg_derive!(Clone, Debug, Deserialize, Serialize)]
     pub struct DelimiterTypes {
         pub span: Span,
     }
 
     /// A `---functions---` delimiter.
-    #[cfg_derive!(Clone, Debug)]
+    #[cfg_derive!(Clone, Debug, Deserialize, Serialize)]
     pub struct DelimiterFunctions {
         pub span: Span,
     }
 
-    /// A layer item: `// LAYER 78`.
-    #[cfg_derive!(Clone, Debug)]
+    /// A layer item: `// LAYER 78`, `/* Layer 100 */`, `===78===`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Serialize)]
     pub struct ItemLayer {
-        pub slash_slash_token: SlashSlash,
-        pub layer_token: TLToken![LAYER],
+        pub marker: LayerMarker,
         pub layer_span: Span,
         pub layer: u32,
     }
 
+    /// The syntax used to mark the beginning of a layer.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub enum LayerMarker {
+        SingleLine(LayerMarkerSingleLine),
+        MultiLine(LayerMarkerMultiLine),
+        Equals(LayerMarkerEquals),
+    }
+
+    /// A `// LAYER 78` layer marker.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct LayerMarkerSingleLine {
+        pub slash_slash_token: SlashSlash,
+        pub layer_keyword: LayerKeyword,
+    }
+
+    /// A `/* Layer 100 */` layer marker.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct LayerMarkerMultiLine {
+        pub slash_asterisk_token: SlashAsterisk,
+        pub layer_keyword: LayerKeyword,
+    }
+
+    /// A `===78===` layer marker.
+    #[cfg_derive!(Clone, Debug, Deserialize, Serialize)]
+    pub struct LayerMarkerEquals {
+        pub left_span: Span,
+        pub right_span: Span,
+    }
+
+    /// The word that starts a layer marker in a comment.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub enum LayerKeyword {
+        /// `LAYER`
+        Upper(TLToken![LAYER]),
+        /// `Layer`
+        TitleCase(TLToken![Layer]),
+    }
+
     /// A comment item.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct ItemComment {
-        pub comment: Comment,
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct ItemComment<'a> {
+        pub comment: Comment<'a>,
+    }
+
+    /// Source text skipped by the recovering parser because it couldn't be
+    /// parsed as any other item.
+    #[cfg_derive!(Clone, Debug, Deserialize, Serialize)]
+    pub struct ItemError<'a> {
+        pub span: Span,
+        pub text: Cow<'a, str>,
+    }
+}
+
+
+impl<'a> OptParam<'a> {
+    /// Determine what the variables of this optional field declaration stand
+    /// for from its type.
+    pub fn kind(&self) -> OptParamKind {
+        OptParamKind::from_type(&self.ty)
+    }
+}
+
+impl OptParamKind {
+    /// Determine the kind of optional field declaration with the type `ty`.
+    pub fn from_type(ty: &Type) -> OptParamKind {
+        match *ty {
+            Type::Int(_) => OptParamKind::Nat,
+            Type::ParameterizedPath(ref ty) => {
+                let parameterized_path = &ty.parameterized_path;
+                let segments = &parameterized_path.path.segments;
+
+                if parameterized_path.args.is_none() && segments.len() == 1 && segments[0].as_str() == "Type" {
+                    OptParamKind::Type
+                } else {
+                    OptParamKind::Other
+                }
+            },
+            Type::Paren(ref paren) => OptParamKind::from_type(&paren.inner),
+            Type::TypeParameter(_) |
+            Type::Bare(_) => OptParamKind::Other,
+        }
+    }
+}
+
+impl<'a> ItemCombinator<'a> {
+    /// Return the TDLib-style documentation comment made of the leading
+    /// comments of this combinator, if there is one.
+    pub fn doc_comment(&self) -> Option<&DocComment<'a>> {
+        self.doc_comment.as_ref()
+    }
+
+    /// Start building a combinator named `name` with zeroed spans.
+    ///
+    /// ```
+    /// # use tl_lang_syn::{ItemCombinator, ParameterizedPath, Path, Type};
+    /// let path = |s| Path::new(s).unwrap();
+    ///
+    /// // messages.sendMessage#fa88427a flags:# silent:flags.5?true peer:InputPeer
+    /// //     entities:flags.3?Vector<MessageEntity> = Updates;
+    /// let send_message = ItemCombinator::builder("messages.sendMessage")
+    ///     .id(0xfa88427a)
+    ///     .param("flags", Type::int())
+    ///     .conditional_param("silent", "flags", 5, path("true"))
+    ///     .param("peer", path("InputPeer"))
+    ///     .conditional_param("entities", "flags", 3,
+    ///         ParameterizedPath::with_args(path("Vector"), vec![Type::from(path("MessageEntity"))]))
+    ///     .result_type(path("Updates"))
+    ///     .build();
+    ///
+    /// assert_eq!(send_message.params.len(), 4);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is not a dot-separated list of valid TL language
+    /// identifiers.
+    pub fn builder<S: Into<Cow<'a, str>>>(name: S) -> ItemCombinatorBuilder<'a> {
+        ItemCombinatorBuilder {
+            name: builder_path(name),
+            combinator_id: None,
+            opt_params: Vec::new(),
+            params: Vec::new(),
+            result_type: None,
+        }
     }
 }
 
+/// A builder of `ItemCombinator`s, created by `ItemCombinator::builder`.
+///
+/// Every node created by the builder has zeroed spans, which is how spans of
+/// nodes that don't come from source text are represented. Methods taking
+/// names panic if a name is not a valid TL language identifier.
+pub struct ItemCombinatorBuilder<'a> {
+    name: Path<'a>,
+    combinator_id: Option<CombinatorId>,
+    opt_params: Vec<OptParam<'a>>,
+    params: Vec<Param<'a>>,
+    result_type: Option<ParameterizedPath<'a>>,
+}
+
+impl<'a> ItemCombinatorBuilder<'a> {
+    /// Set the combinator id: `#fa88427a`.
+    pub fn id(mut self, id: u32) -> ItemCombinatorBuilder<'a> {
+        self.combinator_id = Some(CombinatorId {
+            hash_token: Default::default(),
+            id: Id { span: Span::zeroed(), id, format: IdFormat::default() },
+        });
+        self
+    }
+
+    /// Add an optional field declaration: `{X:Type}`.
+    pub fn opt_param<S, T>(mut self, name: S, ty: T) -> ItemCombinatorBuilder<'a>
+    where
+        S: Into<Cow<'a, str>>,
+        T: Into<Type<'a>>,
+    {
+        self.opt_params.push(OptParam {
+            brace_token: Brace(Span::zeroed()),
+            var_idents: vec![builder_ident(name)],
+            colon_token: Default::default(),
+            ty: ty.into(),
+        });
+        self
+    }
+
+    /// Add a field: `peer:InputPeer`.
+    pub fn param<S, T>(mut self, name: S, ty: T) -> ItemCombinatorBuilder<'a>
+    where
+        S: Into<Cow<'a, str>>,
+        T: Into<Type<'a>>,
+    {
+        self.params.push(Param::Conditional(ParamConditional {
+            var_ident: builder_ident(name),
+            colon_token: Default::default(),
+            conditional_param_def: None,
+            ty: ty.into(),
+        }));
+        self
+    }
+
+    /// Add a field which is present if the bit `bit_index` of the `#` field
+    /// `flags` is set: `silent:flags.5?true`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit_index` is not less than 32.
+    pub fn conditional_param<S, F, T>(mut self, name: S, flags: F, bit_index: u8, ty: T) -> ItemCombinatorBuilder<'a>
+    where
+        S: Into<Cow<'a, str>>,
+        F: Into<Cow<'a, str>>,
+        T: Into<Type<'a>>,
+    {
+        let bit_index = BitIndex::new(Span::zeroed(), bit_index)
+            .unwrap_or_else(|| panic!("bit index out of range: {}", bit_index));
+
+        self.params.push(Param::Conditional(ParamConditional {
+            var_ident: builder_ident(name),
+            colon_token: Default::default(),
+            conditional_param_def: Some(ConditionalParamDef {
+                var_ident: builder_ident(flags),
+                bit_selector: Some(BitSelector {
+                    dot_token: Default::default(),
+                    bit_index,
+                }),
+                question_token: Default::default(),
+            }),
+            ty: ty.into(),
+        }));
+        self
+    }
+
+    /// Set the result type: `Updates`.
+    pub fn result_type<T: Into<ParameterizedPath<'a>>>(mut self, result_type: T) -> ItemCombinatorBuilder<'a> {
+        self.result_type = Some(result_type.into());
+        self
+    }
+
+    /// Build the combinator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result type wasn't set.
+    pub fn build(self) -> ItemCombinator<'a> {
+        let result_type = self.result_type.expect("the result type of a combinator must be set");
+
+        ItemCombinator {
+            leading_comments: Vec::new(),
+            doc_comment: None,
+            name: self.name,
+            combinator_id: self.combinator_id,
+            opt_params: self.opt_params,
+            opt_param_comments: Vec::new(),
+            params: self.params,
+            param_comments: Vec::new(),
+            equals_token: Default::default(),
+            result_comments: Vec::new(),
+            result_type,
+            semicolon_token: Default::default(),
+            trailing_comment: None,
+        }
+    }
+}
+
+fn builder_path<'a, S: Into<Cow<'a, str>>>(name: S) -> Path<'a> {
+    let name = name.into();
+    let panic_message = format!("invalid TL language path: {:?}", name);
+
+    Path::new(name).expect(&panic_message)
+}
+
+fn builder_ident<'a, S: Into<Cow<'a, str>>>(name: S) -> Ident<'a> {
+    let name = name.into();
+    let panic_message = format!("invalid TL language identifier: {:?}", name);
+
+    Ident::new(Span::zeroed(), name).expect(&panic_message)
+}
+
 
 #[cfg(feature = "eq-impls")]
 mod eq_impls {
@@ -157,6 +477,8 @@ mod eq_impls {
     impl Eq for DelimiterTypes {}
     impl Eq for DelimiterFunctions {}
     impl Eq for ItemLayer {}
+    impl Eq for LayerMarkerEquals {}
+    impl<'a> Eq for ItemError<'a> {}
 
     impl PartialEq for DelimiterTypes {
         fn eq(&self, _other: &DelimiterTypes) -> bool {
@@ -175,6 +497,18 @@ mod eq_impls {
             self.layer == other.layer
         }
     }
+
+    impl PartialEq for LayerMarkerEquals {
+        fn eq(&self, _other: &LayerMarkerEquals) -> bool {
+            true
+        }
+    }
+
+    impl<'a> PartialEq for ItemError<'a> {
+        fn eq(&self, other: &ItemError<'a>) -> bool {
+            self.text == other.text
+        }
+    }
 }
 
 
@@ -201,6 +535,18 @@ mod hash_impls {
             self.layer.hash(state);
         }
     }
+
+    impl Hash for LayerMarkerEquals {
+        fn hash<H: Hasher>(&self, _state: &mut H) {
+            // No state to hash -- do nothing
+        }
+    }
+
+    impl<'a> Hash for ItemError<'a> {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.text.hash(state);
+        }
+    }
 }
 
 
@@ -210,46 +556,70 @@ mod spanned {
     use spanned::Spanned;
     use spanned::private::Sealed;
 
-    impl Sealed for  Item {}
-    impl Sealed for  ItemCombinator {}
+    impl<'a> Sealed for  Item<'a> {}
+    impl<'a> Sealed for  ItemCombinator<'a> {}
+    impl<'a> Sealed for  ParamComment<'a> {}
     impl Sealed for  CombinatorId {}
-    impl Sealed for  OptParam {}
-    impl Sealed for  Param {}
-    impl Sealed for  ParamConditional {}
-    impl Sealed for  ConditionalParamDef {}
+    impl<'a> Sealed for  OptParam<'a> {}
+    impl<'a> Sealed for  Param<'a> {}
+    impl<'a> Sealed for  ParamConditional<'a> {}
+    impl<'a> Sealed for  ConditionalParamDef<'a> {}
     impl Sealed for  BitSelector {}
-    impl Sealed for  ParamRepeated {}
-    impl Sealed for  ParamRepeatedIdent {}
-    impl Sealed for  Multiplicity {}
-    impl Sealed for  ParamWithParen {}
-    impl Sealed for  ParamTypeOnly {}
+    impl<'a> Sealed for  ParamRepeated<'a> {}
+    impl<'a> Sealed for  ParamRepeatedIdent<'a> {}
+    impl<'a> Sealed for  Multiplicity<'a> {}
+    impl<'a> Sealed for  ParamWithParen<'a> {}
+    impl<'a> Sealed for  ParamTypeOnly<'a> {}
+    impl<'a> Sealed for  ItemBuiltin<'a> {}
+    impl<'a> Sealed for  ItemTypeDeclaration<'a> {}
+    impl Sealed for  TypeDeclarationKind {}
     impl Sealed for  ItemDelimiter {}
     impl Sealed for  Delimiter {}
     impl Sealed for  DelimiterTypes {}
     impl Sealed for  DelimiterFunctions {}
     impl Sealed for  ItemLayer {}
-    impl Sealed for  ItemComment {}
-
-    impl Spanned for Item {
+    impl Sealed for  LayerMarker {}
+    impl Sealed for  LayerMarkerSingleLine {}
+    impl Sealed for  LayerMarkerMultiLine {}
+    impl Sealed for  LayerMarkerEquals {}
+    impl Sealed for  LayerKeyword {}
+    impl<'a> Sealed for  ItemComment<'a> {}
+    impl<'a> Sealed for  ItemError<'a> {}
+
+    impl<'a> Spanned for Item<'a> {
         fn span(&self) -> Span {
             match *self {
                 Item::Combinator(ref t) => t.span(),
+                Item::Builtin(ref t) => t.span(),
+                Item::TypeDeclaration(ref t) => t.span(),
                 Item::Delimiter(ref t) => t.span(),
                 Item::Layer(ref t) => t.span(),
                 Item::Comment(ref t) => t.span(),
+                Item::Error(ref t) => t.span(),
             }
         }
     }
 
-    impl Spanned for ItemCombinator {
+    impl<'a> Spanned for ItemCombinator<'a> {
         fn span(&self) -> Span {
-            self.name.span()
+            self.leading_comments.span()
+                .to(self.name.span())
                 .to(self.combinator_id.span())
                 .to(self.opt_params.span())
+                .to(self.opt_param_comments.span())
                 .to(self.params.span())
+                .to(self.param_comments.span())
                 .to(self.equals_token.span())
+                .to(self.result_comments.span())
                 .to(self.result_type.span())
                 .to(self.semicolon_token.span())
+                .to(self.trailing_comment.span())
+        }
+    }
+
+    impl<'a> Spanned for ParamComment<'a> {
+        fn span(&self) -> Span {
+            self.comment.span()
         }
     }
 
@@ -260,7 +630,7 @@ mod spanned {
         }
     }
 
-    impl Spanned for OptParam {
+    impl<'a> Spanned for OptParam<'a> {
         fn span(&self) -> Span {
             self.brace_token.span()
                 .to(self.var_idents.span())
@@ -269,7 +639,7 @@ mod spanned {
         }
     }
 
-    impl Spanned for Param {
+    impl<'a> Spanned for Param<'a> {
         fn span(&self) -> Span {
             match *self {
                 Param::Conditional(ref t) => t.span(),
@@ -280,7 +650,7 @@ mod spanned {
         }
     }
 
-    impl Spanned for ParamConditional {
+    impl<'a> Spanned for ParamConditional<'a> {
         fn span(&self) -> Span {
             self.var_ident.span()
                 .to(self.colon_token.span())
@@ -289,7 +659,7 @@ mod spanned {
         }
     }
 
-    impl Spanned for ConditionalParamDef {
+    impl<'a> Spanned for ConditionalParamDef<'a> {
         fn span(&self) -> Span {
             self.var_ident.span()
                 .to(self.bit_selector.span())
@@ -304,7 +674,7 @@ mod spanned {
         }
     }
 
-    impl Spanned for ParamRepeated {
+    impl<'a> Spanned for ParamRepeated<'a> {
         fn span(&self) -> Span {
             self.param_repeated_ident.span()
                 .to(self.multiplicity.span())
@@ -313,21 +683,21 @@ mod spanned {
         }
     }
 
-    impl Spanned for ParamRepeatedIdent {
+    impl<'a> Spanned for ParamRepeatedIdent<'a> {
         fn span(&self) -> Span {
             self.var_ident.span()
                 .to(self.colon_token.span())
         }
     }
 
-    impl Spanned for Multiplicity {
+    impl<'a> Spanned for Multiplicity<'a> {
         fn span(&self) -> Span {
             self.term.span()
                 .to(self.asterisk_token.span())
         }
     }
 
-    impl Spanned for ParamWithParen {
+    impl<'a> Spanned for ParamWithParen<'a> {
         fn span(&self) -> Span {
             self.paren_token.span()
                 .to(self.var_idents.span())
@@ -336,12 +706,41 @@ mod spanned {
         }
     }
 
-    impl Spanned for ParamTypeOnly {
+    impl<'a> Spanned for ParamTypeOnly<'a> {
         fn span(&self) -> Span {
             self.ty.span()
         }
     }
 
+    impl<'a> Spanned for ItemBuiltin<'a> {
+        fn span(&self) -> Span {
+            self.name.span()
+                .to(self.combinator_id.span())
+                .to(self.question_token.span())
+                .to(self.equals_token.span())
+                .to(self.result_type.span())
+                .to(self.semicolon_token.span())
+        }
+    }
+
+    impl<'a> Spanned for ItemTypeDeclaration<'a> {
+        fn span(&self) -> Span {
+            self.kind.span()
+                .to(self.name.span())
+                .to(self.semicolon_token.span())
+        }
+    }
+
+    impl Spanned for TypeDeclarationKind {
+        fn span(&self) -> Span {
+            match *self {
+                TypeDeclarationKind::New(ref t) => t.span(),
+                TypeDeclarationKind::Final(ref t) => t.span(),
+                TypeDeclarationKind::Empty(ref t) => t.span(),
+            }
+        }
+    }
+
     impl Spanned for ItemDelimiter {
         fn span(&self) -> Span {
             self.delimiter.span()
@@ -371,17 +770,298 @@ mod spanned {
 
     impl Spanned for ItemLayer {
         fn span(&self) -> Span {
-            self.slash_slash_token.span()
-                .to(self.layer_token.span())
+            self.marker.span()
                 .to(self.layer_span)
         }
     }
 
-    impl Spanned for ItemComment {
+    impl Spanned for LayerMarker {
+        fn span(&self) -> Span {
+            match *self {
+                LayerMarker::SingleLine(ref t) => t.span(),
+                LayerMarker::MultiLine(ref t) => t.span(),
+                LayerMarker::Equals(ref t) => t.span(),
+            }
+        }
+    }
+
+    impl Spanned for LayerMarkerSingleLine {
+        fn span(&self) -> Span {
+            self.slash_slash_token.span()
+                .to(self.layer_keyword.span())
+        }
+    }
+
+    impl Spanned for LayerMarkerMultiLine {
+        fn span(&self) -> Span {
+            self.slash_asterisk_token.span()
+                .to(self.layer_keyword.span())
+        }
+    }
+
+    impl Spanned for LayerMarkerEquals {
+        fn span(&self) -> Span {
+            self.left_span
+                .to(self.right_span)
+        }
+    }
+
+    impl Spanned for LayerKeyword {
+        fn span(&self) -> Span {
+            match *self {
+                LayerKeyword::Upper(ref t) => t.span(),
+                LayerKeyword::TitleCase(ref t) => t.span(),
+            }
+        }
+    }
+
+    impl<'a> Spanned for ItemComment<'a> {
         fn span(&self) -> Span {
             self.comment.span()
         }
     }
+
+    impl<'a> Spanned for ItemError<'a> {
+        fn span(&self) -> Span {
+            self.span
+        }
+    }
+}
+
+
+mod owned {
+    use super::*;
+    use owned::IntoOwned;
+    use owned::private::Sealed;
+
+    impl_to_owned!(
+        Item, ItemCombinator, ParamComment, OptParam, Param, ParamConditional, ConditionalParamDef,
+        ParamRepeated, ParamRepeatedIdent, Multiplicity, ParamWithParen, ParamTypeOnly, ItemBuiltin,
+        ItemTypeDeclaration, ItemComment, ItemError,
+    );
+
+    impl<'a> Sealed for  Item<'a> {}
+    impl<'a> Sealed for  ItemCombinator<'a> {}
+    impl<'a> Sealed for  ParamComment<'a> {}
+    impl<'a> Sealed for  OptParam<'a> {}
+    impl<'a> Sealed for  Param<'a> {}
+    impl<'a> Sealed for  ParamConditional<'a> {}
+    impl<'a> Sealed for  ConditionalParamDef<'a> {}
+    impl<'a> Sealed for  ParamRepeated<'a> {}
+    impl<'a> Sealed for  ParamRepeatedIdent<'a> {}
+    impl<'a> Sealed for  Multiplicity<'a> {}
+    impl<'a> Sealed for  ParamWithParen<'a> {}
+    impl<'a> Sealed for  ParamTypeOnly<'a> {}
+    impl<'a> Sealed for  ItemBuiltin<'a> {}
+    impl<'a> Sealed for  ItemTypeDeclaration<'a> {}
+    impl<'a> Sealed for  ItemComment<'a> {}
+    impl<'a> Sealed for  ItemError<'a> {}
+
+    impl<'a> IntoOwned for Item<'a> {
+        type Owned = Item<'static>;
+
+        fn into_owned(self) -> Item<'static> {
+            match self {
+                Item::Combinator(t) => Item::Combinator(t.into_owned()),
+                Item::Builtin(t) => Item::Builtin(t.into_owned()),
+                Item::TypeDeclaration(t) => Item::TypeDeclaration(t.into_owned()),
+                Item::Delimiter(t) => Item::Delimiter(t),
+                Item::Layer(t) => Item::Layer(t),
+                Item::Comment(t) => Item::Comment(t.into_owned()),
+                Item::Error(t) => Item::Error(t.into_owned()),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for ItemCombinator<'a> {
+        type Owned = ItemCombinator<'static>;
+
+        fn into_owned(self) -> ItemCombinator<'static> {
+            ItemCombinator {
+                leading_comments: self.leading_comments.into_owned(),
+                doc_comment: self.doc_comment.into_owned(),
+                name: self.name.into_owned(),
+                combinator_id: self.combinator_id,
+                opt_params: self.opt_params.into_owned(),
+                opt_param_comments: self.opt_param_comments.into_owned(),
+                params: self.params.into_owned(),
+                param_comments: self.param_comments.into_owned(),
+                equals_token: self.equals_token,
+                result_comments: self.result_comments.into_owned(),
+                result_type: self.result_type.into_owned(),
+                semicolon_token: self.semicolon_token,
+                trailing_comment: self.trailing_comment.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for ParamComment<'a> {
+        type Owned = ParamComment<'static>;
+
+        fn into_owned(self) -> ParamComment<'static> {
+            ParamComment {
+                index: self.index,
+                comment: self.comment.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for OptParam<'a> {
+        type Owned = OptParam<'static>;
+
+        fn into_owned(self) -> OptParam<'static> {
+            OptParam {
+                brace_token: self.brace_token,
+                var_idents: self.var_idents.into_owned(),
+                colon_token: self.colon_token,
+                ty: self.ty.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for Param<'a> {
+        type Owned = Param<'static>;
+
+        fn into_owned(self) -> Param<'static> {
+            match self {
+                Param::Conditional(t) => Param::Conditional(t.into_owned()),
+                Param::Repeated(t) => Param::Repeated(t.into_owned()),
+                Param::WithParen(t) => Param::WithParen(t.into_owned()),
+                Param::TypeOnly(t) => Param::TypeOnly(t.into_owned()),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for ParamConditional<'a> {
+        type Owned = ParamConditional<'static>;
+
+        fn into_owned(self) -> ParamConditional<'static> {
+            ParamConditional {
+                var_ident: self.var_ident.into_owned(),
+                colon_token: self.colon_token,
+                conditional_param_def: self.conditional_param_def.into_owned(),
+                ty: self.ty.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for ConditionalParamDef<'a> {
+        type Owned = ConditionalParamDef<'static>;
+
+        fn into_owned(self) -> ConditionalParamDef<'static> {
+            ConditionalParamDef {
+                var_ident: self.var_ident.into_owned(),
+                bit_selector: self.bit_selector,
+                question_token: self.question_token,
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for ParamRepeated<'a> {
+        type Owned = ParamRepeated<'static>;
+
+        fn into_owned(self) -> ParamRepeated<'static> {
+            ParamRepeated {
+                param_repeated_ident: self.param_repeated_ident.into_owned(),
+                multiplicity: self.multiplicity.into_owned(),
+                bracket_token: self.bracket_token,
+                params: self.params.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for ParamRepeatedIdent<'a> {
+        type Owned = ParamRepeatedIdent<'static>;
+
+        fn into_owned(self) -> ParamRepeatedIdent<'static> {
+            ParamRepeatedIdent {
+                var_ident: self.var_ident.into_owned(),
+                colon_token: self.colon_token,
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for Multiplicity<'a> {
+        type Owned = Multiplicity<'static>;
+
+        fn into_owned(self) -> Multiplicity<'static> {
+            Multiplicity {
+                term: self.term.into_owned(),
+                asterisk_token: self.asterisk_token,
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for ParamWithParen<'a> {
+        type Owned = ParamWithParen<'static>;
+
+        fn into_owned(self) -> ParamWithParen<'static> {
+            ParamWithParen {
+                paren_token: self.paren_token,
+                var_idents: self.var_idents.into_owned(),
+                colon_token: self.colon_token,
+                ty: self.ty.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for ParamTypeOnly<'a> {
+        type Owned = ParamTypeOnly<'static>;
+
+        fn into_owned(self) -> ParamTypeOnly<'static> {
+            ParamTypeOnly {
+                ty: self.ty.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for ItemBuiltin<'a> {
+        type Owned = ItemBuiltin<'static>;
+
+        fn into_owned(self) -> ItemBuiltin<'static> {
+            ItemBuiltin {
+                name: self.name.into_owned(),
+                combinator_id: self.combinator_id,
+                question_token: self.question_token,
+                equals_token: self.equals_token,
+                result_type: self.result_type.into_owned(),
+                semicolon_token: self.semicolon_token,
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for ItemTypeDeclaration<'a> {
+        type Owned = ItemTypeDeclaration<'static>;
+
+        fn into_owned(self) -> ItemTypeDeclaration<'static> {
+            ItemTypeDeclaration {
+                kind: self.kind,
+                name: self.name.into_owned(),
+                semicolon_token: self.semicolon_token,
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for ItemComment<'a> {
+        type Owned = ItemComment<'static>;
+
+        fn into_owned(self) -> ItemComment<'static> {
+            ItemComment {
+                comment: self.comment.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for ItemError<'a> {
+        type Owned = ItemError<'static>;
+
+        fn into_owned(self) -> ItemError<'static> {
+            ItemError {
+                span: self.span,
+                text: Cow::Owned(self.text.into_owned()),
+            }
+        }
+    }
 }
 
 
@@ -391,83 +1071,187 @@ mod parsing {
 
     use super::*;
     use cursor::Cursor;
+    use error::{ErrorCode, error_code_at};
     use synom::Synom;
     use synom::private::Sealed;
     use utils::parsing::is_decimal_digit;
 
-    impl Sealed for  Item {}
-    impl Sealed for  ItemCombinator {}
+    impl<'a> Sealed for  Item<'a> {}
+    impl<'a> Sealed for  ItemCombinator<'a> {}
+    impl<'a> Sealed for  ParamComment<'a> {}
     impl Sealed for  CombinatorId {}
-    impl Sealed for  OptParam {}
-    impl Sealed for  Param {}
-    impl Sealed for  ParamConditional {}
-    impl Sealed for  ConditionalParamDef {}
+    impl<'a> Sealed for  OptParam<'a> {}
+    impl<'a> Sealed for  Param<'a> {}
+    impl<'a> Sealed for  ParamConditional<'a> {}
+    impl<'a> Sealed for  ConditionalParamDef<'a> {}
     impl Sealed for  BitSelector {}
-    impl Sealed for  ParamRepeated {}
-    impl Sealed for  ParamRepeatedIdent {}
-    impl Sealed for  Multiplicity {}
-    impl Sealed for  ParamWithParen {}
-    impl Sealed for  ParamTypeOnly {}
+    impl<'a> Sealed for  ParamRepeated<'a> {}
+    impl<'a> Sealed for  ParamRepeatedIdent<'a> {}
+    impl<'a> Sealed for  Multiplicity<'a> {}
+    impl<'a> Sealed for  ParamWithParen<'a> {}
+    impl<'a> Sealed for  ParamTypeOnly<'a> {}
+    impl<'a> Sealed for  ItemBuiltin<'a> {}
+    impl<'a> Sealed for  ItemTypeDeclaration<'a> {}
+    impl Sealed for  TypeDeclarationKind {}
     impl Sealed for  ItemDelimiter {}
     impl Sealed for  Delimiter {}
     impl Sealed for  DelimiterTypes {}
     impl Sealed for  DelimiterFunctions {}
     impl Sealed for  ItemLayer {}
-    impl Sealed for  ItemComment {}
+    impl Sealed for  LayerMarker {}
+    impl Sealed for  LayerMarkerSingleLine {}
+    impl Sealed for  LayerMarkerMultiLine {}
+    impl Sealed for  LayerMarkerEquals {}
+    impl Sealed for  LayerKeyword {}
+    impl<'a> Sealed for  ItemComment<'a> {}
+
+    impl<'a> Synom<'a> for Item<'a> {
+        // Unlike `alt_complete!`, which fails at the beginning of the item,
+        // this reports the error of the alternative which got the furthest.
+        fn parse_cursor(input: Cursor<'a>) -> nom::IResult<Cursor<'a>, Item<'a>> {
+            let alternatives: [fn(Cursor<'a>) -> nom::IResult<Cursor<'a>, Item<'a>>; 6] = [
+                |i| map!(i, tlsyn!(ItemCombinator), Item::Combinator),
+                |i| map!(i, tlsyn!(ItemBuiltin), Item::Builtin),
+                |i| map!(i, tlsyn!(ItemTypeDeclaration), Item::TypeDeclaration),
+                |i| map!(i, tlsyn!(ItemDelimiter), Item::Delimiter),
+                |i| map!(i, tlsyn!(ItemLayer), Item::Layer),
+                |i| map!(i, tlsyn!(ItemComment), Item::Comment),
+            ];
+            let mut furthest: Option<(Cursor<'a>, Option<ErrorCode>)> = None;
+
+            for alternative in &alternatives {
+                let (cursor, code) = match alternative(input) {
+                    Err(nom::Err::Error(context)) => error_code_at(&context),
+                    Err(nom::Err::Incomplete(_)) => (input, None),
+                    res => return res,
+                };
+
+                if furthest.map_or(true, |(furthest_cursor, _)| cursor.offset() > furthest_cursor.offset()) {
+                    furthest = Some((cursor, code));
+                }
+            }
 
-    impl Synom for Item {
-        named!(parse_cursor(Cursor) -> Item, alt_complete!(
-            tlsyn!(ItemCombinator) => { Item::Combinator }
-            |
-            tlsyn!(ItemDelimiter) => { Item::Delimiter }
-            |
-            tlsyn!(ItemLayer) => { Item::Layer }
-            |
-            tlsyn!(ItemComment) => { Item::Comment }
-        ));
+            let (cursor, code) = furthest.unwrap_or((input, None));
+            code.unwrap_or(ErrorCode::ExpectedItem).error(cursor)
+        }
     }
 
-    impl Synom for ItemCombinator {
-        named!(parse_cursor(Cursor) -> ItemCombinator, do_parse!(
+    // Whitespace between `name` and `;` may include newlines, so that a
+    // combinator can span several lines, but both the leading and the trailing
+    // comments must stay on their own lines.
+    impl<'a> Synom<'a> for ItemCombinator<'a> {
+        named!(parse_cursor(Cursor<'a>) -> ItemCombinator<'a>, do_parse!(
+            leading_comments: many0!(do_parse!(
+                // Layer markers look like comments, but aren't attached to
+                // combinators
+                not!(tlsyn!(ItemLayer)) >>
+                call!(nom::space0) >>
+                comment: tlsyn!(Comment) >>
+                call!(nom::space0) >>
+                call!(nom::line_ending) >>
+
+                (comment)
+            )) >>
             call!(nom::space0) >>
             name: tlsyn!(Path) >>
             combinator_id: opt!(tlsyn!(CombinatorId)) >>
+            call!(nom::multispace0) >>
+            opt_params_and_comments: many0!(alt_complete!(
+                with_afterspace!(tlsyn!(Comment)) => { ParamOrComment::Comment }
+                |
+                with_afterspace!(tlsyn!(OptParam)) => { ParamOrComment::Param }
+            )) >>
+            params_and_comments: many0!(alt_complete!(
+                with_afterspace!(tlsyn!(Comment)) => { ParamOrComment::Comment }
+                |
+                with_afterspace!(tlsyn!(Param)) => { ParamOrComment::Param }
+            )) >>
+            equals_token: expect_recoverable!(ErrorCode::ExpectedParamOrEquals, tlpunct!(=)) >>
+            call!(nom::multispace0) >>
+            result_comments: many0!(with_afterspace!(tlsyn!(Comment))) >>
+            // Nothing but a combinator may have come this far
+            result_type: expect!(ErrorCode::ExpectedResultType, tlsyn!(ParameterizedPath)) >>
+            call!(nom::multispace0) >>
+            more_result_comments: many0!(with_afterspace!(tlsyn!(Comment))) >>
+            semicolon_token: expect!(ErrorCode::ExpectedSemicolon, tlpunct!(;)) >>
             call!(nom::space0) >>
-            opt_params: many0!(with_afterspace!(tlsyn!(OptParam))) >>
-            call!(nom::space0) >>
-            params: many0!(with_afterspace!(tlsyn!(Param))) >>
-            call!(nom::space0) >>
-            equals_token: tlpunct!(=) >>
-            call!(nom::space0) >>
-            result_type: tlsyn!(ParameterizedPath) >>
-            call!(nom::space0) >>
-            semicolon_token: tlpunct!(;) >>
+            trailing_comment: opt!(tlsyn!(Comment)) >>
             call!(nom::space0) >>
 
-            (ItemCombinator {
-                name, combinator_id, opt_params, params, equals_token, result_type, semicolon_token,
+            ({
+                let (opt_params, mut opt_param_comments) = split_params_and_comments(opt_params_and_comments);
+                let (params, mut param_comments) = split_params_and_comments(params_and_comments);
+                // Comments after the last optional field precede the fields
+                let first_param_comments = opt_param_comments.iter()
+                    .position(|c| c.index == opt_params.len())
+                    .map(|start| opt_param_comments.split_off(start))
+                    .unwrap_or_default();
+                param_comments.splice(0..0, first_param_comments.into_iter().map(|c| ParamComment { index: 0, ..c }));
+                let mut result_comments = result_comments;
+                result_comments.extend(more_result_comments);
+
+                ItemCombinator {
+                    doc_comment: DocComment::from_comments(&leading_comments),
+                    leading_comments,
+                    name,
+                    combinator_id,
+                    opt_params,
+                    opt_param_comments,
+                    params,
+                    param_comments,
+                    equals_token,
+                    result_comments,
+                    result_type,
+                    semicolon_token,
+                    trailing_comment,
+                }
             })
         ));
     }
 
-    impl Synom for CombinatorId {
-        named!(parse_cursor(Cursor) -> CombinatorId, do_parse!(
+    // Only lives until params and comments are split
+    #[allow(clippy::large_enum_variant)]
+    enum ParamOrComment<'a, T> {
+        Param(T),
+        Comment(Comment<'a>),
+    }
+
+    fn split_params_and_comments<'a, T>(
+        params_and_comments: Vec<ParamOrComment<'a, T>>,
+    ) -> (Vec<T>, Vec<ParamComment<'a>>) {
+        let mut params = Vec::new();
+        let mut param_comments = Vec::new();
+
+        for param_or_comment in params_and_comments {
+            match param_or_comment {
+                ParamOrComment::Param(param) => params.push(param),
+                ParamOrComment::Comment(comment) => {
+                    param_comments.push(ParamComment { index: params.len(), comment });
+                },
+            }
+        }
+
+        (params, param_comments)
+    }
+
+    impl<'a> Synom<'a> for CombinatorId {
+        named!(parse_cursor(Cursor<'a>) -> CombinatorId, do_parse!(
             hash_token: tlpunct!(#) >>
-            id: tlsyn!(Id) >>
+            id: expect!(ErrorCode::InvalidId, tlsyn!(Id)) >>
             (CombinatorId { hash_token, id })
         ));
     }
 
-    impl Synom for OptParam {
-        named!(parse_cursor(Cursor) -> OptParam, do_parse!(
+    impl<'a> Synom<'a> for OptParam<'a> {
+        named!(parse_cursor(Cursor<'a>) -> OptParam<'a>, do_parse!(
             opt_param: braces!(do_parse!(
-                call!(nom::space0) >>
+                call!(nom::multispace0) >>
                 var_idents: many1!(with_afterspace!(tlsyn!(Ident))) >>
-                call!(nom::space0) >>
+                call!(nom::multispace0) >>
                 colon_token: tlpunct!(:) >>
-                call!(nom::space0) >>
-                ty: tlsyn!(Type) >>
-                call!(nom::space0) >>
+                call!(nom::multispace0) >>
+                ty: expect!(ErrorCode::ExpectedType, tlsyn!(Type)) >>
+                call!(nom::multispace0) >>
 
                 (var_idents, colon_token, ty)
             )) >>
@@ -481,31 +1265,35 @@ mod parsing {
         ));
     }
 
-    impl Synom for Param {
-        named!(parse_cursor(Cursor) -> Param, alt_complete!(
-            tlsyn!(ParamConditional) => { Param::Conditional }
-            |
+    impl<'a> Synom<'a> for Param<'a> {
+        // `ParamRepeated` goes first, otherwise `a:m*[ ... ]` would be parsed
+        // as `ParamConditional` with type `m`.
+        named!(parse_cursor(Cursor<'a>) -> Param<'a>, alt_complete!(
             tlsyn!(ParamRepeated) => { Param::Repeated }
             |
+            tlsyn!(ParamConditional) => { Param::Conditional }
+            |
             tlsyn!(ParamWithParen) => { Param::WithParen }
             |
             tlsyn!(ParamTypeOnly) => { Param::TypeOnly }
         ));
     }
 
-    impl Synom for ParamConditional {
-        named!(parse_cursor(Cursor) -> ParamConditional, do_parse!(
+    impl<'a> Synom<'a> for ParamConditional<'a> {
+        named!(parse_cursor(Cursor<'a>) -> ParamConditional<'a>, do_parse!(
             var_ident: tlsyn!(Ident) >>
             colon_token: tlpunct!(:) >>
             conditional_param_def: opt!(tlsyn!(ConditionalParamDef)) >>
-            ty: tlsyn!(Type) >>
+            // `ParamRepeated` has been tried already, so nothing but a type
+            // can follow
+            ty: expect!(ErrorCode::ExpectedType, call!(Type::parse_term)) >>
 
             (ParamConditional { var_ident, colon_token, conditional_param_def, ty })
         ));
     }
 
-    impl Synom for ConditionalParamDef {
-        named!(parse_cursor(Cursor) -> ConditionalParamDef, do_parse!(
+    impl<'a> Synom<'a> for ConditionalParamDef<'a> {
+        named!(parse_cursor(Cursor<'a>) -> ConditionalParamDef<'a>, do_parse!(
             var_ident: tlsyn!(Ident) >>
             bit_selector: opt!(tlsyn!(BitSelector)) >>
             question_token: tlpunct!(?) >>
@@ -514,8 +1302,8 @@ mod parsing {
         ));
     }
 
-    impl Synom for BitSelector {
-        named!(parse_cursor(Cursor) -> BitSelector, do_parse!(
+    impl<'a> Synom<'a> for BitSelector {
+        named!(parse_cursor(Cursor<'a>) -> BitSelector, do_parse!(
             dot_token: tlpunct!(.) >>
             bit_index: tlsyn!(BitIndex) >>
 
@@ -523,11 +1311,17 @@ mod parsing {
         ));
     }
 
-    impl Synom for ParamRepeated {
-        named!(parse_cursor(Cursor) -> ParamRepeated, do_parse!(
+    impl<'a> Synom<'a> for ParamRepeated<'a> {
+        named!(parse_cursor(Cursor<'a>) -> ParamRepeated<'a>, do_parse!(
             param_repeated_ident: opt!(tlsyn!(ParamRepeatedIdent)) >>
             multiplicity: opt!(tlsyn!(Multiplicity)) >>
-            params: brackets!(many0!(with_afterspace!(tlsyn!(Param)))) >>
+            params: brackets!(do_parse!(
+                call!(nom::multispace0) >>
+                params: many0!(with_afterspace!(tlsyn!(Param))) >>
+                expect!(ErrorCode::ExpectedParamOrRightBracket, peek!(tag!("]"))) >>
+
+                (params)
+            )) >>
 
             (ParamRepeated {
                 param_repeated_ident,
@@ -538,8 +1332,8 @@ mod parsing {
         ));
     }
 
-    impl Synom for ParamRepeatedIdent {
-        named!(parse_cursor(Cursor) -> ParamRepeatedIdent, do_parse!(
+    impl<'a> Synom<'a> for ParamRepeatedIdent<'a> {
+        named!(parse_cursor(Cursor<'a>) -> ParamRepeatedIdent<'a>, do_parse!(
             var_ident: tlsyn!(Ident) >>
             colon_token: tlpunct!(:) >>
 
@@ -547,25 +1341,25 @@ mod parsing {
         ));
     }
 
-    impl Synom for Multiplicity {
-        named!(parse_cursor(Cursor) -> Multiplicity, do_parse!(
-            term: tlsyn!(Ident) >>
+    impl<'a> Synom<'a> for Multiplicity<'a> {
+        named!(parse_cursor(Cursor<'a>) -> Multiplicity<'a>, do_parse!(
+            term: tlsyn!(NatExpr) >>
             asterisk_token: tlpunct!(*) >>
 
             (Multiplicity { term, asterisk_token })
         ));
     }
 
-    impl Synom for ParamWithParen {
-        named!(parse_cursor(Cursor) -> ParamWithParen, do_parse!(
+    impl<'a> Synom<'a> for ParamWithParen<'a> {
+        named!(parse_cursor(Cursor<'a>) -> ParamWithParen<'a>, do_parse!(
             param: parens!(do_parse!(
-                call!(nom::space0) >>
+                call!(nom::multispace0) >>
                 var_idents: many1!(with_afterspace!(tlsyn!(Ident))) >>
-                call!(nom::space0) >>
+                call!(nom::multispace0) >>
                 colon_token: tlpunct!(:) >>
-                call!(nom::space0) >>
-                ty: tlsyn!(Type) >>
-                call!(nom::space0) >>
+                call!(nom::multispace0) >>
+                ty: expect!(ErrorCode::ExpectedType, tlsyn!(Type)) >>
+                call!(nom::multispace0) >>
 
                 (var_idents, colon_token, ty)
             )) >>
@@ -579,31 +1373,80 @@ mod parsing {
         ));
     }
 
-    impl Synom for ParamTypeOnly {
-        named!(parse_cursor(Cursor) -> ParamTypeOnly, do_parse!(
-            ty: tlsyn!(Type) >>
+    impl<'a> Synom<'a> for ParamTypeOnly<'a> {
+        named!(parse_cursor(Cursor<'a>) -> ParamTypeOnly<'a>, do_parse!(
+            ty: call!(Type::parse_term) >>
 
             (ParamTypeOnly { ty })
         ));
     }
 
-    impl Synom for ItemDelimiter {
-        named!(parse_cursor(Cursor) -> ItemDelimiter, do_parse!(
-            delimiter: tlsyn!(Delimiter) >>
+    impl<'a> Synom<'a> for ItemBuiltin<'a> {
+        named!(parse_cursor(Cursor<'a>) -> ItemBuiltin<'a>, do_parse!(
+            call!(nom::space0) >>
+            name: tlsyn!(Path) >>
+            combinator_id: opt!(tlsyn!(CombinatorId)) >>
+            call!(nom::space0) >>
+            question_token: tlpunct!(?) >>
+            call!(nom::space0) >>
+            equals_token: expect!(ErrorCode::ExpectedEquals, tlpunct!(=)) >>
+            call!(nom::space0) >>
+            result_type: expect!(ErrorCode::ExpectedResultType, tlsyn!(Path)) >>
+            call!(nom::space0) >>
+            semicolon_token: expect!(ErrorCode::ExpectedSemicolon, tlpunct!(;)) >>
+            call!(nom::space0) >>
+
+            (ItemBuiltin {
+                name, combinator_id, question_token, equals_token, result_type, semicolon_token,
+            })
+        ));
+    }
+
+    impl<'a> Synom<'a> for ItemTypeDeclaration<'a> {
+        named!(parse_cursor(Cursor<'a>) -> ItemTypeDeclaration<'a>, do_parse!(
+            call!(nom::space0) >>
+            kind: tlsyn!(TypeDeclarationKind) >>
+            call!(nom::space1) >>
+            name: tlsyn!(Path) >>
+            call!(nom::space0) >>
+            semicolon_token: tlpunct!(;) >>
+            call!(nom::space0) >>
+
+            (ItemTypeDeclaration { kind, name, semicolon_token })
+        ));
+    }
+
+    impl<'a> Synom<'a> for TypeDeclarationKind {
+        named!(parse_cursor(Cursor<'a>) -> TypeDeclarationKind, alt_complete!(
+            tlkeyword!(new) => { TypeDeclarationKind::New }
+            |
+            tlkeyword!(final) => { TypeDeclarationKind::Final }
+            |
+            tlkeyword!(empty) => { TypeDeclarationKind::Empty }
+        ));
+    }
+
+    impl<'a> Synom<'a> for ItemDelimiter {
+        named!(parse_cursor(Cursor<'a>) -> ItemDelimiter, do_parse!(
+            delimiter: alt_complete!(
+                tlsyn!(Delimiter)
+                |
+                preceded!(tag!("---"), call!(|i| ErrorCode::ExpectedDelimiterKind.failure(i)))
+            ) >>
             (ItemDelimiter { delimiter })
         ));
     }
 
-    impl Synom for Delimiter {
-        named!(parse_cursor(Cursor) -> Delimiter, alt_complete!(
+    impl<'a> Synom<'a> for Delimiter {
+        named!(parse_cursor(Cursor<'a>) -> Delimiter, alt_complete!(
             tlsyn!(DelimiterTypes) => { Delimiter::Types }
             |
             tlsyn!(DelimiterFunctions) => { Delimiter::Functions }
         ));
     }
 
-    impl Synom for DelimiterTypes {
-        named!(parse_cursor(Cursor) -> DelimiterTypes, do_parse!(
+    impl<'a> Synom<'a> for DelimiterTypes {
+        named!(parse_cursor(Cursor<'a>) -> DelimiterTypes, do_parse!(
             types_cursor: tag!("---types---") >>
             span: value!(types_cursor.span()) >>
 
@@ -611,8 +1454,8 @@ mod parsing {
         ));
     }
 
-    impl Synom for DelimiterFunctions {
-        named!(parse_cursor(Cursor) -> DelimiterFunctions, do_parse!(
+    impl<'a> Synom<'a> for DelimiterFunctions {
+        named!(parse_cursor(Cursor<'a>) -> DelimiterFunctions, do_parse!(
             functions_cursor: tag!("---functions---") >>
             span: value!(functions_cursor.span()) >>
 
@@ -620,24 +1463,96 @@ mod parsing {
         ));
     }
 
-    impl Synom for ItemLayer {
-        named!(parse_cursor(Cursor) -> ItemLayer, do_parse!(
+    impl<'a> Synom<'a> for ItemLayer {
+        named!(parse_cursor(Cursor<'a>) -> ItemLayer, do_parse!(
             call!(nom::space0) >>
+            item_layer: alt_complete!(
+                call!(ItemLayer::parse_single_line)
+                |
+                call!(ItemLayer::parse_multi_line)
+                |
+                call!(ItemLayer::parse_equals)
+            ) >>
+            call!(nom::space0) >>
+
+            (item_layer)
+        ));
+    }
+
+    impl ItemLayer {
+        named!(parse_single_line(Cursor) -> ItemLayer, do_parse!(
             slash_slash_token: tlsyn!(SlashSlash) >>
             call!(nom::space0) >>
-            layer_token: tlkeyword!(LAYER) >>
+            layer_keyword: tlsyn!(LayerKeyword) >>
             call!(nom::space0) >>
-            layer_cursor: take_while!(is_decimal_digit) >>
-            layer_span: value!(layer_cursor.span()) >>
-            layer: map_res!(value!(layer_cursor.to_str()), str::parse) >>
+            layer: call!(parse_layer_number) >>
+
+            (ItemLayer {
+                marker: LayerMarker::SingleLine(LayerMarkerSingleLine {
+                    slash_slash_token,
+                    layer_keyword,
+                }),
+                layer_span: layer.0,
+                layer: layer.1,
+            })
+        ));
+
+        named!(parse_multi_line(Cursor) -> ItemLayer, do_parse!(
+            marker: call!(|i| SlashAsterisk::parse(i, |i| do_parse!(i,
+                call!(nom::multispace0) >>
+                layer_keyword: tlsyn!(LayerKeyword) >>
+                call!(nom::multispace0) >>
+                layer: call!(parse_layer_number) >>
+                call!(nom::multispace0) >>
+
+                ((layer_keyword, layer))
+            ))) >>
+
+            (ItemLayer {
+                marker: LayerMarker::MultiLine(LayerMarkerMultiLine {
+                    slash_asterisk_token: marker.0,
+                    layer_keyword: (marker.1).0,
+                }),
+                layer_span: ((marker.1).1).0,
+                layer: ((marker.1).1).1,
+            })
+        ));
+
+        named!(parse_equals(Cursor) -> ItemLayer, do_parse!(
+            left_cursor: tag!("===") >>
+            call!(nom::space0) >>
+            layer: call!(parse_layer_number) >>
             call!(nom::space0) >>
+            right_cursor: tag!("===") >>
+
+            (ItemLayer {
+                marker: LayerMarker::Equals(LayerMarkerEquals {
+                    left_span: left_cursor.span(),
+                    right_span: right_cursor.span(),
+                }),
+                layer_span: layer.0,
+                layer: layer.1,
+            })
+        ));
+    }
+
+    named!(parse_layer_number(Cursor) -> (Span, u32), do_parse!(
+        layer_cursor: take_while1!(is_decimal_digit) >>
+        layer: map_res!(value!(layer_cursor.to_str()), str::parse) >>
 
-            (ItemLayer { slash_slash_token, layer_token, layer_span, layer })
+        ((layer_cursor.span(), layer))
+    ));
+
+    impl<'a> Synom<'a> for LayerKeyword {
+        named!(parse_cursor(Cursor<'a>) -> LayerKeyword, alt_complete!(
+            tlkeyword!(LAYER) => { LayerKeyword::Upper }
+            |
+            tlkeyword!(Layer) => { LayerKeyword::TitleCase }
         ));
     }
 
-    impl Synom for ItemComment {
-        named!(parse_cursor(Cursor) -> ItemComment, do_parse!(
+    impl<'a> Synom<'a> for ItemComment<'a> {
+        named!(parse_cursor(Cursor<'a>) -> ItemComment<'a>, do_parse!(
             comment: tlsyn!(Comment) >>
             (ItemComment { comment })
         ));
@@ -653,57 +1568,107 @@ mod printing {
     use print::{Print, print_slice_with_separator};
     use print::private::Sealed;
 
-    impl Sealed for  Item {}
-    impl Sealed for  ItemCombinator {}
+    impl<'a> Sealed for  Item<'a> {}
+    impl<'a> Sealed for  ItemCombinator<'a> {}
+    impl<'a> Sealed for  ParamComment<'a> {}
     impl Sealed for  CombinatorId {}
-    impl Sealed for  OptParam {}
-    impl Sealed for  Param {}
-    impl Sealed for  ParamConditional {}
-    impl Sealed for  ConditionalParamDef {}
+    impl<'a> Sealed for  OptParam<'a> {}
+    impl<'a> Sealed for  Param<'a> {}
+    impl<'a> Sealed for  ParamConditional<'a> {}
+    impl<'a> Sealed for  ConditionalParamDef<'a> {}
     impl Sealed for  BitSelector {}
-    impl Sealed for  ParamRepeated {}
-    impl Sealed for  ParamRepeatedIdent {}
-    impl Sealed for  Multiplicity {}
-    impl Sealed for  ParamWithParen {}
-    impl Sealed for  ParamTypeOnly {}
+    impl<'a> Sealed for  ParamRepeated<'a> {}
+    impl<'a> Sealed for  ParamRepeatedIdent<'a> {}
+    impl<'a> Sealed for  Multiplicity<'a> {}
+    impl<'a> Sealed for  ParamWithParen<'a> {}
+    impl<'a> Sealed for  ParamTypeOnly<'a> {}
+    impl<'a> Sealed for  ItemBuiltin<'a> {}
+    impl<'a> Sealed for  ItemTypeDeclaration<'a> {}
+    impl Sealed for  TypeDeclarationKind {}
     impl Sealed for  ItemDelimiter {}
     impl Sealed for  Delimiter {}
     impl Sealed for  DelimiterTypes {}
     impl Sealed for  DelimiterFunctions {}
     impl Sealed for  ItemLayer {}
-    impl Sealed for  ItemComment {}
-
-    impl Print for Item {
+    impl Sealed for  LayerMarker {}
+    impl Sealed for  LayerMarkerSingleLine {}
+    impl Sealed for  LayerMarkerMultiLine {}
+    impl Sealed for  LayerMarkerEquals {}
+    impl Sealed for  LayerKeyword {}
+    impl<'a> Sealed for  ItemComment<'a> {}
+    impl<'a> Sealed for  ItemError<'a> {}
+
+    impl<'a> Print for Item<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             match *self {
                 Item::Combinator(ref t) => t.print(f),
+                Item::Builtin(ref t) => t.print(f),
+                Item::TypeDeclaration(ref t) => t.print(f),
                 Item::Layer(ref t) => t.print(f),
                 Item::Delimiter(ref t) => t.print(f),
                 Item::Comment(ref t) => t.print(f),
+                Item::Error(ref t) => t.print(f),
             }
         }
     }
 
-    impl Print for ItemCombinator {
+    impl<'a> Print for ItemCombinator<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            for comment in &self.leading_comments {
+                comment.print(f)?;
+                f.write_str("\n")?;
+            }
             self.name.print(f)?;
             self.combinator_id.print(f)?;
             f.write_str(" ")?;
-            if let Some(()) = print_slice_with_separator(&self.opt_params, " ", f)? {
+            for (index, opt_param) in self.opt_params.iter().enumerate() {
+                for opt_param_comment in self.opt_param_comments.iter().filter(|c| c.index == index) {
+                    opt_param_comment.print(f)?;
+                }
+                opt_param.print(f)?;
                 f.write_str(" ")?;
             }
-            if let Some(()) = print_slice_with_separator(&self.params, " ", f)? {
-                f.write_str(" ")?;
+            for index in 0..self.params.len() + 1 {
+                for param_comment in self.param_comments.iter().filter(|c| c.index == index) {
+                    param_comment.print(f)?;
+                }
+                if let Some(param) = self.params.get(index) {
+                    param.print(f)?;
+                    f.write_str(" ")?;
+                }
             }
             self.equals_token.print(f)?;
             f.write_str(" ")?;
+            for comment in &self.result_comments {
+                print_comment_before(comment, f)?;
+            }
             self.result_type.print(f)?;
             self.semicolon_token.print(f)?;
+            if let Some(ref comment) = self.trailing_comment {
+                f.write_str(" ")?;
+                comment.print(f)?;
+            }
 
             Ok(())
         }
     }
 
+    impl<'a> Print for ParamComment<'a> {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            print_comment_before(&self.comment, f)
+        }
+    }
+
+    fn print_comment_before(comment: &Comment, f: &mut fmt::Formatter) -> fmt::Result {
+        comment.print(f)?;
+
+        // A `//...` comment runs until the end of the line
+        match *comment {
+            Comment::SingleLine(_) => f.write_str("\n"),
+            Comment::MultiLine(_) => f.write_str(" "),
+        }
+    }
+
     impl Print for CombinatorId {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             self.hash_token.print(f)?;
@@ -713,7 +1678,7 @@ mod printing {
         }
     }
 
-    impl Print for OptParam {
+    impl<'a> Print for OptParam<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             Brace::print(f, |f| {
                 print_slice_with_separator(&self.var_idents, " ", f)?;
@@ -724,7 +1689,7 @@ mod printing {
         }
     }
 
-    impl Print for Param {
+    impl<'a> Print for Param<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             match *self {
                 Param::Conditional(ref t) => t.print(f),
@@ -735,7 +1700,7 @@ mod printing {
         }
     }
 
-    impl Print for ParamConditional {
+    impl<'a> Print for ParamConditional<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             self.var_ident.print(f)?;
             self.colon_token.print(f)?;
@@ -746,7 +1711,7 @@ mod printing {
         }
     }
 
-    impl Print for ConditionalParamDef {
+    impl<'a> Print for ConditionalParamDef<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             self.var_ident.print(f)?;
             self.bit_selector.print(f)?;
@@ -765,7 +1730,7 @@ mod printing {
         }
     }
 
-    impl Print for ParamRepeated {
+    impl<'a> Print for ParamRepeated<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             self.param_repeated_ident.print(f)?;
             self.multiplicity.print(f)?;
@@ -778,7 +1743,7 @@ mod printing {
         }
     }
 
-    impl Print for ParamRepeatedIdent {
+    impl<'a> Print for ParamRepeatedIdent<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             self.var_ident.print(f)?;
             self.colon_token.print(f)?;
@@ -787,7 +1752,7 @@ mod printing {
         }
     }
 
-    impl Print for Multiplicity {
+    impl<'a> Print for Multiplicity<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             self.term.print(f)?;
             self.asterisk_token.print(f)?;
@@ -796,7 +1761,7 @@ mod printing {
         }
     }
 
-    impl Print for ParamWithParen {
+    impl<'a> Print for ParamWithParen<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             Paren::print(f, |f| {
                 print_slice_with_separator(&self.var_idents, " ", f)?;
@@ -809,12 +1774,49 @@ mod printing {
         }
     }
 
-    impl Print for ParamTypeOnly {
+    impl<'a> Print for ParamTypeOnly<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             self.ty.print(f)
         }
     }
 
+    impl<'a> Print for ItemBuiltin<'a> {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.name.print(f)?;
+            self.combinator_id.print(f)?;
+            f.write_str(" ")?;
+            self.question_token.print(f)?;
+            f.write_str(" ")?;
+            self.equals_token.print(f)?;
+            f.write_str(" ")?;
+            self.result_type.print(f)?;
+            self.semicolon_token.print(f)?;
+
+            Ok(())
+        }
+    }
+
+    impl<'a> Print for ItemTypeDeclaration<'a> {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.kind.print(f)?;
+            f.write_str(" ")?;
+            self.name.print(f)?;
+            self.semicolon_token.print(f)?;
+
+            Ok(())
+        }
+    }
+
+    impl Print for TypeDeclarationKind {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match *self {
+                TypeDeclarationKind::New(ref t) => t.print(f),
+                TypeDeclarationKind::Final(ref t) => t.print(f),
+                TypeDeclarationKind::Empty(ref t) => t.print(f),
+            }
+        }
+    }
+
     impl Print for ItemDelimiter {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             self.delimiter.print(f)
@@ -844,21 +1846,55 @@ mod printing {
 
     impl Print for ItemLayer {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            self.slash_slash_token.print(f)?;
-            f.write_str(" ")?;
-            self.layer_token.print(f)?;
-            f.write_str(" ")?;
-            fmt::Display::fmt(&self.layer, f)?;
+            match self.marker {
+                LayerMarker::SingleLine(ref marker) => {
+                    marker.slash_slash_token.print(f)?;
+                    f.write_str(" ")?;
+                    marker.layer_keyword.print(f)?;
+                    f.write_str(" ")?;
+                    fmt::Display::fmt(&self.layer, f)?;
+                },
+                LayerMarker::MultiLine(ref marker) => {
+                    SlashAsterisk::print(f, |f| {
+                        f.write_str(" ")?;
+                        marker.layer_keyword.print(f)?;
+                        f.write_str(" ")?;
+                        fmt::Display::fmt(&self.layer, f)?;
+                        f.write_str(" ")?;
+                        Ok(())
+                    })?;
+                },
+                LayerMarker::Equals(_) => {
+                    f.write_str("===")?;
+                    fmt::Display::fmt(&self.layer, f)?;
+                    f.write_str("===")?;
+                },
+            }
 
             Ok(())
         }
     }
 
-    impl Print for ItemComment {
+    impl Print for LayerKeyword {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match *self {
+                LayerKeyword::Upper(ref t) => t.print(f),
+                LayerKeyword::TitleCase(ref t) => t.print(f),
+            }
+        }
+    }
+
+    impl<'a> Print for ItemComment<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             self.comment.print(f)
         }
     }
+
+    impl<'a> Print for ItemError<'a> {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(&self.text)
+        }
+    }
 }
 
 
@@ -925,8 +1961,10 @@ mod tests {
     {
         fn new_item_layer(span: Span, layer: u32) -> ItemLayer {
             ItemLayer {
-                slash_slash_token: SlashSlash(span),
-                layer_token: TLToken![LAYER](span),
+                marker: LayerMarker::SingleLine(LayerMarkerSingleLine {
+                    slash_slash_token: SlashSlash(span),
+                    layer_keyword: LayerKeyword::Upper(TLToken![LAYER](span)),
+                }),
                 layer_span: span,
                 layer,
             }
@@ -1020,4 +2058,130 @@ mod tests {
             );
         }
     }
+
+    #[cfg(all(feature = "eq-impls", feature = "parsing"))]
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn matches_parsed() {
+            let parsed = ::parse_str::<ItemCombinator>(
+                "vector#1cb5c415 {t:Type} flags:# big:flags.31?Vector<t> = VectorInfo<t>;",
+            ).unwrap();
+
+            let t = || Type::from(Path::new("t").unwrap());
+            let built = ItemCombinator::builder("vector")
+                .id(0x1cb5c415)
+                .opt_param("t", Path::new("Type").unwrap())
+                .param("flags", Type::int())
+                .conditional_param("big", "flags", 31, ParameterizedPath::with_args(Path::new("Vector").unwrap(), vec![t()]))
+                .result_type(ParameterizedPath::with_args(Path::new("VectorInfo").unwrap(), vec![t()]))
+                .build();
+
+            assert!(built.opt_params[0].kind() == OptParamKind::Type);
+            assert!(built == parsed);
+        }
+
+        #[test]
+        #[should_panic(expected = "invalid TL language path")]
+        fn invalid_name() {
+            ItemCombinator::builder("messages..sendMessage");
+        }
+    }
+
+    #[cfg(feature = "parsing")]
+    mod parsing {
+        use super::super::*;
+
+        #[test]
+        fn comment_attachment() {
+            let input = "\
+                // Not attached\n\
+                \n\
+                // Attached\n\
+                foo#0e16a8a5 a:int /* inline */ b:string = Foo; // trailing\n\
+            ";
+
+            let file = ::parse_file_str(input).unwrap();
+            assert_eq!(file.items.len(), 2);
+
+            match file.items[1] {
+                Item::Combinator(ref combinator) => {
+                    assert_eq!(combinator.leading_comments.len(), 1);
+                    assert_eq!(combinator.params.len(), 2);
+                    assert_eq!(combinator.param_comments.len(), 1);
+                    assert_eq!(combinator.param_comments[0].index, 1);
+                    assert!(combinator.trailing_comment.is_some());
+                },
+                _ => panic!("expected a combinator"),
+            }
+        }
+
+        #[test]
+        fn multiline_comment_attachment() {
+            let input = "\
+                foo a:int = Foo\n\
+                ;\n\
+                pair {X:Type} // the first one\n\
+                  {Y:Type} = /* always */ Pair\n\
+                  X Y // both\n\
+                ;\n\
+            ";
+
+            let file = ::parse_file_str(input).unwrap();
+            assert_eq!(file.items.len(), 2);
+
+            match file.items[1] {
+                Item::Combinator(ref combinator) => {
+                    assert_eq!(combinator.opt_params.len(), 2);
+                    assert_eq!(combinator.opt_param_comments.len(), 1);
+                    assert_eq!(combinator.opt_param_comments[0].index, 1);
+                    assert_eq!(combinator.result_comments.len(), 2);
+                    assert!(combinator.result_type.args.is_some());
+                },
+                _ => panic!("expected a combinator"),
+            }
+        }
+
+        #[test]
+        fn doc_comment_attachment() {
+            let input = "\
+                //@description Represents a file @id Unique file identifier\n\
+                //@size File size; 0 if unknown\n\
+                file id:int size:int = File;\n\
+            ";
+
+            let file = ::parse_file_str(input).unwrap();
+
+            match file.items[0] {
+                Item::Combinator(ref combinator) => {
+                    let doc_comment = combinator.doc_comment().unwrap();
+
+                    assert_eq!(doc_comment.description(), Some("Represents a file"));
+                    assert_eq!(doc_comment.get("id"), Some("Unique file identifier"));
+                    assert_eq!(doc_comment.get("size"), Some("File size; 0 if unknown"));
+
+                    let id_tag = &doc_comment.tags[1];
+                    assert_eq!(&input[id_tag.text_span.begin() - 1..id_tag.text_span.end() - 1], "Unique file identifier");
+                },
+                _ => panic!("expected a combinator"),
+            }
+        }
+
+        #[cfg(all(feature = "debug-impls", feature = "eq-impls"))]
+        #[test]
+        fn opt_param_kind() {
+            let input = "foo {X:Type} {Y:(Type)} {n:#} {m:Nat} {Z:Vector<Type>} = Foo X n;";
+            let combinator = ::parse_str::<ItemCombinator>(input).unwrap();
+            let kinds = combinator.opt_params.iter().map(OptParam::kind).collect::<Vec<_>>();
+
+            assert_eq!(kinds, [
+                OptParamKind::Type,
+                OptParamKind::Type,
+                OptParamKind::Nat,
+                OptParamKind::Other,
+                OptParamKind::Other,
+            ]);
+        }
+    }
 }
@@ -6,6 +6,11 @@ extern crate macro_attr;
 #[cfg(feature = "parsing")]
 #[macro_use]
 extern crate nom;
+#[cfg(feature = "serde-impls")]
+extern crate serde;
+
+#[cfg(all(test, feature = "serde-impls"))]
+extern crate serde_json;
 
 
 #[cfg(feature = "parsing")]
@@ -16,16 +21,30 @@ mod utils;
 
 #[cfg(feature = "parsing")]
 pub mod cursor;
+pub mod diagnostic;
 pub mod error;
+pub mod line_index;
+pub mod owned;
+#[cfg(feature = "printing")]
+pub mod pretty;
 #[cfg(feature = "printing")]
 pub mod print;
 pub mod punctuated;
+pub mod source_database;
 pub mod span;
 pub mod spanned;
+#[cfg(feature = "interning")]
+pub mod symbol;
 #[cfg(feature = "parsing")]
 pub mod synom;
 #[macro_use]
 pub mod token;
+#[cfg(feature = "visit")]
+pub mod visit;
+#[cfg(feature = "visit-mut")]
+pub mod visit_mut;
+#[cfg(feature = "fold")]
+pub mod fold;
 
 
 mod bit;
@@ -34,11 +53,16 @@ pub use bit::BitIndex;
 mod comment;
 pub use comment::{Comment, CommentMultiLine, CommentSingleLine};
 
+mod doc_comment;
+pub use doc_comment::{DocComment, DocCommentTag};
+
 mod file;
-pub use file::File;
+pub use file::{File, LayerMarkerPlacement};
 
 mod id;
-pub use id::Id;
+pub use id::{Id, IdFormat};
+#[cfg(feature = "parsing")]
+pub use id::IdValidation;
 
 mod ident;
 pub use ident::Ident;
@@ -46,58 +70,143 @@ pub use ident::Ident;
 mod item;
 pub use item::{
     BitSelector, CombinatorId, ConditionalParamDef, Delimiter, DelimiterTypes, DelimiterFunctions,
-    Item, ItemCombinator, ItemComment, ItemDelimiter, ItemLayer, Multiplicity, OptParam, Param,
-    ParamConditional, ParamRepeated, ParamRepeatedIdent, ParamTypeOnly, ParamWithParen,
+    Item, ItemBuiltin, ItemCombinator, ItemCombinatorBuilder, ItemComment, ItemDelimiter,
+    ItemError, ItemLayer, ItemTypeDeclaration, LayerKeyword, LayerMarker, LayerMarkerEquals,
+    LayerMarkerMultiLine, LayerMarkerSingleLine, Multiplicity, OptParam, OptParamKind, Param,
+    ParamComment, ParamConditional, ParamRepeated, ParamRepeatedIdent, ParamTypeOnly,
+    ParamWithParen, TypeDeclarationKind,
 };
 
+mod lossless;
+pub use lossless::{LosslessFile, LosslessItem};
+
+mod nat;
+pub use nat::{NatExpr, NatExprAdd, NatExprConst, NatExprIdent, NatExprParen};
+
 mod path;
 pub use path::{
-    AngleBracketedGenericArguments, GenericArguments, ParameterizedPath, Path,
-    SafeParameterizedPath, SafeParameterizedPathSpaceImmune, SafeParameterizedPathParenthesized,
+    AngleBracketedGenericArguments, GenericArgument, GenericArguments, ParameterizedPath, Path,
     SpaceSeparatedGenericArguments,
 };
 
+mod section;
+pub use section::{Section, SectionDiagnostic, SectionKind, Sections};
+
+#[cfg(feature = "parsing")]
+mod stream;
+#[cfg(feature = "parsing")]
+pub use stream::ItemReader;
+
 mod ty;
-pub use ty::{Type, TypeBare, TypeInt, TypeParameterizedPath, TypeTypeParameter};
+pub use ty::{Type, TypeBare, TypeInt, TypeParameterizedPath, TypeParen, TypeTypeParameter};
 
 
 #[cfg(feature = "parsing")]
 mod convenience_parsing_functions {
     use std::fs::File as FsFile;
-    use std::io::{self, Read};
+    use std::io::{self, BufRead, Read};
     use std::path::Path as FsPath;
 
     use super::*;
-    use error::ParseResult;
+    use cursor::Cursor;
+    use error::{ParseError, ParseResult};
+    use owned::IntoOwned;
     use synom::{Parser, Synom};
 
     /// Parse a string of TL language schema into the chosen syntax tree node.
-    pub fn parse_str<T: Synom>(s: &str) -> ParseResult<T> {
+    pub fn parse_str<'a, T: Synom<'a>>(s: &'a str) -> ParseResult<T> {
+        parse_str_with(s, IdValidation::Lenient)
+    }
+
+    /// Parse a string of TL language schema into the chosen syntax tree node,
+    /// validating combinator ids as specified by `id_validation`.
+    pub fn parse_str_with<'a, T: Synom<'a>>(s: &'a str, id_validation: IdValidation) -> ParseResult<T> {
         let parser = T::parse_cursor;
-        parser.parse_str(s)
+        parser.parse_str_with(s, id_validation)
     }
 
     /// Parse the content of a file of TL language schema.
-    pub fn parse_file_str(mut content: &str) -> ParseResult<File> {
-        // Strip the BOM if it is present
+    pub fn parse_file_str(content: &str) -> ParseResult<File<'_>> {
+        parse_file_str_with(content, IdValidation::Lenient)
+    }
+
+    /// Parse the content of a file of TL language schema, validating
+    /// combinator ids as specified by `id_validation`.
+    pub fn parse_file_str_with(content: &str, id_validation: IdValidation) -> ParseResult<File<'_>> {
+        parse_str_with(strip_bom(content), id_validation)
+    }
+
+    /// Parse the content of a file of TL language schema, skipping items which
+    /// fail to parse.
+    ///
+    /// Every skipped item is recorded in the returned file as `Item::Error`
+    /// and the error which caused it to be skipped is returned alongside.
+    pub fn parse_file_str_recovering(content: &str) -> (File<'_>, Vec<ParseError>) {
+        parse_file_str_recovering_with(content, IdValidation::Lenient)
+    }
+
+    /// Parse the content of a file of TL language schema, skipping items which
+    /// fail to parse and validating combinator ids as specified by
+    /// `id_validation`.
+    pub fn parse_file_str_recovering_with(content: &str, id_validation: IdValidation) -> (File<'_>, Vec<ParseError>) {
+        File::parse_cursor_recovering(Cursor::with_id_validation(strip_bom(content), id_validation))
+    }
+
+    /// Parse the content of a file of TL language schema, keeping the text
+    /// between items so that printing the result reproduces `content`.
+    pub fn parse_file_str_lossless(content: &str) -> ParseResult<LosslessFile<'_>> {
+        parse_file_str_lossless_with(content, IdValidation::Lenient)
+    }
+
+    /// Parse the content of a file of TL language schema, keeping the text
+    /// between items and validating combinator ids as specified by
+    /// `id_validation`.
+    pub fn parse_file_str_lossless_with(content: &str, id_validation: IdValidation) -> ParseResult<LosslessFile<'_>> {
+        parse_file_str_with(content, id_validation).map(|file| {
+            LosslessFile::new(file, content).expect("spans of a parsed file point into its source")
+        })
+    }
+
+    fn strip_bom(content: &str) -> &str {
         const BOM: &str = "\u{feff}";
-        if content.starts_with(BOM) {
-            content = &content[BOM.len()..];
-        }
 
-        parse_str(content)
+        content.strip_prefix(BOM).unwrap_or(content)
     }
 
-    // FIXME: load&parse in a streaming fashion?
     /// Load the content of the entire file into the memory and parse it.
-    pub fn parse_file<P: AsRef<FsPath>>(path: P) -> io::Result<File> {
+    ///
+    /// Unlike the other parsing functions, this one returns a syntax tree
+    /// which owns its data since the content of the file is dropped.
+    pub fn parse_file<P: AsRef<FsPath>>(path: P) -> io::Result<File<'static>> {
+        parse_file_with(path, IdValidation::Lenient)
+    }
+
+    /// Load the content of the entire file into the memory and parse it,
+    /// validating combinator ids as specified by `id_validation`.
+    ///
+    /// Use `parse_items` to parse large files without loading them entirely.
+    pub fn parse_file_with<P: AsRef<FsPath>>(path: P, id_validation: IdValidation) -> io::Result<File<'static>> {
         let mut file = FsFile::open(path)?;
 
         let initial_buffer_size = file.metadata().map(|m| m.len() as usize + 1).unwrap_or(0);
         let mut content = String::with_capacity(initial_buffer_size);
         file.read_to_string(&mut content)?;
 
-        parse_file_str(&content).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+        parse_file_str_with(&content, id_validation)
+            .map(IntoOwned::into_owned)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    /// Parse items of TL language schema one at a time as they are read from
+    /// `reader`.
+    pub fn parse_items<R: BufRead>(reader: R) -> ItemReader<R> {
+        parse_items_with(reader, IdValidation::Lenient)
+    }
+
+    /// Parse items of TL language schema one at a time as they are read from
+    /// `reader`, validating combinator ids as specified by `id_validation`.
+    pub fn parse_items_with<R: BufRead>(reader: R, id_validation: IdValidation) -> ItemReader<R> {
+        ItemReader::new(reader, id_validation)
     }
 }
 
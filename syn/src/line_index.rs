@@ -0,0 +1,188 @@
+use span::{Span, SpanState};
+
+
+/// A 1-based line and column in the source text. Columns count chars.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// An index of line beginnings of a source text.
+#[derive(Clone, Debug)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    /// Byte offsets of the first byte of each line, starting with `0`.
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Create a new `LineIndex` for the given `source` text.
+    pub fn new(source: &'a str) -> LineIndex<'a> {
+        let line_starts = Some(0).into_iter()
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        LineIndex { source, line_starts }
+    }
+
+    /// Get the source text this index was created for.
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// Return the number of lines in the source text.
+    ///
+    /// The source text always has at least one line, even if it's empty.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Return the line and the column of the given 1-based `offset`, as
+    /// stored in a `Span`.
+    ///
+    /// An offset inside of a multi-byte character resolves to the column of
+    /// that character, and an offset inside of a line terminator resolves to
+    /// the column right after the end of the line.
+    ///
+    /// Return `None` if `offset` is zero or lies past the end of the source
+    /// text.
+    pub fn line_column(&self, offset: usize) -> Option<LineColumn> {
+        if offset == 0 || offset > self.source.len() + 1 {
+            return None;
+        }
+
+        let byte_offset = offset - 1;
+        let line_index = match self.line_starts.binary_search(&byte_offset) {
+            Ok(i) => i,
+            Err(i) => i - 1,
+        };
+        let line_start = self.line_starts[line_index];
+        let line = self.line_at(line_index);
+        let line_len = byte_offset - line_start;
+
+        let column = line.char_indices()
+            .take_while(|&(i, c)| i + c.len_utf8() <= line_len)
+            .count() + 1;
+
+        Some(LineColumn { line: line_index + 1, column })
+    }
+
+    /// Return the line and the column of the beginning and the end of the
+    /// given `span`.
+    ///
+    /// The end is exclusive just like the end of the `span` is.
+    ///
+    /// Return `None` if `span` is zeroed or doesn't fit in the source text.
+    pub fn span_line_columns(&self, span: Span) -> Option<(LineColumn, LineColumn)> {
+        match span.state() {
+            SpanState::Zeroed => None,
+            SpanState::Normal => Some((self.line_column(span.begin())?, self.line_column(span.end())?)),
+        }
+    }
+
+    /// Return the text of the 1-based `line` without its line terminator.
+    ///
+    /// Return `None` if there is no such line.
+    pub fn line(&self, line: usize) -> Option<&'a str> {
+        if line == 0 || line > self.line_starts.len() {
+            return None;
+        }
+
+        Some(self.line_at(line - 1))
+    }
+
+    /// Return the span of the 1-based `line` without its line terminator.
+    ///
+    /// Return `None` if there is no such line.
+    pub fn line_span(&self, line: usize) -> Option<Span> {
+        let text = self.line(line)?;
+        let begin = self.line_starts[line - 1] + 1;
+
+        Some(Span::new(begin, begin + text.len()))
+    }
+
+    fn line_at(&self, line_index: usize) -> &'a str {
+        let start = self.line_starts[line_index];
+        let end = self.line_starts.get(line_index + 1).map_or(self.source.len(), |&next| next - 1);
+        let line = &self.source[start..end];
+
+        line.strip_suffix('\r').unwrap_or(line)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lc(line: usize, column: usize) -> Option<LineColumn> {
+        Some(LineColumn { line, column })
+    }
+
+    #[test]
+    fn line_column() {
+        let index = LineIndex::new("foo = Foo;\nbar = Bar;\n");
+
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.line_column(0), None);
+        assert_eq!(index.line_column(1), lc(1, 1));
+        assert_eq!(index.line_column(5), lc(1, 5));
+        assert_eq!(index.line_column(11), lc(1, 11));
+        assert_eq!(index.line_column(12), lc(2, 1));
+        assert_eq!(index.line_column(22), lc(2, 11));
+        assert_eq!(index.line_column(23), lc(3, 1));
+        assert_eq!(index.line_column(24), None);
+    }
+
+    #[test]
+    fn crlf() {
+        let index = LineIndex::new("foo = Foo;\r\nbar = Bar;");
+
+        assert_eq!(index.line_count(), 2);
+        assert_eq!(index.line(1), Some("foo = Foo;"));
+        assert_eq!(index.line(2), Some("bar = Bar;"));
+        assert_eq!(index.line(3), None);
+        assert_eq!(index.line_column(11), lc(1, 11));
+        // Inside of `\r\n`
+        assert_eq!(index.line_column(12), lc(1, 11));
+        assert_eq!(index.line_column(13), lc(2, 1));
+        assert_eq!(index.line_span(1), Some(Span::new(1, 11)));
+        assert_eq!(index.line_span(2), Some(Span::new(13, 23)));
+    }
+
+    #[test]
+    fn multi_byte_utf8() {
+        // `é` takes 2 bytes and `€` takes 3 bytes
+        let source = "// é€\nfoo = Foo;";
+        let index = LineIndex::new(source);
+
+        assert_eq!(index.line_column(4), lc(1, 4));
+        assert_eq!(index.line_column(6), lc(1, 5));
+        // Inside of `€`
+        assert_eq!(index.line_column(7), lc(1, 5));
+        assert_eq!(index.line_column(9), lc(1, 6));
+        assert_eq!(index.line_column(10), lc(2, 1));
+        assert_eq!(index.line(1), Some("// é€"));
+    }
+
+    #[test]
+    fn span_line_columns() {
+        let source = "foo = Foo;\nbar = Bar;";
+        let index = LineIndex::new(source);
+
+        assert_eq!(index.span_line_columns(Span::zeroed()), None);
+        assert_eq!(index.span_line_columns(Span::new(7, 10)), Some((lc(1, 7).unwrap(), lc(1, 10).unwrap())));
+        assert_eq!(index.span_line_columns(Span::new(7, 15)), Some((lc(1, 7).unwrap(), lc(2, 4).unwrap())));
+        assert_eq!(index.span_line_columns(Span::new(7, 100)), None);
+    }
+
+    #[test]
+    fn empty() {
+        let index = LineIndex::new("");
+
+        assert_eq!(index.line_count(), 1);
+        assert_eq!(index.line(1), Some(""));
+        assert_eq!(index.line_column(1), lc(1, 1));
+    }
+}
@@ -0,0 +1,486 @@
+use std::borrow::Cow;
+
+use super::{Comment, File, Item, ItemCombinator, OptParam, Param, ParameterizedPath};
+use span::Span;
+use spanned::Spanned;
+
+
+macro_attr_many! {
+    /// A complete file of TL language source text which also keeps the text
+    /// between its items.
+    ///
+    /// Printing a `LosslessFile` reproduces the source text it was created
+    /// from byte-for-byte. Items modified through `LosslessItem::item_mut` are
+    /// printed from their syntax tree nodes, so that an automated edit only
+    /// changes the text of the items it touches.
+    ///
+    /// A modified combinator keeps the source text of its unchanged comments,
+    /// params and other parts together with the whitespace between them, so
+    /// that e.g. removing a param doesn't reformat the remaining ones.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct LosslessFile<'a> {
+        pub items: Vec<LosslessItem<'a>>,
+        /// Whitespace after the last item.
+        pub trailing_trivia: Cow<'a, str>,
+    }
+
+    /// An item of a `LosslessFile` together with the whitespace before it and
+    /// its original source text.
+    #[cfg_derive!(Clone, Debug, Deserialize, Serialize)]
+    pub struct LosslessItem<'a> {
+        /// Whitespace before the item.
+        pub leading_trivia: Cow<'a, str>,
+        item: Item<'a>,
+        source_text: Option<Cow<'a, str>>,
+        /// Original spans of the parts of a combinator in printing order.
+        part_spans: Vec<Span>,
+        modified: bool,
+    }
+}
+
+impl<'a> LosslessFile<'a> {
+    /// Create a new `LosslessFile` from `file` parsed from `source`.
+    ///
+    /// Spans of items are expected to point into `source` as if its BOM, if
+    /// any, was stripped, which is how `parse_file_str` treats its input.
+    /// Items with zeroed spans are kept without source text.
+    ///
+    /// Return `None` if spans of items are out of order or don't fit into
+    /// `source`, e.g. when `file` was parsed from a different text.
+    pub fn new(file: File<'a>, source: &'a str) -> Option<LosslessFile<'a>> {
+        let bom_len = if source.starts_with('\u{feff}') { '\u{feff}'.len_utf8() } else { 0 };
+        let mut position = 0;
+        let mut items = Vec::with_capacity(file.items.len());
+
+        for item in file.items {
+            let span = item.span();
+            if span.begin() == 0 {
+                items.push(LosslessItem::new("", item));
+                continue;
+            }
+
+            let (begin, end) = (bom_len + span.begin() - 1, bom_len + span.end() - 1);
+            let leading_trivia = Cow::Borrowed(source.get(position..begin)?);
+            let source_text = Cow::Borrowed(source.get(begin..end)?);
+            position = end;
+
+            let part_spans = match item {
+                Item::Combinator(ref combinator) => combinator_part_spans(combinator),
+                _ => Vec::new(),
+            };
+
+            items.push(LosslessItem {
+                leading_trivia,
+                item,
+                source_text: Some(source_text),
+                part_spans,
+                modified: false,
+            });
+        }
+
+        Some(LosslessFile {
+            items,
+            trailing_trivia: Cow::Borrowed(&source[position..]),
+        })
+    }
+
+    /// Convert this file into a usual `File`, dropping the text between items.
+    pub fn into_file(self) -> File<'a> {
+        File {
+            items: self.items.into_iter().map(LosslessItem::into_item).collect(),
+        }
+    }
+}
+
+impl<'a> LosslessItem<'a> {
+    /// Create a new `LosslessItem` which is printed from its syntax tree node.
+    pub fn new<S: Into<Cow<'a, str>>>(leading_trivia: S, item: Item<'a>) -> LosslessItem<'a> {
+        LosslessItem {
+            leading_trivia: leading_trivia.into(),
+            item,
+            source_text: None,
+            part_spans: Vec::new(),
+            modified: false,
+        }
+    }
+
+    /// Get the syntax tree node of this item.
+    pub fn item(&self) -> &Item<'a> {
+        &self.item
+    }
+
+    /// Get the syntax tree node of this item for modification.
+    ///
+    /// The item is printed from its node from now on. Parts of a combinator
+    /// that keep their spans and print the same are still taken from the
+    /// original source text.
+    pub fn item_mut(&mut self) -> &mut Item<'a> {
+        self.modified = true;
+        &mut self.item
+    }
+
+    /// Get the original source text of this item, or `None` if the item was
+    /// modified or created without one.
+    pub fn source_text(&self) -> Option<&str> {
+        if self.modified {
+            None
+        } else {
+            self.source_text.as_deref()
+        }
+    }
+
+    /// Convert this item into its syntax tree node.
+    pub fn into_item(self) -> Item<'a> {
+        self.item
+    }
+}
+
+/// A part of a combinator which keeps its own source text and the whitespace
+/// around it when the combinator is modified.
+enum CombinatorPart<'b, 'a: 'b> {
+    LeadingComment(&'b Comment<'a>),
+    Head(&'b ItemCombinator<'a>),
+    Comment(&'b Comment<'a>),
+    OptParam(&'b OptParam<'a>),
+    Param(&'b Param<'a>),
+    EqualsToken(&'b TLToken![=]),
+    ResultType(&'b ParameterizedPath<'a>),
+    SemicolonToken(&'b TLToken![;]),
+}
+
+impl<'b, 'a> CombinatorPart<'b, 'a> {
+    /// Split `combinator` into parts in the order they are printed.
+    fn split(combinator: &'b ItemCombinator<'a>) -> Vec<CombinatorPart<'b, 'a>> {
+        let mut parts = Vec::new();
+
+        parts.extend(combinator.leading_comments.iter().map(CombinatorPart::LeadingComment));
+        parts.push(CombinatorPart::Head(combinator));
+        for (index, opt_param) in combinator.opt_params.iter().enumerate() {
+            for opt_param_comment in combinator.opt_param_comments.iter().filter(|c| c.index == index) {
+                parts.push(CombinatorPart::Comment(&opt_param_comment.comment));
+            }
+            parts.push(CombinatorPart::OptParam(opt_param));
+        }
+        for index in 0..combinator.params.len() + 1 {
+            for param_comment in combinator.param_comments.iter().filter(|c| c.index == index) {
+                parts.push(CombinatorPart::Comment(&param_comment.comment));
+            }
+            if let Some(param) = combinator.params.get(index) {
+                parts.push(CombinatorPart::Param(param));
+            }
+        }
+        parts.push(CombinatorPart::EqualsToken(&combinator.equals_token));
+        parts.extend(combinator.result_comments.iter().map(CombinatorPart::Comment));
+        parts.push(CombinatorPart::ResultType(&combinator.result_type));
+        parts.push(CombinatorPart::SemicolonToken(&combinator.semicolon_token));
+        parts.extend(combinator.trailing_comment.iter().map(CombinatorPart::Comment));
+
+        parts
+    }
+
+    fn span(&self) -> Span {
+        match *self {
+            CombinatorPart::LeadingComment(comment) |
+            CombinatorPart::Comment(comment) => comment.span(),
+            CombinatorPart::Head(combinator) => combinator.name.span().to(combinator.combinator_id.span()),
+            CombinatorPart::OptParam(opt_param) => opt_param.span(),
+            CombinatorPart::Param(param) => param.span(),
+            CombinatorPart::EqualsToken(token) => token.span(),
+            CombinatorPart::ResultType(result_type) => result_type.span(),
+            CombinatorPart::SemicolonToken(token) => token.span(),
+        }
+    }
+}
+
+/// Return the spans of the parts of `combinator` in printing order, or an
+/// empty `Vec` if they don't follow each other in the source text.
+fn combinator_part_spans(combinator: &ItemCombinator) -> Vec<Span> {
+    let part_spans = CombinatorPart::split(combinator).iter().map(CombinatorPart::span).collect::<Vec<_>>();
+    let is_ordered = part_spans.iter().all(|span| span.begin() != 0) &&
+        part_spans.windows(2).all(|pair| pair[0].end() <= pair[1].begin()) &&
+        Span::union(part_spans.iter().cloned()) == combinator.span();
+
+    if is_ordered { part_spans } else { Vec::new() }
+}
+
+
+#[cfg(feature = "eq-impls")]
+mod eq_impls {
+    use super::*;
+
+    impl<'a> Eq for LosslessItem<'a> {}
+
+    impl<'a> PartialEq for LosslessItem<'a> {
+        fn eq(&self, other: &LosslessItem<'a>) -> bool {
+            self.leading_trivia == other.leading_trivia &&
+                self.item == other.item &&
+                self.source_text() == other.source_text()
+        }
+    }
+}
+
+
+#[cfg(feature = "hash-impls")]
+mod hash_impls {
+    use std::hash::{Hash, Hasher};
+
+    use super::*;
+
+    impl<'a> Hash for LosslessItem<'a> {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.leading_trivia.hash(state);
+            self.item.hash(state);
+            self.source_text().hash(state);
+        }
+    }
+}
+
+
+mod spanned {
+    use super::*;
+    use spanned::private::Sealed;
+
+    impl<'a> Sealed for LosslessFile<'a> {}
+    impl<'a> Sealed for LosslessItem<'a> {}
+
+    impl<'a> Spanned for LosslessFile<'a> {
+        fn span(&self) -> Span {
+            self.items.span()
+        }
+    }
+
+    impl<'a> Spanned for LosslessItem<'a> {
+        fn span(&self) -> Span {
+            self.item.span()
+        }
+    }
+}
+
+mod owned {
+    use super::*;
+    use owned::IntoOwned;
+    use owned::private::Sealed;
+
+    impl_to_owned!(LosslessFile, LosslessItem);
+
+    impl<'a> Sealed for LosslessFile<'a> {}
+    impl<'a> Sealed for LosslessItem<'a> {}
+
+    impl<'a> IntoOwned for LosslessFile<'a> {
+        type Owned = LosslessFile<'static>;
+
+        fn into_owned(self) -> LosslessFile<'static> {
+            LosslessFile {
+                items: self.items.into_owned(),
+                trailing_trivia: Cow::Owned(self.trailing_trivia.into_owned()),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for LosslessItem<'a> {
+        type Owned = LosslessItem<'static>;
+
+        fn into_owned(self) -> LosslessItem<'static> {
+            LosslessItem {
+                leading_trivia: Cow::Owned(self.leading_trivia.into_owned()),
+                item: self.item.into_owned(),
+                source_text: self.source_text.map(|text| Cow::Owned(text.into_owned())),
+                part_spans: self.part_spans,
+                modified: self.modified,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "printing")]
+mod printing {
+    use std::fmt;
+
+    use super::*;
+    use print::Print;
+    use print::private::Sealed;
+
+    impl<'a> Sealed for LosslessFile<'a> {}
+    impl<'a> Sealed for LosslessItem<'a> {}
+
+    impl<'a> Print for LosslessFile<'a> {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            for item in &self.items {
+                item.print(f)?;
+            }
+
+            f.write_str(&self.trailing_trivia)
+        }
+    }
+
+    impl<'a> Print for LosslessItem<'a> {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(&self.leading_trivia)?;
+
+            match self.source_text {
+                Some(ref text) if !self.modified => f.write_str(text),
+                Some(ref text) => match self.item {
+                    Item::Combinator(ref combinator) if !self.part_spans.is_empty() => {
+                        print_combinator_with_source(combinator, text, &self.part_spans, f)
+                    },
+                    _ => self.item.print(f),
+                },
+                None => self.item.print(f),
+            }
+        }
+    }
+
+    impl<'b, 'a> Sealed for CombinatorPart<'b, 'a> {}
+
+    impl<'b, 'a> Print for CombinatorPart<'b, 'a> {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match *self {
+                CombinatorPart::LeadingComment(comment) |
+                CombinatorPart::Comment(comment) => comment.print(f),
+                CombinatorPart::Head(combinator) => {
+                    combinator.name.print(f)?;
+                    combinator.combinator_id.print(f)
+                },
+                CombinatorPart::OptParam(opt_param) => opt_param.print(f),
+                CombinatorPart::Param(param) => param.print(f),
+                CombinatorPart::EqualsToken(token) => token.print(f),
+                CombinatorPart::ResultType(result_type) => result_type.print(f),
+                CombinatorPart::SemicolonToken(token) => token.print(f),
+            }
+        }
+    }
+
+    impl<'b, 'a> CombinatorPart<'b, 'a> {
+        /// The whitespace `ItemCombinator::print` puts after this part.
+        fn separator_after(&self) -> &'static str {
+            match *self {
+                CombinatorPart::LeadingComment(_) |
+                CombinatorPart::Comment(&Comment::SingleLine(_)) => "\n",
+                CombinatorPart::ResultType(_) => "",
+                _ => " ",
+            }
+        }
+    }
+
+    /// Print a modified `combinator`, taking the text of parts which still
+    /// have their original spans and the whitespace between them from
+    /// `source_text`.
+    fn print_combinator_with_source(
+        combinator: &ItemCombinator,
+        source_text: &str,
+        part_spans: &[Span],
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        // `source_text` starts where the first part of the combinator does
+        let offset = part_spans[0].begin();
+        let source = |begin: usize, end: usize| &source_text[begin - offset..end - offset];
+        let mut previous: Option<(Option<usize>, &str)> = None;
+
+        for part in CombinatorPart::split(combinator) {
+            let span = part.span();
+            let original = part_spans.iter().position(|&part_span| part_span == span);
+
+            if let Some((previous_original, separator)) = previous {
+                let original_trivia = original
+                    .and_then(|index| index.checked_sub(1))
+                    .map(|index| (index, source(part_spans[index].end(), span.begin())));
+
+                // Keep the original whitespace between parts which still follow
+                // each other, and before other original parts if it separates
+                // them at least as well as `separator` does
+                match original_trivia {
+                    Some((index, trivia)) if previous_original == Some(index) => f.write_str(trivia)?,
+                    Some((_, trivia)) if fits_separator(trivia, separator) => f.write_str(trivia)?,
+                    _ => f.write_str(separator)?,
+                }
+            }
+
+            let text = part.display_wrapper().to_string();
+            match original.map(|_| source(span.begin(), span.end())) {
+                Some(original_text) if original_text.split_whitespace().eq(text.split_whitespace()) => {
+                    f.write_str(original_text)?;
+                },
+                _ => f.write_str(&text)?,
+            }
+
+            previous = Some((original, part.separator_after()));
+        }
+
+        Ok(())
+    }
+
+    fn fits_separator(trivia: &str, separator: &str) -> bool {
+        match separator {
+            "\n" => trivia.contains('\n'),
+            " " => !trivia.is_empty(),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "parsing", feature = "printing"))]
+mod tests {
+    use super::*;
+    use print::Print;
+
+    #[test]
+    fn reproduces_source() {
+        let input = "\u{feff}// Booleans\n\n  boolFalse#bc799737   = Bool ;\r\nboolTrue#997275b5 =\n  Bool;\n\n---functions---\n";
+        let file = ::parse_file_str_lossless(input).unwrap();
+
+        assert_eq!(file.items.len(), 4);
+        assert_eq!(file.display_wrapper().to_string(), input);
+    }
+
+    #[test]
+    fn modified_item() {
+        let input = "boolFalse#bc799737   = Bool ;\nfoo   a:int = Foo;\n\nbar = Bar;\n";
+        let mut file = ::parse_file_str_lossless(input).unwrap();
+
+        if let Item::Combinator(ref mut combinator) = *file.items[1].item_mut() {
+            combinator.params.clear();
+        }
+
+        assert_eq!(file.items[0].source_text(), Some("boolFalse#bc799737   = Bool ;"));
+        assert_eq!(file.items[1].source_text(), None);
+        assert_eq!(file.display_wrapper().to_string(), "boolFalse#bc799737   = Bool ;\nfoo = Foo;\n\nbar = Bar;\n");
+    }
+
+    #[test]
+    fn modified_combinator_keeps_formatting() {
+        let input = "foo\n    a:int // first\n    b:string\n    c:long\n  = Foo;\n";
+        let mut file = ::parse_file_str_lossless(input).unwrap();
+
+        if let Item::Combinator(ref mut combinator) = *file.items[0].item_mut() {
+            combinator.params.remove(1);
+        }
+
+        assert_eq!(file.display_wrapper().to_string(), "foo\n    a:int // first\n    c:long\n  = Foo;\n");
+
+        if let Item::Combinator(ref mut combinator) = *file.items[0].item_mut() {
+            combinator.params.swap(0, 1);
+        }
+
+        assert_eq!(file.display_wrapper().to_string(), "foo\n    c:long // first\n    a:int\n  = Foo;\n");
+    }
+
+    #[test]
+    fn recovered_errors() {
+        let input = "foo#zz  = Foo;\n bar = Bar;";
+        let (file, errors) = ::parse_file_str_recovering(input);
+        let file = LosslessFile::new(file, input).unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(file.display_wrapper().to_string(), input);
+    }
+
+    #[test]
+    fn mismatched_source() {
+        let input = "boolFalse#bc799737 = Bool;\nboolTrue#997275b5 = Bool;\n";
+        let file = ::parse_file_str(input).unwrap();
+        assert!(LosslessFile::new(file, &input[..30]).is_none());
+
+        let file = ::parse_file_str(input).unwrap();
+        assert!(LosslessFile::new(file, input).is_some());
+    }
+}
@@ -0,0 +1,396 @@
+use super::Ident;
+use span::Span;
+use token::Paren;
+
+
+macro_attr_many! {
+    /// A natural number expression: `2`, `n`, `n+1`, `(n+2)`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub enum NatExpr<'a> {
+        Const(NatExprConst),
+        Ident(NatExprIdent<'a>),
+        Add(NatExprAdd<'a>),
+        Paren(NatExprParen<'a>),
+    }
+
+    /// A natural number literal: `0`, `2`, `100`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Serialize)]
+    pub struct NatExprConst {
+        pub span: Span,
+        pub value: u32,
+    }
+
+    /// A natural number variable: `n`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct NatExprIdent<'a> {
+        pub ident: Ident<'a>,
+    }
+
+    /// A sum of two natural number expressions: `n+1`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct NatExprAdd<'a> {
+        pub left: Box<NatExpr<'a>>,
+        pub plus_token: TLToken![+],
+        pub right: Box<NatExpr<'a>>,
+    }
+
+    /// A natural number expression enclosed in parentheses: `(n+1)`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct NatExprParen<'a> {
+        pub paren_token: Paren,
+        pub expr: Box<NatExpr<'a>>,
+    }
+}
+
+
+impl<'a> NatExpr<'a> {
+    /// Return true if this expression is a single identifier, possibly enclosed
+    /// in parentheses, and false otherwise.
+    pub fn is_ident(&self) -> bool {
+        match *self {
+            NatExpr::Ident(_) => true,
+            NatExpr::Paren(ref paren) => paren.expr.is_ident(),
+            NatExpr::Const(_) | NatExpr::Add(_) => false,
+        }
+    }
+}
+
+
+#[cfg(feature = "eq-impls")]
+mod eq_impls {
+    use super::*;
+
+    impl Eq for NatExprConst {}
+
+    impl PartialEq for NatExprConst {
+        fn eq(&self, other: &NatExprConst) -> bool {
+            self.value == other.value
+        }
+    }
+}
+
+
+#[cfg(feature = "hash-impls")]
+mod hash_impls {
+    use std::hash::{Hash, Hasher};
+
+    use super::*;
+
+    impl Hash for NatExprConst {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.value.hash(state);
+        }
+    }
+}
+
+
+mod spanned {
+    use super::*;
+    use span::Span;
+    use spanned::Spanned;
+    use spanned::private::Sealed;
+
+    impl<'a> Sealed for NatExpr<'a> {}
+    impl Sealed for NatExprConst {}
+    impl<'a> Sealed for NatExprIdent<'a> {}
+    impl<'a> Sealed for NatExprAdd<'a> {}
+    impl<'a> Sealed for NatExprParen<'a> {}
+
+    impl<'a> Spanned for NatExpr<'a> {
+        fn span(&self) -> Span {
+            match *self {
+                NatExpr::Const(ref t) => t.span(),
+                NatExpr::Ident(ref t) => t.span(),
+                NatExpr::Add(ref t) => t.span(),
+                NatExpr::Paren(ref t) => t.span(),
+            }
+        }
+    }
+
+    impl Spanned for NatExprConst {
+        fn span(&self) -> Span {
+            self.span
+        }
+    }
+
+    impl<'a> Spanned for NatExprIdent<'a> {
+        fn span(&self) -> Span {
+            self.ident.span()
+        }
+    }
+
+    impl<'a> Spanned for NatExprAdd<'a> {
+        fn span(&self) -> Span {
+            self.left.span()
+                .to(self.plus_token.span())
+                .to(self.right.span())
+        }
+    }
+
+    impl<'a> Spanned for NatExprParen<'a> {
+        fn span(&self) -> Span {
+            self.paren_token.span()
+                .to(self.expr.span())
+        }
+    }
+}
+
+
+mod owned {
+    use super::*;
+    use owned::IntoOwned;
+    use owned::private::Sealed;
+
+    impl_to_owned!(NatExpr, NatExprIdent, NatExprAdd, NatExprParen);
+
+    impl<'a> Sealed for NatExpr<'a> {}
+    impl<'a> Sealed for NatExprIdent<'a> {}
+    impl<'a> Sealed for NatExprAdd<'a> {}
+    impl<'a> Sealed for NatExprParen<'a> {}
+
+    impl<'a> IntoOwned for NatExpr<'a> {
+        type Owned = NatExpr<'static>;
+
+        fn into_owned(self) -> NatExpr<'static> {
+            match self {
+                NatExpr::Const(t) => NatExpr::Const(t),
+                NatExpr::Ident(t) => NatExpr::Ident(t.into_owned()),
+                NatExpr::Add(t) => NatExpr::Add(t.into_owned()),
+                NatExpr::Paren(t) => NatExpr::Paren(t.into_owned()),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for NatExprIdent<'a> {
+        type Owned = NatExprIdent<'static>;
+
+        fn into_owned(self) -> NatExprIdent<'static> {
+            NatExprIdent {
+                ident: self.ident.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for NatExprAdd<'a> {
+        type Owned = NatExprAdd<'static>;
+
+        fn into_owned(self) -> NatExprAdd<'static> {
+            NatExprAdd {
+                left: self.left.into_owned(),
+                plus_token: self.plus_token,
+                right: self.right.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for NatExprParen<'a> {
+        type Owned = NatExprParen<'static>;
+
+        fn into_owned(self) -> NatExprParen<'static> {
+            NatExprParen {
+                paren_token: self.paren_token,
+                expr: self.expr.into_owned(),
+            }
+        }
+    }
+}
+
+
+#[cfg(feature = "parsing")]
+mod parsing {
+    use nom;
+
+    use super::*;
+    use cursor::Cursor;
+    use synom::Synom;
+    use synom::private::Sealed;
+    use utils::parsing::is_decimal_digit;
+
+    impl<'a> Sealed for NatExpr<'a> {}
+    impl Sealed for NatExprConst {}
+    impl<'a> Sealed for NatExprIdent<'a> {}
+    impl<'a> Sealed for NatExprAdd<'a> {}
+    impl<'a> Sealed for NatExprParen<'a> {}
+
+    impl<'a> Synom<'a> for NatExpr<'a> {
+        named!(parse_cursor(Cursor<'a>) -> NatExpr<'a>, do_parse!(
+            first: call!(NatExpr::parse_term) >>
+            rest: many0!(complete!(do_parse!(
+                call!(nom::multispace0) >>
+                plus_token: tlpunct!(+) >>
+                call!(nom::multispace0) >>
+                right: call!(NatExpr::parse_term) >>
+
+                (plus_token, right)
+            ))) >>
+
+            // `+` is left-associative: `a+b+c` is `(a+b)+c`
+            (rest.into_iter().fold(first, |left, (plus_token, right)| {
+                NatExpr::Add(NatExprAdd {
+                    left: Box::new(left),
+                    plus_token,
+                    right: Box::new(right),
+                })
+            }))
+        ));
+    }
+
+    impl<'a> NatExpr<'a> {
+        named!(pub(crate) parse_term(Cursor<'a>) -> NatExpr<'a>, alt_complete!(
+            tlsyn!(NatExprConst) => { NatExpr::Const }
+            |
+            tlsyn!(NatExprIdent) => { NatExpr::Ident }
+            |
+            tlsyn!(NatExprParen) => { NatExpr::Paren }
+        ));
+    }
+
+    impl<'a> Synom<'a> for NatExprConst {
+        named!(parse_cursor(Cursor<'a>) -> NatExprConst, do_parse!(
+            value_cursor: take_while1!(is_decimal_digit) >>
+            value: map_res!(value!(value_cursor.to_str()), str::parse) >>
+            span: value!(value_cursor.span()) >>
+
+            (NatExprConst { span, value })
+        ));
+    }
+
+    impl<'a> Synom<'a> for NatExprIdent<'a> {
+        named!(parse_cursor(Cursor<'a>) -> NatExprIdent<'a>, do_parse!(
+            ident: tlsyn!(Ident) >>
+            (NatExprIdent { ident })
+        ));
+    }
+
+    impl<'a> Synom<'a> for NatExprParen<'a> {
+        named!(parse_cursor(Cursor<'a>) -> NatExprParen<'a>, do_parse!(
+            expr: parens!(do_parse!(
+                call!(nom::multispace0) >>
+                expr: map!(tlsyn!(NatExpr), Box::new) >>
+                call!(nom::multispace0) >>
+
+                (expr)
+            )) >>
+
+            (NatExprParen {
+                paren_token: expr.0,
+                expr: expr.1,
+            })
+        ));
+    }
+}
+
+
+#[cfg(feature = "printing")]
+mod printing {
+    use std::fmt;
+
+    use super::*;
+    use print::Print;
+    use print::private::Sealed;
+
+    impl<'a> Sealed for NatExpr<'a> {}
+    impl Sealed for NatExprConst {}
+    impl<'a> Sealed for NatExprIdent<'a> {}
+    impl<'a> Sealed for NatExprAdd<'a> {}
+    impl<'a> Sealed for NatExprParen<'a> {}
+
+    impl<'a> Print for NatExpr<'a> {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match *self {
+                NatExpr::Const(ref t) => t.print(f),
+                NatExpr::Ident(ref t) => t.print(f),
+                NatExpr::Add(ref t) => t.print(f),
+                NatExpr::Paren(ref t) => t.print(f),
+            }
+        }
+    }
+
+    impl Print for NatExprConst {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt::Display::fmt(&self.value, f)
+        }
+    }
+
+    impl<'a> Print for NatExprIdent<'a> {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.ident.print(f)
+        }
+    }
+
+    impl<'a> Print for NatExprAdd<'a> {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.left.print(f)?;
+            self.plus_token.print(f)?;
+            self.right.print(f)?;
+
+            Ok(())
+        }
+    }
+
+    impl<'a> Print for NatExprParen<'a> {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            Paren::print(f, |f| {
+                self.expr.print(f)
+            })
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "eq-impls")]
+    use super::*;
+    #[cfg(feature = "eq-impls")]
+    use utils::tests::test_span_permutations;
+    #[cfg(all(feature = "eq-impls", feature = "hash-impls"))]
+    use utils::tests::get_hasher_state;
+
+
+    #[cfg(feature = "eq-impls")]
+    fn test_nat_expr_const_span_permutations<FT, FA1, FA2>(
+        test_eq: FT,
+        assert_when_eq: FA1,
+        assert_when_ne: FA2,
+    )
+    where
+        FT: Fn(&NatExprConst, &NatExprConst) -> bool,
+        FA1: Fn(&NatExprConst, &NatExprConst),
+        FA2: Fn(&NatExprConst, &NatExprConst),
+    {
+        let values = [0, 1, 2, 100, 0xFFFF, 0x7FFF_FFFF];
+
+        for value1 in &values {
+            for value2 in &values {
+                test_span_permutations(
+                    |span1| NatExprConst { span: span1, value: *value1 },
+                    |span2| NatExprConst { span: span2, value: *value2 },
+                    &test_eq,
+                    &assert_when_eq,
+                    &assert_when_ne,
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "eq-impls")]
+    #[test]
+    fn eq_does_not_depend_on_span() {
+        test_nat_expr_const_span_permutations(
+            |x, y| x.value == y.value,
+            |x, y| any_debug_assert_eq!(x, y),
+            |x, y| any_debug_assert_ne!(x, y),
+        );
+    }
+
+    #[cfg(all(feature = "eq-impls", feature = "hash-impls"))]
+    #[test]
+    fn eq_hash_property() {
+        test_nat_expr_const_span_permutations(
+            |x, y| x == y,
+            |x, y| any_debug_assert_eq!(get_hasher_state(x), get_hasher_state(y)),
+            |x, y| any_debug_assert_ne!(get_hasher_state(x), get_hasher_state(y)),
+        );
+    }
+}
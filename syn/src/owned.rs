@@ -0,0 +1,107 @@
+//! Conversion of syntax tree nodes borrowing from the source text into nodes
+//! which own all of their data.
+//!
+//! Parsing doesn't allocate for identifiers and comments: syntax tree nodes
+//! produced by `Synom` borrow them from the input string instead, which makes
+//! parsing large schemas cheap. [`IntoOwned`] detaches such a node from the
+//! input so that it can outlive it.
+
+
+pub(crate) mod private {
+    /// `Sealed` stops crates other than `tl-lang-syn` from implementing the
+    /// `IntoOwned` trait.
+    pub trait Sealed {}
+
+    impl<T: Sealed> Sealed for Box<T> {}
+    impl<T: Sealed> Sealed for Option<T> {}
+    impl<T: Sealed> Sealed for Vec<T> {}
+}
+
+
+/// Conversion of a syntax tree node into the same node with the `'static`
+/// lifetime.
+///
+/// This trait is sealed and cannot be implemented for types outside of
+/// `tl-lang-syn` to avoid breaking backwards compatibility when adding new
+/// methods or derived traits.
+pub trait IntoOwned: private::Sealed {
+    /// The type of this syntax tree node which owns all of its data.
+    type Owned: 'static;
+
+    /// Copy all strings borrowed from the source text into the node itself.
+    fn into_owned(self) -> Self::Owned;
+}
+
+
+impl<T: IntoOwned> IntoOwned for Box<T> {
+    type Owned = Box<T::Owned>;
+
+    fn into_owned(self) -> Box<T::Owned> {
+        Box::new((*self).into_owned())
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn into_owned(self) -> Option<T::Owned> {
+        self.map(IntoOwned::into_owned)
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Vec<T> {
+    type Owned = Vec<T::Owned>;
+
+    fn into_owned(self) -> Vec<T::Owned> {
+        self.into_iter().map(IntoOwned::into_owned).collect()
+    }
+}
+
+
+#[cfg(all(test, feature = "parsing"))]
+mod tests {
+    use super::*;
+    use item::Item;
+
+    fn first_combinator_name<'a>(file: &'a ::File) -> &'a str {
+        match file.items[0] {
+            Item::Combinator(ref combinator) => combinator.name.segments[0].as_str(),
+            _ => panic!("expected a combinator"),
+        }
+    }
+
+    #[test]
+    fn parsing_borrows_from_input() {
+        let input = String::from("messages.sendMessage#fa88427a peer:InputPeer message:string = Updates;");
+        let file = ::parse_file_str(&input).unwrap();
+        let name = first_combinator_name(&file);
+
+        assert_eq!(name, "messages");
+        assert_eq!(name.as_ptr(), input.as_ptr());
+    }
+
+    #[test]
+    fn into_owned() {
+        let file = {
+            let input = String::from("// Booleans\nboolTrue#997275b5 = Bool;\n");
+            ::parse_file_str(&input).unwrap().into_owned()
+        };
+
+        assert_eq!(first_combinator_name(&file), "boolTrue");
+        match file.items[0] {
+            Item::Combinator(ref combinator) => assert_eq!(combinator.leading_comments.len(), 1),
+            _ => panic!("expected a combinator"),
+        }
+    }
+
+    #[cfg(all(feature = "clone-impls", feature = "eq-impls"))]
+    #[test]
+    fn to_owned() {
+        let input = String::from("vector#1cb5c415 {t:Type} # [ t ] = Vector t;");
+        let file = ::parse_file_str(&input).unwrap();
+        let owned = file.to_owned();
+
+        drop(input);
+        any_debug_assert_eq!(owned, ::parse_file_str("vector#1cb5c415 {t:Type} # [ t ] = Vector t;").unwrap());
+    }
+}
@@ -37,7 +37,57 @@ macro_rules! parens {
 
 macro_rules! slash_asterisks {
     ($i:expr,) => {
-        $crate::token::SlashAsterisk::parse($i, |i| take_until!(i, $crate::token::SlashAsterisk::RIGHT))
+        {
+            let i = {$i};
+
+            match $crate::token::SlashAsterisk::parse(i, |i| take_until!(i, $crate::token::SlashAsterisk::RIGHT)) {
+                Err($crate::nom::Err::Incomplete(_)) if i.to_str().starts_with($crate::token::SlashAsterisk::LEFT) => {
+                    $crate::error::ErrorCode::UnterminatedComment.failure(i)
+                },
+                res => res,
+            }
+        }
+    };
+}
+
+
+/// Turn a recoverable error of the given parser into a hard failure described
+/// by the given `ErrorCode`.
+///
+/// Use this once the input can't be anything but the node being parsed, so
+/// that the error points to the offending token instead of the beginning of
+/// the node.
+macro_rules! expect {
+    ($i:expr, $code:expr, $submac:ident!( $($args:tt)* )) => {
+        {
+            let i = {$i};
+
+            match $submac!(i, $($args)*) {
+                Err($crate::nom::Err::Error(_)) |
+                Err($crate::nom::Err::Incomplete(_)) => $code.failure(i),
+                res => res,
+            }
+        }
+    };
+}
+
+
+/// Describe a recoverable error of the given parser by the given
+/// `ErrorCode`.
+///
+/// Unlike `expect!`, other alternatives are still tried, and the error is
+/// only reported if none of them gets further.
+macro_rules! expect_recoverable {
+    ($i:expr, $code:expr, $submac:ident!( $($args:tt)* )) => {
+        {
+            let i = {$i};
+
+            match $submac!(i, $($args)*) {
+                Err($crate::nom::Err::Error(_)) |
+                Err($crate::nom::Err::Incomplete(_)) => $code.error(i),
+                res => res,
+            }
+        }
     };
 }
 
@@ -49,7 +99,7 @@ macro_rules! with_afterspace {
 
             match $submac!(i, $($args)*) {
                 Err(e) => Err(e),
-                Ok((rest1, o)) => match nom::space0(rest1) {
+                Ok((rest1, o)) => match nom::multispace0(rest1) {
                     Err(e) => Err(e),
                     Ok((rest2, _space)) => Ok((rest2, o)),
                 },
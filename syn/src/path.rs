@@ -1,66 +1,130 @@
-use super::Ident;
+use std::borrow::Cow;
+
+use super::{Ident, NatExpr, Type};
 use punctuated::Punctuated;
-use token::Paren;
+use span::Span;
 
 
 macro_attr_many! {
     /// A dot-separated list of identifiers.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct Path {
-        pub segments: Punctuated<Ident, TLToken![.]>,
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct Path<'a> {
+        pub segments: Punctuated<Ident<'a>, TLToken![.]>,
     }
 
     /// A dot-separated list of identifiers with optional generic arguments.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct ParameterizedPath {
-        pub path: Path,
-        pub args: Option<GenericArguments>,
+    ///
+    /// Space-separated generic arguments are only accepted when parsing a
+    /// type expression; as a type term a parameterized path may only have
+    /// angle-bracketed ones.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct ParameterizedPath<'a> {
+        pub path: Path<'a>,
+        pub args: Option<GenericArguments<'a>>,
     }
 
     /// Generic arguments for parameterized paths.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub enum GenericArguments {
-        AngleBracketed(AngleBracketedGenericArguments),
-        SpaceSeparated(SpaceSeparatedGenericArguments),
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub enum GenericArguments<'a> {
+        AngleBracketed(AngleBracketedGenericArguments<'a>),
+        SpaceSeparated(SpaceSeparatedGenericArguments<'a>),
     }
 
     /// A comma-separated list of generic arguments enclosed in angle tokens.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct AngleBracketedGenericArguments {
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct AngleBracketedGenericArguments<'a> {
         pub langle_token: TLToken![<],
-        pub args: Punctuated<ParameterizedPath, TLToken![,]>,
+        pub args: Punctuated<GenericArgument<'a>, TLToken![,]>,
         pub rangle_token: TLToken![>],
     }
 
     /// A space-separated list of generic arguments.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct SpaceSeparatedGenericArguments {
-        pub args: Vec<ParameterizedPath>,
+    ///
+    /// Every argument is a type term, so nested applications must be enclosed
+    /// in parentheses: `Vector (Maybe %Foo)`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct SpaceSeparatedGenericArguments<'a> {
+        pub args: Vec<GenericArgument<'a>>,
+    }
+
+    /// A single generic argument: either a type or a natural number expression.
+    ///
+    /// A lone identifier is always parsed as a type, since it is impossible to
+    /// tell a type from a `#` variable without looking at its declaration.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub enum GenericArgument<'a> {
+        Type(Type<'a>),
+        Nat(NatExpr<'a>),
     }
+}
 
-    /// A dot-separated list of identifiers with optional generic arguments that
-    /// spans over a single token tree group.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub enum SafeParameterizedPath {
-        SpaceImmune(SafeParameterizedPathSpaceImmune),
-        Parenthesized(SafeParameterizedPathParenthesized),
+impl<'a> Path<'a> {
+    /// Create a new `Path` with zeroed spans from a string of dot-separated
+    /// identifiers: `"messages.sendMessage"`.
+    ///
+    /// Return `None` if any of the segments is not a valid TL language
+    /// identifier.
+    pub fn new<S: Into<Cow<'a, str>>>(string: S) -> Option<Path<'a>> {
+        let segments = match string.into() {
+            Cow::Borrowed(string) => string.split('.')
+                .map(|segment| Ident::new(Span::zeroed(), segment))
+                .collect::<Option<Vec<_>>>()?,
+            Cow::Owned(string) => string.split('.')
+                .map(|segment| Ident::new(Span::zeroed(), segment.to_owned()))
+                .collect::<Option<Vec<_>>>()?,
+        };
+
+        Some(Path { segments: segments.into_iter().collect() })
     }
+}
 
-    /// A parameterized path that spans a single token tree group.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct SafeParameterizedPathSpaceImmune {
-        pub path: Path,
-        pub args: Option<AngleBracketedGenericArguments>,
+impl<'a> From<Ident<'a>> for Path<'a> {
+    fn from(ident: Ident<'a>) -> Path<'a> {
+        let mut segments = Punctuated::new();
+        segments.push_value(ident);
+
+        Path { segments }
     }
+}
 
-    /// An arbitrary parameterized path enclosed in parentheses.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct SafeParameterizedPathParenthesized {
-        pub paren_token: Paren,
-        pub parameterized_path: ParameterizedPath,
+impl<'a> ParameterizedPath<'a> {
+    /// Create a new `ParameterizedPath` which applies `path` to
+    /// angle-bracketed `args`: `Vector<InputPeer>`.
+    pub fn with_args<I>(path: Path<'a>, args: I) -> ParameterizedPath<'a>
+    where
+        I: IntoIterator,
+        I::Item: Into<GenericArgument<'a>>,
+    {
+        ParameterizedPath {
+            path,
+            args: Some(GenericArguments::AngleBracketed(AngleBracketedGenericArguments {
+                langle_token: Default::default(),
+                args: args.into_iter().map(Into::into).collect(),
+                rangle_token: Default::default(),
+            })),
+        }
     }
 }
 
+impl<'a> From<Path<'a>> for ParameterizedPath<'a> {
+    fn from(path: Path<'a>) -> ParameterizedPath<'a> {
+        ParameterizedPath { path, args: None }
+    }
+}
+
+impl<'a> From<Type<'a>> for GenericArgument<'a> {
+    fn from(ty: Type<'a>) -> GenericArgument<'a> {
+        GenericArgument::Type(ty)
+    }
+}
+
+impl<'a> From<NatExpr<'a>> for GenericArgument<'a> {
+    fn from(nat: NatExpr<'a>) -> GenericArgument<'a> {
+        GenericArgument::Nat(nat)
+    }
+}
+
+
 
 mod spanned {
     use super::*;
@@ -68,29 +132,27 @@ mod spanned {
     use spanned::Spanned;
     use spanned::private::Sealed;
 
-    impl Sealed for Path {}
-    impl Sealed for ParameterizedPath {}
-    impl Sealed for GenericArguments {}
-    impl Sealed for AngleBracketedGenericArguments {}
-    impl Sealed for SpaceSeparatedGenericArguments {}
-    impl Sealed for SafeParameterizedPath {}
-    impl Sealed for SafeParameterizedPathSpaceImmune {}
-    impl Sealed for SafeParameterizedPathParenthesized {}
+    impl<'a> Sealed for Path<'a> {}
+    impl<'a> Sealed for ParameterizedPath<'a> {}
+    impl<'a> Sealed for GenericArguments<'a> {}
+    impl<'a> Sealed for AngleBracketedGenericArguments<'a> {}
+    impl<'a> Sealed for SpaceSeparatedGenericArguments<'a> {}
+    impl<'a> Sealed for GenericArgument<'a> {}
 
-    impl Spanned for Path {
+    impl<'a> Spanned for Path<'a> {
         fn span(&self) -> Span {
             self.segments.span()
         }
     }
 
-    impl Spanned for ParameterizedPath {
+    impl<'a> Spanned for ParameterizedPath<'a> {
         fn span(&self) -> Span {
             self.path.span()
                 .to(self.args.span())
         }
     }
 
-    impl Spanned for GenericArguments {
+    impl<'a> Spanned for GenericArguments<'a> {
         fn span(&self) -> Span {
             match *self {
                 GenericArguments::AngleBracketed(ref t) => t.span(),
@@ -99,7 +161,7 @@ mod spanned {
         }
     }
 
-    impl Spanned for AngleBracketedGenericArguments {
+    impl<'a> Spanned for AngleBracketedGenericArguments<'a> {
         fn span(&self) -> Span {
             self.langle_token.span()
                 .to(self.args.span())
@@ -107,36 +169,109 @@ mod spanned {
         }
     }
 
-    impl Spanned for SpaceSeparatedGenericArguments {
+    impl<'a> Spanned for SpaceSeparatedGenericArguments<'a> {
         fn span(&self) -> Span {
             self.args.span()
         }
     }
 
-    impl Spanned for SafeParameterizedPath {
+    impl<'a> Spanned for GenericArgument<'a> {
         fn span(&self) -> Span {
             match *self {
-                SafeParameterizedPath::SpaceImmune(ref t) => t.span(),
-                SafeParameterizedPath::Parenthesized(ref t) => t.span(),
+                GenericArgument::Type(ref t) => t.span(),
+                GenericArgument::Nat(ref t) => t.span(),
             }
         }
     }
+}
 
-    impl Spanned for SafeParameterizedPathSpaceImmune {
-        fn span(&self) -> Span {
-            self.path.span()
-                .to(self.args.span())
+
+mod owned {
+    use super::*;
+    use owned::IntoOwned;
+    use owned::private::Sealed;
+
+    impl_to_owned!(
+        Path,
+        ParameterizedPath,
+        GenericArguments,
+        AngleBracketedGenericArguments,
+        SpaceSeparatedGenericArguments,
+        GenericArgument,
+    );
+
+    impl<'a> Sealed for Path<'a> {}
+    impl<'a> Sealed for ParameterizedPath<'a> {}
+    impl<'a> Sealed for GenericArguments<'a> {}
+    impl<'a> Sealed for AngleBracketedGenericArguments<'a> {}
+    impl<'a> Sealed for SpaceSeparatedGenericArguments<'a> {}
+    impl<'a> Sealed for GenericArgument<'a> {}
+
+    impl<'a> IntoOwned for Path<'a> {
+        type Owned = Path<'static>;
+
+        fn into_owned(self) -> Path<'static> {
+            Path {
+                segments: self.segments.into_owned(),
+            }
         }
     }
 
-    impl Spanned for SafeParameterizedPathParenthesized {
-        fn span(&self) -> Span {
-            self.paren_token.span()
-                .to(self.parameterized_path.span())
+    impl<'a> IntoOwned for ParameterizedPath<'a> {
+        type Owned = ParameterizedPath<'static>;
+
+        fn into_owned(self) -> ParameterizedPath<'static> {
+            ParameterizedPath {
+                path: self.path.into_owned(),
+                args: self.args.into_owned(),
+            }
         }
     }
-}
 
+    impl<'a> IntoOwned for GenericArguments<'a> {
+        type Owned = GenericArguments<'static>;
+
+        fn into_owned(self) -> GenericArguments<'static> {
+            match self {
+                GenericArguments::AngleBracketed(t) => GenericArguments::AngleBracketed(t.into_owned()),
+                GenericArguments::SpaceSeparated(t) => GenericArguments::SpaceSeparated(t.into_owned()),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for AngleBracketedGenericArguments<'a> {
+        type Owned = AngleBracketedGenericArguments<'static>;
+
+        fn into_owned(self) -> AngleBracketedGenericArguments<'static> {
+            AngleBracketedGenericArguments {
+                langle_token: self.langle_token,
+                args: self.args.into_owned(),
+                rangle_token: self.rangle_token,
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for SpaceSeparatedGenericArguments<'a> {
+        type Owned = SpaceSeparatedGenericArguments<'static>;
+
+        fn into_owned(self) -> SpaceSeparatedGenericArguments<'static> {
+            SpaceSeparatedGenericArguments {
+                args: self.args.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for GenericArgument<'a> {
+        type Owned = GenericArgument<'static>;
+
+        fn into_owned(self) -> GenericArgument<'static> {
+            match self {
+                GenericArgument::Type(t) => GenericArgument::Type(t.into_owned()),
+                GenericArgument::Nat(t) => GenericArgument::Nat(t.into_owned()),
+            }
+        }
+    }
+}
 
 #[cfg(feature = "parsing")]
 mod parsing {
@@ -148,18 +283,16 @@ mod parsing {
     use synom::Synom;
     use synom::private::Sealed;
 
-    impl Sealed for Path {}
-    impl Sealed for ParameterizedPath {}
-    impl Sealed for GenericArguments {}
-    impl Sealed for AngleBracketedGenericArguments {}
-    impl Sealed for SpaceSeparatedGenericArguments {}
-    impl Sealed for SafeParameterizedPath {}
-    impl Sealed for SafeParameterizedPathSpaceImmune {}
-    impl Sealed for SafeParameterizedPathParenthesized {}
-
-    impl Synom for Path {
-        named!(parse_cursor(Cursor) -> Path, do_parse!(
-            segments: call!(|s| Punctuated::<Ident, TLToken![.]>::parse(
+    impl<'a> Sealed for Path<'a> {}
+    impl<'a> Sealed for ParameterizedPath<'a> {}
+    impl<'a> Sealed for GenericArguments<'a> {}
+    impl<'a> Sealed for AngleBracketedGenericArguments<'a> {}
+    impl<'a> Sealed for SpaceSeparatedGenericArguments<'a> {}
+    impl<'a> Sealed for GenericArgument<'a> {}
+
+    impl<'a> Synom<'a> for Path<'a> {
+        named!(parse_cursor(Cursor<'a>) -> Path<'a>, do_parse!(
+            segments: call!(|s| Punctuated::<Ident<'a>, TLToken![.]>::parse(
                 s,
                 TrailingPunctuation::None,
                 Count::OneOrMore,
@@ -170,28 +303,40 @@ mod parsing {
         ));
     }
 
-    impl Synom for ParameterizedPath {
-        named!(parse_cursor(Cursor) -> ParameterizedPath, do_parse!(
+    impl<'a> Synom<'a> for ParameterizedPath<'a> {
+        named!(parse_cursor(Cursor<'a>) -> ParameterizedPath<'a>, do_parse!(
             path: tlsyn!(Path) >>
-            call!(nom::space0) >>
+            call!(nom::multispace0) >>
             args: opt!(tlsyn!(GenericArguments)) >>
 
             (ParameterizedPath { path, args })
         ));
     }
 
-    impl Synom for GenericArguments {
-        named!(parse_cursor(Cursor) -> GenericArguments, alt_complete!(
+    impl<'a> ParameterizedPath<'a> {
+        named!(pub(crate) parse_term(Cursor<'a>) -> ParameterizedPath<'a>, do_parse!(
+            path: tlsyn!(Path) >>
+            args: opt!(map!(
+                tlsyn!(AngleBracketedGenericArguments),
+                GenericArguments::AngleBracketed
+            )) >>
+
+            (ParameterizedPath { path, args })
+        ));
+    }
+
+    impl<'a> Synom<'a> for GenericArguments<'a> {
+        named!(parse_cursor(Cursor<'a>) -> GenericArguments<'a>, alt_complete!(
             tlsyn!(AngleBracketedGenericArguments) => { GenericArguments::AngleBracketed }
             |
             tlsyn!(SpaceSeparatedGenericArguments) => { GenericArguments::SpaceSeparated }
         ));
     }
 
-    impl Synom for AngleBracketedGenericArguments {
-        named!(parse_cursor(Cursor) -> AngleBracketedGenericArguments, do_parse!(
+    impl<'a> Synom<'a> for AngleBracketedGenericArguments<'a> {
+        named!(parse_cursor(Cursor<'a>) -> AngleBracketedGenericArguments<'a>, do_parse!(
             langle_token: tlpunct!(<) >>
-            args: call!(|s| Punctuated::<ParameterizedPath, TLToken![,]>::parse(
+            args: call!(|s| Punctuated::<GenericArgument<'a>, TLToken![,]>::parse(
                 s,
                 TrailingPunctuation::Optional,
                 Count::OneOrMore,
@@ -203,40 +348,50 @@ mod parsing {
         ));
     }
 
-    impl Synom for SpaceSeparatedGenericArguments {
-        named!(parse_cursor(Cursor) -> SpaceSeparatedGenericArguments, do_parse!(
-            args: many1!(with_afterspace!(tlsyn!(ParameterizedPath))) >>
+    impl<'a> Synom<'a> for SpaceSeparatedGenericArguments<'a> {
+        named!(parse_cursor(Cursor<'a>) -> SpaceSeparatedGenericArguments<'a>, do_parse!(
+            args: many1!(with_afterspace!(call!(GenericArgument::parse_term))) >>
 
             (SpaceSeparatedGenericArguments { args })
         ));
     }
 
-    impl Synom for SafeParameterizedPath {
-        named!(parse_cursor(Cursor) -> SafeParameterizedPath, alt_complete!(
-            tlsyn!(SafeParameterizedPathSpaceImmune) => { SafeParameterizedPath::SpaceImmune }
+    impl<'a> Synom<'a> for GenericArgument<'a> {
+        named!(parse_cursor(Cursor<'a>) -> GenericArgument<'a>, alt_complete!(
+            call!(parse_nat_expr_non_ident) => { GenericArgument::Nat }
             |
-            tlsyn!(SafeParameterizedPathParenthesized) => { SafeParameterizedPath::Parenthesized }
+            tlsyn!(Type) => { GenericArgument::Type }
         ));
     }
 
-    impl Synom for SafeParameterizedPathSpaceImmune {
-        named!(parse_cursor(Cursor) -> SafeParameterizedPathSpaceImmune, do_parse!(
-            path: tlsyn!(Path) >>
-            args: opt!(tlsyn!(AngleBracketedGenericArguments)) >>
-
-            (SafeParameterizedPathSpaceImmune { path, args })
+    impl<'a> GenericArgument<'a> {
+        named!(pub(crate) parse_term(Cursor<'a>) -> GenericArgument<'a>, alt_complete!(
+            call!(parse_nat_expr_term_non_ident) => { GenericArgument::Nat }
+            |
+            call!(Type::parse_term) => { GenericArgument::Type }
         ));
     }
 
-    impl Synom for SafeParameterizedPathParenthesized {
-        named!(parse_cursor(Cursor) -> SafeParameterizedPathParenthesized, do_parse!(
-            parameterized_path: parens!(tlsyn!(ParameterizedPath)) >>
+    fn parse_nat_expr_non_ident(input: Cursor) -> nom::IResult<Cursor, NatExpr> {
+        verify_nat_expr_non_ident(input, NatExpr::parse_cursor(input))
+    }
 
-            (SafeParameterizedPathParenthesized {
-                paren_token: parameterized_path.0,
-                parameterized_path: parameterized_path.1,
-            })
-        ));
+    fn parse_nat_expr_term_non_ident(input: Cursor) -> nom::IResult<Cursor, NatExpr> {
+        verify_nat_expr_non_ident(input, NatExpr::parse_term(input))
+    }
+
+    // Identifiers, even parenthesized ones, are left for `Type` to handle.
+    fn verify_nat_expr_non_ident<'a>(
+        input: Cursor<'a>,
+        result: nom::IResult<Cursor<'a>, NatExpr<'a>>,
+    ) -> nom::IResult<Cursor<'a>, NatExpr<'a>> {
+        let (rest, nat_expr) = result?;
+
+        if nat_expr.is_ident() {
+            Err(nom::Err::Error(error_position!(input, nom::ErrorKind::Verify)))
+        } else {
+            Ok((rest, nat_expr))
+        }
     }
 }
 
@@ -250,22 +405,20 @@ mod printing {
     use print::private::Sealed;
     use punctuated::{Count, Whitespace};
 
-    impl Sealed for Path {}
-    impl Sealed for ParameterizedPath {}
-    impl Sealed for GenericArguments {}
-    impl Sealed for AngleBracketedGenericArguments {}
-    impl Sealed for SpaceSeparatedGenericArguments {}
-    impl Sealed for SafeParameterizedPath {}
-    impl Sealed for SafeParameterizedPathSpaceImmune {}
-    impl Sealed for SafeParameterizedPathParenthesized {}
+    impl<'a> Sealed for Path<'a> {}
+    impl<'a> Sealed for ParameterizedPath<'a> {}
+    impl<'a> Sealed for GenericArguments<'a> {}
+    impl<'a> Sealed for AngleBracketedGenericArguments<'a> {}
+    impl<'a> Sealed for SpaceSeparatedGenericArguments<'a> {}
+    impl<'a> Sealed for GenericArgument<'a> {}
 
-    impl Print for Path {
+    impl<'a> Print for Path<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             self.segments.print(f, Count::OneOrMore, Whitespace::None)
         }
     }
 
-    impl Print for ParameterizedPath {
+    impl<'a> Print for ParameterizedPath<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             self.path.print(f)?;
             if let Some(GenericArguments::SpaceSeparated(_)) = self.args {
@@ -277,7 +430,7 @@ mod printing {
         }
     }
 
-    impl Print for GenericArguments {
+    impl<'a> Print for GenericArguments<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             match *self {
                 GenericArguments::AngleBracketed(ref t) => t.print(f),
@@ -286,7 +439,7 @@ mod printing {
         }
     }
 
-    impl Print for AngleBracketedGenericArguments {
+    impl<'a> Print for AngleBracketedGenericArguments<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             self.langle_token.print(f)?;
             self.args.print(f, Count::OneOrMore, Whitespace::Present)?;
@@ -296,7 +449,7 @@ mod printing {
         }
     }
 
-    impl Print for SpaceSeparatedGenericArguments {
+    impl<'a> Print for SpaceSeparatedGenericArguments<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             let (first, rest) = self.args.split_first().unwrap();  // There must be at least one argument
 
@@ -311,29 +464,12 @@ mod printing {
         }
     }
 
-    impl Print for SafeParameterizedPath {
+    impl<'a> Print for GenericArgument<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             match *self {
-                SafeParameterizedPath::SpaceImmune(ref t) => t.print(f),
-                SafeParameterizedPath::Parenthesized(ref t) => t.print(f),
+                GenericArgument::Type(ref t) => t.print(f),
+                GenericArgument::Nat(ref t) => t.print(f),
             }
         }
     }
-
-    impl Print for SafeParameterizedPathSpaceImmune {
-        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            self.path.print(f)?;
-            self.args.print(f)?;
-
-            Ok(())
-        }
-    }
-
-    impl Print for SafeParameterizedPathParenthesized {
-        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            Paren::print(f, |f| {
-                self.parameterized_path.print(f)
-            })
-        }
-    }
 }
@@ -0,0 +1,719 @@
+//! A configurable formatter of TL language schemas.
+//!
+//! `Print` writes every syntax tree node in a single fixed style which keeps
+//! the parsed structure, but doesn't care about how the result looks. A
+//! [`PrettyPrinter`] lays out a whole [`File`] instead: it aligns columns of
+//! neighbouring combinators, wraps long combinators over several lines and
+//! normalises the way ids and generic arguments are written.
+//!
+//! The default options of `PrettyPrinter` define the canonical formatting of
+//! TL language schemas.
+
+use std::fmt::Write;
+
+use print::Print;
+use {
+    Comment, File, GenericArgument, GenericArguments, Id, Item, ItemBuiltin, ItemCombinator,
+    NatExpr, OptParam, Param, ParameterizedPath, Path, Type,
+};
+
+
+/// Indentation of parameters of a combinator wrapped over several lines.
+const PARAM_INDENT: &str = "    ";
+/// Indentation of the result type of a combinator wrapped over several lines.
+const RESULT_INDENT: &str = "  ";
+
+
+/// A formatter of TL language schemas with configurable options.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PrettyPrinter {
+    align_ids: bool,
+    align_equals: bool,
+    max_width: Option<usize>,
+    generic_arguments: Option<GenericArgumentsStyle>,
+    id_case: Option<IdCase>,
+    namespace_blank_lines: bool,
+}
+
+/// A way to write generic arguments of a type.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum GenericArgumentsStyle {
+    /// `Vector<t>`.
+    AngleBracketed,
+    /// `Vector t`.
+    ///
+    /// Since types of parameters can't have space-separated generic arguments
+    /// without being wrapped in parentheses, their outermost generic
+    /// arguments keep angle brackets.
+    SpaceSeparated,
+}
+
+/// A letter case of hex digits of combinator ids.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum IdCase {
+    /// `#1cb5c415`.
+    Lowercase,
+    /// `#1CB5C415`.
+    Uppercase,
+}
+
+/// Parts of a combinator laid out on lines by `PrettyPrinter`.
+struct CombinatorLayout {
+    leading_comments: Vec<String>,
+    /// `name#id`.
+    head: String,
+    /// Optional parameters, parameters and comments between them.
+    pieces: Vec<Piece>,
+    /// `= Result;` with the result comments, followed by the trailing
+    /// comment, if any.
+    tail: String,
+}
+
+enum Piece {
+    Param(String),
+    /// A `/*...*/` comment which can be followed by something on the same
+    /// line.
+    Comment(String),
+    /// A `//...` comment which runs until the end of the line.
+    LineComment(String),
+}
+
+
+impl Default for PrettyPrinter {
+    fn default() -> PrettyPrinter {
+        PrettyPrinter {
+            align_ids: true,
+            align_equals: true,
+            max_width: Some(100),
+            generic_arguments: None,
+            id_case: Some(IdCase::Lowercase),
+            namespace_blank_lines: true,
+        }
+    }
+}
+
+impl PrettyPrinter {
+    /// Create a new `PrettyPrinter` with the canonical options.
+    pub fn new() -> PrettyPrinter {
+        PrettyPrinter::default()
+    }
+
+    /// Pad `name#id` of neighbouring combinators so that their parameters
+    /// start at the same column.
+    ///
+    /// An id must immediately follow the name of its combinator, so the
+    /// padding goes after the id.
+    pub fn align_ids(mut self, align_ids: bool) -> PrettyPrinter {
+        self.align_ids = align_ids;
+        self
+    }
+
+    /// Pad neighbouring combinators so that their `=` are at the same column.
+    pub fn align_equals(mut self, align_equals: bool) -> PrettyPrinter {
+        self.align_equals = align_equals;
+        self
+    }
+
+    /// Put each parameter of a combinator on its own line if the combinator
+    /// doesn't fit in `max_width` characters, or never wrap combinators if
+    /// it's `None`.
+    pub fn max_width(mut self, max_width: Option<usize>) -> PrettyPrinter {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Write generic arguments in the given style, or as they are written in
+    /// the syntax tree if it's `None`.
+    pub fn generic_arguments(mut self, style: Option<GenericArgumentsStyle>) -> PrettyPrinter {
+        self.generic_arguments = style;
+        self
+    }
+
+    /// Write hex digits of combinator ids in the given case, or as they are
+    /// written in the syntax tree if it's `None`.
+    pub fn id_case(mut self, id_case: Option<IdCase>) -> PrettyPrinter {
+        self.id_case = id_case;
+        self
+    }
+
+    /// Separate neighbouring combinators from different namespaces, like
+    /// `auth.*` and `account.*`, with a blank line.
+    pub fn namespace_blank_lines(mut self, namespace_blank_lines: bool) -> PrettyPrinter {
+        self.namespace_blank_lines = namespace_blank_lines;
+        self
+    }
+
+    /// Format `file`.
+    pub fn print_file(&self, file: &File) -> String {
+        let items = &file.items;
+        let mut rendered = Vec::with_capacity(items.len());
+        let mut start = 0;
+
+        while start < items.len() {
+            let mut end = start + 1;
+
+            if let Item::Combinator(_) = items[start] {
+                while end < items.len() && is_combinator(&items[end]) &&
+                    !self.is_blank_line_between(&items[end - 1], &items[end])
+                {
+                    end += 1;
+                }
+
+                let combinators = items[start..end].iter().filter_map(|item| match *item {
+                    Item::Combinator(ref combinator) => Some(combinator),
+                    _ => None,
+                });
+                rendered.extend(self.print_combinator_group(combinators));
+            } else {
+                rendered.push(self.print_item(&items[start]));
+            }
+
+            start = end;
+        }
+
+        let mut out = String::new();
+        for (i, text) in rendered.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+                if self.is_blank_line_between(&items[i - 1], &items[i]) {
+                    out.push('\n');
+                }
+            }
+            out.push_str(text);
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+
+        out
+    }
+
+    fn is_blank_line_between(&self, prev: &Item, next: &Item) -> bool {
+        match (prev, next) {
+            // Keep a standalone comment apart from the next combinator so
+            // that it doesn't become the combinator's leading comment
+            (&Item::Comment(_), &Item::Combinator(_)) => true,
+            (&Item::Delimiter(_), _) | (_, &Item::Delimiter(_)) => true,
+            (Item::Combinator(prev), Item::Combinator(next)) => {
+                self.namespace_blank_lines && namespace(&prev.name) != namespace(&next.name)
+            },
+            _ => false,
+        }
+    }
+
+    fn print_item(&self, item: &Item) -> String {
+        match *item {
+            Item::Combinator(ref combinator) => {
+                self.print_combinator_group(Some(combinator)).pop().unwrap()
+            },
+            Item::Builtin(ref builtin) => self.print_builtin(builtin),
+            ref item => item.display_wrapper().to_string(),
+        }
+    }
+
+    fn print_builtin(&self, builtin: &ItemBuiltin) -> String {
+        let mut out = builtin.name.display_wrapper().to_string();
+
+        if let Some(ref combinator_id) = builtin.combinator_id {
+            out.push('#');
+            self.push_id(&mut out, &combinator_id.id);
+        }
+        write!(out, " ? = {};", builtin.result_type.display_wrapper()).unwrap();
+
+        out
+    }
+
+    /// Print combinators which are aligned with each other.
+    fn print_combinator_group<'c, 'a: 'c, I>(&self, combinators: I) -> Vec<String>
+    where
+        I: IntoIterator<Item = &'c ItemCombinator<'a>>,
+    {
+        let layouts = combinators.into_iter()
+            .map(|combinator| self.layout_combinator(combinator))
+            .collect::<Vec<_>>();
+        let single_lines = layouts.iter()
+            .map(|layout| self.single_line(layout))
+            .collect::<Vec<_>>();
+
+        let head_width = if self.align_ids {
+            layouts.iter().map(|layout| width(&layout.head)).max().unwrap_or(0)
+        } else {
+            0
+        };
+        let mut prefixes = layouts.iter().zip(&single_lines)
+            .map(|(layout, single_line)| single_line.as_ref().map(|_| {
+                let mut prefix = pad(&layout.head, head_width);
+                for piece in &layout.pieces {
+                    if let Piece::Param(ref text) | Piece::Comment(ref text) = *piece {
+                        prefix.push(' ');
+                        prefix.push_str(text);
+                    }
+                }
+                prefix
+            }))
+            .collect::<Vec<_>>();
+
+        // Padding a line to its neighbours may push it over `max_width`.
+        // Wrapping it may in turn narrow the padding of the others, so repeat
+        // until all the remaining lines fit.
+        let prefix_width = loop {
+            let prefix_width = if self.align_equals {
+                prefixes.iter().flatten().map(|prefix| width(prefix)).max().unwrap_or(0)
+            } else {
+                0
+            };
+            let mut all_fit = true;
+
+            for (layout, prefix) in layouts.iter().zip(&mut prefixes) {
+                let fits = prefix.as_ref().map_or(true, |prefix| {
+                    self.fits(&padded_line(prefix, prefix_width, &layout.tail))
+                });
+
+                if !fits {
+                    *prefix = None;
+                    all_fit = false;
+                }
+            }
+
+            if all_fit {
+                break prefix_width;
+            }
+        };
+
+        layouts.iter().zip(prefixes).map(|(layout, prefix)| {
+            let mut lines = layout.leading_comments.clone();
+
+            match prefix {
+                Some(prefix) => lines.push(padded_line(&prefix, prefix_width, &layout.tail)),
+                None => lines.extend(wrapped_lines(layout)),
+            }
+
+            lines.join("\n")
+        }).collect()
+    }
+
+    /// Return the combinator on a single line if it fits.
+    fn single_line(&self, layout: &CombinatorLayout) -> Option<String> {
+        // A `//...` comment after `=` puts the result type on its own line
+        if layout.tail.contains('\n') {
+            return None;
+        }
+
+        let mut line = layout.head.clone();
+
+        for piece in &layout.pieces {
+            match *piece {
+                Piece::Param(ref text) | Piece::Comment(ref text) => {
+                    line.push(' ');
+                    line.push_str(text);
+                },
+                Piece::LineComment(_) => return None,
+            }
+        }
+        line.push(' ');
+        line.push_str(&layout.tail);
+
+        if self.fits(&line) { Some(line) } else { None }
+    }
+
+    fn fits(&self, line: &str) -> bool {
+        self.max_width.map_or(true, |max_width| width(line) <= max_width)
+    }
+
+    fn layout_combinator(&self, combinator: &ItemCombinator) -> CombinatorLayout {
+        let leading_comments = combinator.leading_comments.iter()
+            .map(|comment| comment.display_wrapper().to_string())
+            .collect();
+
+        let mut head = combinator.name.display_wrapper().to_string();
+        if let Some(ref combinator_id) = combinator.combinator_id {
+            head.push('#');
+            self.push_id(&mut head, &combinator_id.id);
+        }
+
+        let mut pieces = Vec::new();
+        for (index, opt_param) in combinator.opt_params.iter().enumerate() {
+            for opt_param_comment in combinator.opt_param_comments.iter().filter(|c| c.index == index) {
+                pieces.push(comment_piece(&opt_param_comment.comment));
+            }
+            pieces.push(Piece::Param(self.opt_param_to_string(opt_param)));
+        }
+        for index in 0..combinator.params.len() + 1 {
+            for param_comment in combinator.param_comments.iter().filter(|c| c.index == index) {
+                pieces.push(comment_piece(&param_comment.comment));
+            }
+
+            if let Some(param) = combinator.params.get(index) {
+                let mut text = String::new();
+                self.push_param(&mut text, param);
+                pieces.push(Piece::Param(text));
+            }
+        }
+
+        let mut tail = String::from("= ");
+        for comment in &combinator.result_comments {
+            write!(tail, "{}", comment.display_wrapper()).unwrap();
+            match *comment {
+                Comment::SingleLine(_) => write!(tail, "\n{}", PARAM_INDENT).unwrap(),
+                Comment::MultiLine(_) => tail.push(' '),
+            }
+        }
+        self.push_parameterized_path(&mut tail, &combinator.result_type, true);
+        tail.push(';');
+        if let Some(ref comment) = combinator.trailing_comment {
+            write!(tail, " {}", comment.display_wrapper()).unwrap();
+        }
+
+        CombinatorLayout { leading_comments, head, pieces, tail }
+    }
+
+    fn opt_param_to_string(&self, opt_param: &OptParam) -> String {
+        let mut out = String::from("{");
+
+        push_idents(&mut out, opt_param.var_idents.iter().map(|ident| ident.as_str()));
+        out.push(':');
+        self.push_type(&mut out, &opt_param.ty, true);
+        out.push('}');
+
+        out
+    }
+
+    fn push_param(&self, out: &mut String, param: &Param) {
+        match *param {
+            Param::Conditional(ref param) => {
+                write!(out, "{}:", param.var_ident.as_str()).unwrap();
+                if let Some(ref conditional_param_def) = param.conditional_param_def {
+                    write!(out, "{}", conditional_param_def.display_wrapper()).unwrap();
+                }
+                self.push_type(out, &param.ty, false);
+            },
+            Param::Repeated(ref param) => {
+                if let Some(ref param_repeated_ident) = param.param_repeated_ident {
+                    write!(out, "{}", param_repeated_ident.display_wrapper()).unwrap();
+                }
+                if let Some(ref multiplicity) = param.multiplicity {
+                    write!(out, "{}", multiplicity.display_wrapper()).unwrap();
+                }
+                out.push('[');
+                for (i, param) in param.params.iter().enumerate() {
+                    if i > 0 {
+                        out.push(' ');
+                    }
+                    self.push_param(out, param);
+                }
+                out.push(']');
+            },
+            Param::WithParen(ref param) => {
+                out.push('(');
+                push_idents(out, param.var_idents.iter().map(|ident| ident.as_str()));
+                out.push(':');
+                self.push_type(out, &param.ty, true);
+                out.push(')');
+            },
+            Param::TypeOnly(ref param) => self.push_type(out, &param.ty, false),
+        }
+    }
+
+    /// Push `ty` which may have space-separated generic arguments only if
+    /// `space_allowed` is true.
+    fn push_type(&self, out: &mut String, ty: &Type, space_allowed: bool) {
+        match *ty {
+            Type::Int(_) => out.push('#'),
+            Type::ParameterizedPath(ref ty) => {
+                self.push_parameterized_path(out, &ty.parameterized_path, space_allowed);
+            },
+            Type::TypeParameter(ref ty) => write!(out, "!{}", ty.ident.as_str()).unwrap(),
+            Type::Bare(ref ty) => {
+                out.push('%');
+                self.push_type(out, &ty.inner, space_allowed);
+            },
+            Type::Paren(ref ty) => {
+                out.push('(');
+                self.push_type(out, &ty.inner, true);
+                out.push(')');
+            },
+        }
+    }
+
+    fn push_parameterized_path(&self, out: &mut String, path: &ParameterizedPath, space_allowed: bool) {
+        write!(out, "{}", path.path.display_wrapper()).unwrap();
+
+        let (style, args) = match path.args {
+            None => return,
+            Some(GenericArguments::AngleBracketed(ref args)) => {
+                (GenericArgumentsStyle::AngleBracketed, args.args.iter().collect::<Vec<_>>())
+            },
+            Some(GenericArguments::SpaceSeparated(ref args)) => {
+                (GenericArgumentsStyle::SpaceSeparated, args.args.iter().collect::<Vec<_>>())
+            },
+        };
+
+        match (self.generic_arguments.unwrap_or(style), space_allowed) {
+            (GenericArgumentsStyle::SpaceSeparated, true) => {
+                for arg in args {
+                    out.push(' ');
+                    self.push_space_separated_arg(out, arg);
+                }
+            },
+            _ => {
+                out.push('<');
+                for (i, arg) in args.into_iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    self.push_angle_bracketed_arg(out, arg);
+                }
+                out.push('>');
+            },
+        }
+    }
+
+    fn push_angle_bracketed_arg(&self, out: &mut String, arg: &GenericArgument) {
+        match *arg {
+            GenericArgument::Type(ref ty) => {
+                // Parentheses are redundant between angle brackets
+                match *ty {
+                    Type::Paren(ref ty) if self.generic_arguments.is_some() => {
+                        self.push_type(out, &ty.inner, true);
+                    },
+                    ref ty => self.push_type(out, ty, true),
+                }
+            },
+            GenericArgument::Nat(ref nat_expr) => write!(out, "{}", nat_expr.display_wrapper()).unwrap(),
+        }
+    }
+
+    fn push_space_separated_arg(&self, out: &mut String, arg: &GenericArgument) {
+        match *arg {
+            GenericArgument::Type(Type::ParameterizedPath(ref ty))
+                if ty.parameterized_path.args.is_some() &&
+                    self.generic_arguments == Some(GenericArgumentsStyle::SpaceSeparated) =>
+            {
+                out.push('(');
+                self.push_parameterized_path(out, &ty.parameterized_path, true);
+                out.push(')');
+            },
+            GenericArgument::Type(ref ty) => self.push_type(out, ty, false),
+            GenericArgument::Nat(NatExpr::Add(ref nat_expr)) => {
+                write!(out, "({})", nat_expr.display_wrapper()).unwrap();
+            },
+            GenericArgument::Nat(ref nat_expr) => write!(out, "{}", nat_expr.display_wrapper()).unwrap(),
+        }
+    }
+
+    fn push_id(&self, out: &mut String, id: &Id) {
+        let width = id.format.digit_count() as usize;
+
+        match self.id_case {
+            None => write!(out, "{}", id.display_wrapper()).unwrap(),
+            Some(IdCase::Lowercase) => write!(out, "{:0width$x}", id.id, width = width).unwrap(),
+            Some(IdCase::Uppercase) => write!(out, "{:0width$X}", id.id, width = width).unwrap(),
+        }
+    }
+}
+
+
+fn is_combinator(item: &Item) -> bool {
+    matches!(*item, Item::Combinator(_))
+}
+
+/// Return all segments of `path` but the last one.
+fn namespace<'p>(path: &'p Path) -> Vec<&'p str> {
+    let len = path.segments.len();
+    path.segments.iter().take(len - 1).map(|ident| ident.as_str()).collect()
+}
+
+fn push_idents<'i, I: IntoIterator<Item = &'i str>>(out: &mut String, idents: I) {
+    for (i, ident) in idents.into_iter().enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        out.push_str(ident);
+    }
+}
+
+fn width(s: &str) -> usize {
+    s.chars().count()
+}
+
+fn pad(s: &str, width: usize) -> String {
+    format!("{:width$}", s, width = width)
+}
+
+fn padded_line(prefix: &str, prefix_width: usize, tail: &str) -> String {
+    format!("{} {}", pad(prefix.trim_end(), prefix_width), tail)
+}
+
+fn comment_piece(comment: &Comment) -> Piece {
+    let text = comment.display_wrapper().to_string();
+
+    match *comment {
+        Comment::SingleLine(_) => Piece::LineComment(text),
+        Comment::MultiLine(_) => Piece::Comment(text),
+    }
+}
+
+/// Lay out a combinator with each parameter on its own line.
+fn wrapped_lines(layout: &CombinatorLayout) -> Vec<String> {
+    let mut lines = vec![layout.head.clone()];
+    // Multi-line comments waiting for the next parameter
+    let mut pending = String::new();
+    let mut ends_with_line_comment = false;
+
+    for piece in &layout.pieces {
+        match *piece {
+            Piece::Param(ref text) => {
+                lines.push(format!("{}{}{}", PARAM_INDENT, pending, text));
+                pending.clear();
+                ends_with_line_comment = false;
+            },
+            Piece::Comment(ref text) => {
+                pending.push_str(text);
+                pending.push(' ');
+            },
+            Piece::LineComment(ref text) => {
+                if ends_with_line_comment || !pending.is_empty() {
+                    lines.push(format!("{}{}{}", PARAM_INDENT, pending, text));
+                    pending.clear();
+                } else {
+                    let last = lines.last_mut().unwrap();
+                    last.push(' ');
+                    last.push_str(text);
+                }
+                ends_with_line_comment = true;
+            },
+        }
+    }
+    if !pending.is_empty() {
+        lines.push(format!("{}{}", PARAM_INDENT, pending.trim_end()));
+    }
+    lines.push(format!("{}{}", RESULT_INDENT, layout.tail));
+
+    lines
+}
+
+
+#[cfg(all(test, feature = "parsing"))]
+mod tests {
+    use super::*;
+
+    fn format(printer: &PrettyPrinter, input: &str) -> String {
+        printer.print_file(&::parse_file_str(input).unwrap())
+    }
+
+
+    #[test]
+    fn align() {
+        let input = "\
+            boolFalse#bc799737 = Bool;\n\
+            true#3fedd339 = True;\n\
+            error#c4b9f9bb code:int text:string = Error;\n\
+        ";
+
+        assert_eq!(format(&PrettyPrinter::new(), input), "\
+boolFalse#bc799737                      = Bool;
+true#3fedd339                           = True;
+error#c4b9f9bb     code:int text:string = Error;
+");
+        assert_eq!(format(&PrettyPrinter::new().align_equals(false), input), "\
+boolFalse#bc799737 = Bool;
+true#3fedd339 = True;
+error#c4b9f9bb     code:int text:string = Error;
+");
+        assert_eq!(format(&PrettyPrinter::new().align_ids(false).align_equals(false), input), input);
+    }
+
+    #[test]
+    fn wrap() {
+        let input = "\
+            messages.sendMessage#fa88427a flags:# peer:InputPeer /* text */ message:string = Updates;\n\
+            foo a:int // the first one\n b:int = Foo;\n\
+            pair {X:Type} // the first one\n {Y:Type} = // always\n Pair X Y;\n\
+        ";
+
+        assert_eq!(format(&PrettyPrinter::new().max_width(Some(60)), input), "\
+messages.sendMessage#fa88427a
+    flags:#
+    peer:InputPeer
+    /* text */ message:string
+  = Updates;
+
+foo
+    a:int // the first one
+    b:int
+  = Foo;
+pair
+    {X:Type} // the first one
+    {Y:Type}
+  = // always
+    Pair X Y;
+");
+    }
+
+    #[test]
+    fn wrap_padded() {
+        let input = "\
+            boolFalse#bc799737 = BoolWithALongName;\n\
+            error#c4b9f9bb code:int text:string = E;\n\
+        ";
+
+        // `boolFalse` fits on its own, but not when aligned with `error`
+        assert_eq!(format(&PrettyPrinter::new().max_width(Some(45)), input), "\
+boolFalse#bc799737
+  = BoolWithALongName;
+error#c4b9f9bb     code:int text:string = E;
+");
+    }
+
+    #[test]
+    fn generic_arguments() {
+        let input = "foo {t:Type} v:Vector<Vector<t>> w:(Vector (Vector t)) n:# x:Tuple<t, n+1> = Foo (Vector t) t;";
+
+        let angle_bracketed = PrettyPrinter::new().generic_arguments(Some(GenericArgumentsStyle::AngleBracketed));
+        assert_eq!(
+            format(&angle_bracketed, input),
+            "foo {t:Type} v:Vector<Vector<t>> w:(Vector<Vector<t>>) n:# x:Tuple<t, n+1> = Foo<Vector<t>, t>;\n",
+        );
+
+        let space_separated = PrettyPrinter::new().generic_arguments(Some(GenericArgumentsStyle::SpaceSeparated));
+        let output = format(&space_separated, input);
+        assert_eq!(
+            output,
+            "foo {t:Type} v:Vector<Vector t> w:(Vector (Vector t)) n:# x:Tuple<t, n+1> = Foo (Vector t) t;\n",
+        );
+        assert!(::parse_file_str(&output).is_ok());
+    }
+
+    #[test]
+    fn id_case() {
+        let input = "foo#0000BEEF = Foo;\nint#a8509bda ? = Int;\n";
+
+        assert_eq!(format(&PrettyPrinter::new(), input), "foo#0000beef = Foo;\nint#a8509bda ? = Int;\n");
+        assert_eq!(
+            format(&PrettyPrinter::new().id_case(Some(IdCase::Uppercase)), input),
+            "foo#0000BEEF = Foo;\nint#A8509BDA ? = Int;\n",
+        );
+    }
+
+    #[test]
+    fn namespace_blank_lines() {
+        let input = "\
+            auth.sendCode = auth.SentCode;\n\
+            auth.signIn = auth.Authorization;\n\
+            account.getPassword = account.Password;\n\
+            ---functions---\n\
+            ping = Pong;\n\
+        ";
+
+        assert_eq!(format(&PrettyPrinter::new(), input), "\
+auth.sendCode = auth.SentCode;
+auth.signIn   = auth.Authorization;
+
+account.getPassword = account.Password;
+
+---functions---
+
+ping = Pong;
+");
+    }
+}
@@ -8,7 +8,7 @@ pub(crate) mod private {
     /// `Print` trait.
     pub trait Sealed {}
 
-    impl<'a, T: Sealed + ?Sized> Sealed for &'a T {}
+    impl<T: Sealed + ?Sized> Sealed for &T {}
     impl<T: Sealed + ?Sized> Sealed for Box<T> {}
     impl<T: Sealed> Sealed for Option<T> {}
 }
@@ -38,7 +38,7 @@ impl<'a, T: Print + ?Sized + 'a> fmt::Display for DisplayWrapper<'a, T> {
 }
 
 
-impl<'a, T: Print> Print for &'a T {
+impl<T: Print> Print for &T {
     fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
         (**self).print(f)
     }
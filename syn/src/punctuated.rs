@@ -10,7 +10,7 @@ use std::vec;
 macro_attr! {
     /// A punctuated sequence of syntax tree nodes of type `T` separated by
     /// punctuation of type `P`.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
     pub struct Punctuated<T, P> {
         inner: Vec<(T, P)>,
         last: Option<Box<T>>,
@@ -57,7 +57,7 @@ impl<T, P> Punctuated<T, P> {
         } else {
             self.inner
                 .last()
-                .map(|&(ref t, ref p)| Pair::Punctuated(t, p))
+                .map(|(t, p)| Pair::Punctuated(t, p))
         }
     }
 
@@ -73,7 +73,7 @@ impl<T, P> Punctuated<T, P> {
     }
 
     /// Return an iterator over borrowed syntax tree nodes of type `&T`.
-    pub fn iter(&self) -> Iter<T, P> {
+    pub fn iter(&self) -> Iter<'_, T, P> {
         Iter {
             inner: self.inner.iter(),
             last: self.last.as_ref().map(|t| t.as_ref()).into_iter(),
@@ -82,7 +82,7 @@ impl<T, P> Punctuated<T, P> {
 
     /// Return an iterator over mutably borrowed syntax tree nodes of type
     /// `&mut T`.
-    pub fn iter_mut(&mut self) -> IterMut<T, P> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, P> {
         IterMut {
             inner: self.inner.iter_mut(),
             last: self.last.as_mut().map(|t| t.as_mut()).into_iter(),
@@ -91,7 +91,7 @@ impl<T, P> Punctuated<T, P> {
 
     /// Return an iterator over the contents of this sequence as borrowed
     /// punctuated pairs.
-    pub fn pairs(&self) -> Pairs<T, P> {
+    pub fn pairs(&self) -> Pairs<'_, T, P> {
         Pairs {
             inner: self.inner.iter(),
             last: self.last.as_ref().map(|t| t.as_ref()).into_iter(),
@@ -100,7 +100,7 @@ impl<T, P> Punctuated<T, P> {
 
     /// Return an iterator over the contents of this sequence as mutably
     /// borrowed punctuated pairs.
-    pub fn pairs_mut(&mut self) -> PairsMut<T, P> {
+    pub fn pairs_mut(&mut self) -> PairsMut<'_, T, P> {
         PairsMut {
             inner: self.inner.iter_mut(),
             last: self.last.as_mut().map(|t| t.as_mut()).into_iter(),
@@ -223,6 +223,12 @@ where
     }
 }
 
+impl<T, P> Default for Punctuated<T, P> {
+    fn default() -> Punctuated<T, P> {
+        Punctuated::new()
+    }
+}
+
 impl<T, P> ops::Index<usize> for Punctuated<T, P> {
     type Output = T;
 
@@ -323,13 +329,39 @@ mod spanned {
         fn span(&self) -> Span {
             let spans_iter = self.inner
                 .iter()
-                .map(|&(ref t, ref p)| t.span().to(p.span()));
+                .map(|(t, p)| t.span().to(p.span()));
 
             Span::union(spans_iter).to(self.last.span())
         }
     }
 }
 
+mod owned {
+    use super::*;
+    use owned::IntoOwned;
+    use owned::private::Sealed;
+
+    impl<T, P> Sealed for Punctuated<T, P>
+    where
+        T: Sealed,
+    {}
+
+    impl<T, P> IntoOwned for Punctuated<T, P>
+    where
+        T: IntoOwned,
+        P: 'static,
+    {
+        type Owned = Punctuated<T::Owned, P>;
+
+        fn into_owned(self) -> Punctuated<T::Owned, P> {
+            Punctuated {
+                inner: self.inner.into_iter().map(|(t, p)| (t.into_owned(), p)).collect(),
+                last: self.last.into_owned(),
+            }
+        }
+    }
+}
+
 #[cfg(feature = "parsing")]
 mod parsing {
     use nom;
@@ -338,10 +370,10 @@ mod parsing {
     use cursor::Cursor;
     use synom::Synom;
 
-    impl<T, P> Punctuated<T, P>
+    impl<'a, T, P> Punctuated<T, P>
     where
-        T: Synom,
-        P: Synom,
+        T: Synom<'a>,
+        P: Synom<'a>,
     {
         /// Parse syntax tree nodes with punctuation in between using 3
         /// configuration options:
@@ -351,7 +383,7 @@ mod parsing {
         /// - `Count` — how many syntax tree nodes `parse` is allowed to
         ///   consume.
         /// - `Whitespace` — whether `parse` should ignore whitespace.
-        pub fn parse<'a>(
+        pub fn parse(
             input: Cursor<'a>,
             trailing_punct: TrailingPunctuation,
             count: Count,
@@ -361,20 +393,25 @@ mod parsing {
         }
     }
 
-    impl<T, P> Punctuated<T, P>
+    impl<'a, T, P> Punctuated<T, P>
     where
-        P: Synom,
+        P: Synom<'a>,
     {
-        fn parse_with<'a>(
+        fn parse_with(
             mut input: Cursor<'a>,
-            parse: fn(Cursor) -> nom::IResult<Cursor, T>,
+            parse: fn(Cursor<'a>) -> nom::IResult<Cursor<'a>, T>,
             trailing_punct: TrailingPunctuation,
             count: Count,
             whitespace: Whitespace,
         ) -> nom::IResult<Cursor<'a>, Self> {
             let actual_parse = |s| match whitespace {
                 Whitespace::None => parse(s),
-                Whitespace::Present => with_afterspace!(s, parse),
+                // Whitespace, including line breaks, may surround every
+                // element, e.g. `Tuple<t, n+1>`
+                Whitespace::Present => {
+                    let (s, _) = nom::multispace0(s)?;
+                    with_afterspace!(s, parse)
+                },
             };
 
             let mut res = Punctuated::new();
@@ -475,7 +512,7 @@ mod printing {
                 Whitespace::Present => " ",
             };
 
-            for &(ref t, ref p) in &self.inner {
+            for (t, p) in &self.inner {
                 print(t, f)?;
                 f.write_str(maybe_space)?;
                 p.print(f)?;
@@ -638,7 +675,7 @@ impl<'a, T, P> Iterator for Pairs<'a, T, P> {
     fn next(&mut self) -> Option<Self::Item> {
         self.inner
             .next()
-            .map(|&(ref t, ref p)| Pair::Punctuated(t, p))
+            .map(|(t, p)| Pair::Punctuated(t, p))
             .or_else(|| self.last.next().map(Pair::End))
     }
 }
@@ -778,7 +815,7 @@ mod tests {
     fn punctuated_no_trailing_1_elem_properties() {
         // A workaround to test methods that take `self` by value even when
         // "clone-impls" feature is not enabled.
-        let new_p = || vec![i64::min_value()].into_iter().collect();
+        let new_p = || vec![i64::MIN].into_iter().collect();
         let mut p: Punctuated<i64, PhantomData<String>> = new_p();
 
         assert_eq!(p.len(), 1);
@@ -0,0 +1,138 @@
+use std::fmt;
+
+use super::{Delimiter, Item, ItemCombinator, ItemDelimiter};
+use span::Span;
+use spanned::Spanned;
+
+
+/// The kind of declarations in a section of a TL file.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SectionKind {
+    /// Constructors of types, which go after `---types---` and at the
+    /// beginning of a file.
+    Types,
+    /// Functions, which go after `---functions---`.
+    Functions,
+}
+
+impl SectionKind {
+    /// Return the kind of section started by `delimiter`.
+    pub fn from_delimiter(delimiter: &Delimiter) -> SectionKind {
+        match *delimiter {
+            Delimiter::Types(_) => SectionKind::Types,
+            Delimiter::Functions(_) => SectionKind::Functions,
+        }
+    }
+}
+
+/// A run of items between two section delimiters.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "debug-impls", derive(Debug))]
+pub struct Section<'a> {
+    pub kind: SectionKind,
+    /// The delimiter that starts the section. It is `None` for the items
+    /// before the first delimiter of a file.
+    pub delimiter: Option<&'a ItemDelimiter>,
+    /// The items of the section, excluding the delimiter itself.
+    pub items: &'a [Item<'a>],
+}
+
+/// Items of a file grouped into sections.
+#[derive(Clone)]
+#[cfg_attr(feature = "debug-impls", derive(Debug))]
+pub struct Sections<'a> {
+    pub sections: Vec<Section<'a>>,
+    pub diagnostics: Vec<SectionDiagnostic>,
+}
+
+/// A delimiter which starts a section of the same kind as the current one,
+/// e.g. `---types---` followed by `---types---`.
+///
+/// A redundant delimiter doesn't prevent grouping items into sections, so it
+/// is reported instead of being treated as an error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SectionDiagnostic {
+    /// The span of the redundant delimiter.
+    pub span: Span,
+    /// The span of the delimiter which started the current section.
+    pub previous_span: Span,
+    pub kind: SectionKind,
+}
+
+
+impl<'a> Section<'a> {
+    /// Return an iterator over the combinators of this section.
+    pub fn combinators(&self) -> impl Iterator<Item = &'a ItemCombinator<'a>> {
+        self.items.iter().filter_map(|item| match *item {
+            Item::Combinator(ref combinator) => Some(combinator),
+            _ => None,
+        })
+    }
+}
+
+impl<'a> Sections<'a> {
+    /// Group `items` into sections.
+    ///
+    /// Items before the first delimiter form a types section, unless there
+    /// are none of them.
+    pub fn new(items: &'a [Item<'a>]) -> Sections<'a> {
+        let mut sections = Vec::new();
+        let mut diagnostics = Vec::new();
+
+        let mut kind = SectionKind::Types;
+        let mut delimiter: Option<&'a ItemDelimiter> = None;
+        let mut start = 0;
+
+        for (i, item) in items.iter().enumerate() {
+            let item_delimiter = match *item {
+                Item::Delimiter(ref item_delimiter) => item_delimiter,
+                _ => continue,
+            };
+
+            if delimiter.is_some() || start < i {
+                sections.push(Section { kind, delimiter, items: &items[start..i] });
+            }
+
+            let new_kind = SectionKind::from_delimiter(&item_delimiter.delimiter);
+
+            if let Some(previous_delimiter) = delimiter {
+                if new_kind == kind {
+                    diagnostics.push(SectionDiagnostic {
+                        span: item_delimiter.span(),
+                        previous_span: previous_delimiter.span(),
+                        kind,
+                    });
+                }
+            }
+
+            kind = new_kind;
+            delimiter = Some(item_delimiter);
+            start = i + 1;
+        }
+
+        if delimiter.is_some() || start < items.len() {
+            sections.push(Section { kind, delimiter, items: &items[start..] });
+        }
+
+        Sections { sections, diagnostics }
+    }
+
+    /// Return an iterator over the combinators of all sections of the given
+    /// `kind`.
+    pub fn combinators<'b>(&'b self, kind: SectionKind) -> impl Iterator<Item = &'a ItemCombinator<'a>> + 'b {
+        self.sections.iter()
+            .filter(move |section| section.kind == kind)
+            .flat_map(Section::combinators)
+    }
+}
+
+impl fmt::Display for SectionDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            SectionKind::Types => f.write_str("redundant `---types---` delimiter in a types section"),
+            SectionKind::Functions => {
+                f.write_str("redundant `---functions---` delimiter in a functions section")
+            },
+        }
+    }
+}
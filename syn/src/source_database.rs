@@ -0,0 +1,254 @@
+use std::fs;
+use std::io;
+use std::path::Path as FsPath;
+
+use diagnostic::{ColorChoice, Diagnostic};
+use line_index::LineIndex;
+use span::Span;
+
+
+/// An identifier of a file in a [`SourceDatabase`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct FileId(u32);
+
+/// A [`Span`] together with the file it points into.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FileSpan {
+    pub file: FileId,
+    pub span: Span,
+}
+
+/// A collection of named source texts.
+#[derive(Clone, Debug, Default)]
+pub struct SourceDatabase {
+    files: Vec<SourceFile>,
+}
+
+#[derive(Clone, Debug)]
+struct SourceFile {
+    name: String,
+    source: String,
+}
+
+
+impl FileId {
+    /// Get the 0-based index of the file in the order files were added to
+    /// their `SourceDatabase`.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl FileSpan {
+    /// Create a new `FileSpan`.
+    pub fn new(file: FileId, span: Span) -> FileSpan {
+        FileSpan { file, span }
+    }
+}
+
+impl SourceDatabase {
+    /// Create an empty `SourceDatabase`.
+    pub fn new() -> SourceDatabase {
+        SourceDatabase { files: Vec::new() }
+    }
+
+    /// Add a source text named `name` and return its `FileId`.
+    ///
+    /// A leading byte order mark is dropped, as parsing skips it and spans
+    /// point past it.
+    pub fn add<N, S>(&mut self, name: N, source: S) -> FileId
+    where
+        N: Into<String>,
+        S: Into<String>,
+    {
+        const BOM: char = '\u{feff}';
+
+        assert!(self.files.len() < u32::MAX as usize);
+        let file_id = FileId(self.files.len() as u32);
+
+        let mut source = source.into();
+        if source.starts_with(BOM) {
+            source.drain(..BOM.len_utf8());
+        }
+
+        self.files.push(SourceFile { name: name.into(), source });
+
+        file_id
+    }
+
+    /// Load the content of the file at `path` and add it under the name of
+    /// the path.
+    pub fn add_file<P: AsRef<FsPath>>(&mut self, path: P) -> io::Result<FileId> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)?;
+
+        Ok(self.add(path.display().to_string(), source))
+    }
+
+    /// Return an iterator over the ids of all files in the order they were
+    /// added.
+    pub fn files(&self) -> impl Iterator<Item = FileId> {
+        (0..self.files.len() as u32).map(FileId)
+    }
+
+    /// Get the name of the file with the given `file_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `file_id` was issued by another `SourceDatabase`.
+    pub fn name(&self, file_id: FileId) -> &str {
+        &self.files[file_id.index()].name
+    }
+
+    /// Get the source text of the file with the given `file_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `file_id` was issued by another `SourceDatabase`.
+    pub fn source(&self, file_id: FileId) -> &str {
+        &self.files[file_id.index()].source
+    }
+
+    /// Create a `LineIndex` for the file with the given `file_id`.
+    pub fn line_index(&self, file_id: FileId) -> LineIndex<'_> {
+        LineIndex::new(self.source(file_id))
+    }
+
+    /// Return the source text `file_span` points at, or `None` if the span is
+    /// zeroed or doesn't fit in the file.
+    pub fn slice(&self, file_span: FileSpan) -> Option<&str> {
+        let span = file_span.span;
+        if span.begin() == 0 {
+            return None;
+        }
+
+        self.source(file_span.file).get(span.begin() - 1..span.end() - 1)
+    }
+
+    /// Render `diagnostic` which refers to the file with the given `file_id`.
+    ///
+    /// Labels of the diagnostic which point into other files of this database
+    /// are rendered under the names of their own files.
+    pub fn render(&self, diagnostic: &Diagnostic, file_id: FileId, color: ColorChoice) -> String {
+        let mut file_ids = vec![file_id];
+        for label_file_id in diagnostic.labels.iter().filter_map(|label| label.file) {
+            if !file_ids.contains(&label_file_id) {
+                file_ids.push(label_file_id);
+            }
+        }
+
+        let line_indices = file_ids.iter()
+            .map(|&file_id| self.line_index(file_id))
+            .collect::<Vec<_>>();
+        let sources = file_ids.iter().zip(&line_indices)
+            .map(|(&file_id, line_index)| (Some(file_id), self.name(file_id), line_index))
+            .collect::<Vec<_>>();
+
+        diagnostic.render_sources(&sources, color)
+    }
+}
+
+#[cfg(feature = "parsing")]
+mod parsing {
+    use super::*;
+    use error::{ParseError, ParseResult};
+    use file::File;
+    use id::IdValidation;
+
+    impl SourceDatabase {
+        /// Parse the file with the given `file_id`.
+        pub fn parse(&self, file_id: FileId, id_validation: IdValidation) -> ParseResult<File<'_>> {
+            ::parse_file_str_with(self.source(file_id), id_validation)
+        }
+
+        /// Parse the file with the given `file_id`, skipping items which fail
+        /// to parse.
+        pub fn parse_recovering(&self, file_id: FileId, id_validation: IdValidation) -> (File<'_>, Vec<ParseError>) {
+            ::parse_file_str_recovering_with(self.source(file_id), id_validation)
+        }
+
+        /// Parse all files in the order they were added.
+        pub fn parse_all(&self, id_validation: IdValidation) -> Vec<(FileId, ParseResult<File<'_>>)> {
+            self.files()
+                .map(|file_id| (file_id, self.parse(file_id, id_validation)))
+                .collect()
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diagnostic::Label;
+
+    #[test]
+    fn add_and_get() {
+        let mut db = SourceDatabase::new();
+        let mtproto = db.add("mtproto.tl", "int ? = Int;\n");
+        let api = db.add("api.tl", "boolTrue#997275b5 = Bool;\n");
+
+        assert_eq!(db.files().collect::<Vec<_>>(), [mtproto, api]);
+        assert_eq!(db.name(api), "api.tl");
+        assert_eq!(db.source(mtproto), "int ? = Int;\n");
+        assert_eq!(db.slice(FileSpan::new(api, Span::new(1, 9))), Some("boolTrue"));
+        assert_eq!(db.slice(FileSpan::new(api, Span::zeroed())), None);
+        assert_eq!(db.slice(FileSpan::new(mtproto, Span::new(1, 100))), None);
+    }
+
+    #[test]
+    fn render_across_files() {
+        let mut db = SourceDatabase::new();
+        let api = db.add("api.tl", "boolTrue#997275b5 = Bool;\n");
+        let ext = db.add("ext.tl", "\nboolTrue#997275b5 = Bool;\n");
+
+        let diagnostic = Diagnostic::error("duplicate combinator `boolTrue`")
+            .with_label(Label::primary(Span::new(2, 10), "defined again here"))
+            .with_label(Label::secondary(Span::new(1, 9), "first defined here").with_file(api));
+
+        assert_eq!(db.render(&diagnostic, ext, ColorChoice::Plain), "\
+error: duplicate combinator `boolTrue`
+ --> ext.tl:2:1
+  |
+2 | boolTrue#997275b5 = Bool;
+  | ^^^^^^^^ defined again here
+  |
+ ::: api.tl:1:1
+  |
+1 | boolTrue#997275b5 = Bool;
+  | -------- first defined here
+");
+    }
+
+    #[cfg(feature = "parsing")]
+    #[test]
+    fn byte_order_mark() {
+        use id::IdValidation;
+        use line_index::LineColumn;
+
+        let mut db = SourceDatabase::new();
+        let file_id = db.add("api.tl", "\u{feff}boolTrue#997275b5 = Bool;\n\nfoo#zz = Foo;\n");
+        let (_, errors) = db.parse_recovering(file_id, IdValidation::Lenient);
+        let span = errors[0].span();
+
+        assert_eq!(db.source(file_id), "boolTrue#997275b5 = Bool;\n\nfoo#zz = Foo;\n");
+        assert_eq!(db.slice(FileSpan::new(file_id, span)), Some("zz"));
+        assert_eq!(db.line_index(file_id).line_column(span.begin()), Some(LineColumn { line: 3, column: 5 }));
+        assert!(db.render(&Diagnostic::from(&errors[0]), file_id, ColorChoice::Plain).contains(" --> api.tl:3:5\n"));
+    }
+
+    #[cfg(feature = "parsing")]
+    #[test]
+    fn parse_all() {
+        use id::IdValidation;
+
+        let mut db = SourceDatabase::new();
+        db.add("a.tl", "boolTrue#997275b5 = Bool;\n");
+        db.add("b.tl", "foo#zz = Foo;\n");
+
+        let results = db.parse_all(IdValidation::Lenient);
+
+        assert!(results[0].1.is_ok());
+        assert!(results[1].1.is_err());
+    }
+}
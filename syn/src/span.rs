@@ -172,6 +172,34 @@ pub enum SpanState {
 }
 
 
+#[cfg(feature = "serde-impls")]
+mod serde_impls {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(rename = "Span")]
+    struct SpanRepr {
+        begin: usize,
+        end: usize,
+    }
+
+    impl Serialize for Span {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            SpanRepr { begin: self.begin, end: self.end }.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Span {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Span, D::Error> {
+            // Go through `Span::new` so that the result is in a valid state
+            SpanRepr::deserialize(deserializer).map(|repr| Span::new(repr.begin, repr.end))
+        }
+    }
+}
+
+
 #[cfg(test)]
 mod tests {
     use std::mem;
@@ -188,6 +216,15 @@ mod tests {
         assert_eq!(Span::zeroed(), Span { begin: 0, end: 0 });
     }
 
+    #[cfg(feature = "serde-impls")]
+    #[test]
+    fn serde() {
+        let span: Span = ::serde_json::from_str(r#"{"begin":7,"end":3}"#).unwrap();
+
+        assert_eq!(span, Span::new(3, 7));
+        assert_eq!(::serde_json::to_string(&span).unwrap(), r#"{"begin":3,"end":7}"#);
+    }
+
     mod new {
         use super::super::Span;
 
@@ -203,7 +240,7 @@ mod tests {
 
         #[test]
         fn nonzero_zero() {
-            assert_eq!(Span::new(usize::max_value(), 0), Span { begin: 0, end: 0 });
+            assert_eq!(Span::new(usize::MAX, 0), Span { begin: 0, end: 0 });
         }
 
         #[test]
@@ -9,10 +9,10 @@ pub(crate) mod private {
     /// `Print` trait.
     pub trait Sealed {}
 
-    impl<'a, T: Sealed + ?Sized> Sealed for &'a T {}
+    impl<T: Sealed + ?Sized> Sealed for &T {}
     impl<T: Sealed + ?Sized> Sealed for Box<T> {}
     impl<T: Sealed> Sealed for Option<T> {}
-    impl<'a, T: Sealed> Sealed for &'a [T] {}
+    impl<T: Sealed> Sealed for &[T] {}
     impl<T: Sealed> Sealed for Vec<T> {}
 }
 
@@ -30,7 +30,7 @@ pub trait Spanned: private::Sealed {
 }
 
 
-impl<'a, T: Spanned + ?Sized> Spanned for &'a T {
+impl<T: Spanned + ?Sized> Spanned for &T {
     /// Return a `Span` of the value behind this reference.
     fn span(&self) -> Span {
         (**self).span()
@@ -55,7 +55,7 @@ impl<T: Spanned> Spanned for Option<T> {
     }
 }
 
-impl<'a, T: Spanned> Spanned for &'a [T] {
+impl<T: Spanned> Spanned for &[T] {
     /// Return a [`Span::union()`] of spans in this slice.
     fn span(&self) -> Span {
         Span::union(self.iter().map(Spanned::span))
@@ -0,0 +1,325 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+use std::mem;
+
+use nom::InputTake;
+
+use super::Item;
+use cursor::Cursor;
+use error::ParseError;
+use id::IdValidation;
+use item::ItemError;
+use owned::IntoOwned;
+use synom::Synom;
+
+
+/// An iterator over items of TL language schema read from a `BufRead`.
+///
+/// Input is consumed in blocks of lines which end either with a blank line or
+/// with a line ending in `;`, so that usually only the lines of a single item
+/// are held in memory. Spans of yielded items point into the whole input as if
+/// it was parsed at once. Since blocks are dropped once parsed, yielded items
+/// own all of their data.
+///
+/// An item which spans several blocks is parsed once all of them are read.
+/// An item which fails to parse is skipped once reading the next block doesn't
+/// change the error, in the same way as `parse_file_str_recovering` does: its
+/// text is yielded as an `Item::Error` and the error is kept until it is taken
+/// with `take_errors`. Iteration stops at the first I/O error.
+pub struct ItemReader<R> {
+    reader: R,
+    id_validation: IdValidation,
+    /// Unparsed input which starts at `offset` of the whole input.
+    buffer: String,
+    offset: usize,
+    items: VecDeque<Item<'static>>,
+    last_error: Option<ParseError>,
+    errors: Vec<ParseError>,
+    state: State,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum State {
+    Reading,
+    ReachedEof,
+    Finished,
+}
+
+impl<R: BufRead> ItemReader<R> {
+    /// Create a new `ItemReader` which validates combinator ids as specified
+    /// by `id_validation`.
+    pub fn new(reader: R, id_validation: IdValidation) -> ItemReader<R> {
+        ItemReader {
+            reader,
+            id_validation,
+            buffer: String::new(),
+            offset: 1,
+            items: VecDeque::new(),
+            last_error: None,
+            errors: Vec::new(),
+            state: State::Reading,
+        }
+    }
+
+    /// Take the errors of the items which were yielded as `Item::Error` so
+    /// far.
+    pub fn take_errors(&mut self) -> Vec<ParseError> {
+        mem::take(&mut self.errors)
+    }
+
+    /// Unwrap this `ItemReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Append the next block of lines to the buffer, including the line which
+    /// ends it.
+    fn read_block(&mut self) -> io::Result<()> {
+        let mut has_content = false;
+
+        loop {
+            let len = self.buffer.len();
+
+            if self.reader.read_line(&mut self.buffer)? == 0 {
+                self.state = State::ReachedEof;
+                break;
+            }
+
+            // Strip the BOM if it is present. Spans don't count it, just like
+            // with `parse_file_str`.
+            if self.offset == 1 && len == 0 && self.buffer.starts_with('\u{feff}') {
+                self.buffer.drain(..'\u{feff}'.len_utf8());
+            }
+
+            let line = self.buffer[len..].trim();
+
+            if line.is_empty() {
+                if has_content {
+                    break;
+                }
+            } else if ends_with_semicolon(line) {
+                break;
+            } else {
+                has_content = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse as many items of the buffer as possible and return the error of
+    /// the item which failed to parse, if any.
+    fn parse_buffer(&mut self) -> Option<ParseError> {
+        let (consumed, error) = {
+            let mut input = Cursor::with_offset(&self.buffer, self.offset, self.id_validation);
+            let mut error = None;
+
+            loop {
+                let s = input.to_str();
+                input = input.take_split(s.len() - s.trim_start().len()).0;
+
+                if input.to_str().is_empty() {
+                    break;
+                }
+
+                match Item::parse_cursor(input) {
+                    Ok((rest, item)) => {
+                        self.items.push_back(item.into_owned());
+                        input = rest;
+                    },
+                    Err(e) => {
+                        error = Some(ParseError::from_nom_err_cursor(e)
+                            .unwrap_or_else(|| ParseError::unexpected_end_of_input(input)));
+                        break;
+                    },
+                }
+            }
+
+            (input.offset() - self.offset, error)
+        };
+
+        self.consume(consumed);
+
+        error
+    }
+
+    /// Skip the item at the start of the buffer which failed with `error`.
+    fn skip_failed_item(&mut self, error: ParseError) {
+        let consumed = {
+            let input = Cursor::with_offset(&self.buffer, self.offset, self.id_validation);
+            let (rest, item_error) = ItemError::skip_cursor(input);
+
+            self.items.push_back(Item::Error(item_error).into_owned());
+            rest.offset() - self.offset
+        };
+
+        self.consume(consumed);
+        self.errors.push(error);
+    }
+
+    fn consume(&mut self, len: usize) {
+        self.buffer.drain(..len);
+        self.offset += len;
+    }
+}
+
+impl<R: BufRead> Iterator for ItemReader<R> {
+    type Item = io::Result<Item<'static>>;
+
+    fn next(&mut self) -> Option<io::Result<Item<'static>>> {
+        loop {
+            if let Some(item) = self.items.pop_front() {
+                return Some(Ok(item));
+            }
+
+            match self.state {
+                State::Reading => {
+                    if let Err(e) = self.read_block() {
+                        self.state = State::Finished;
+                        return Some(Err(e));
+                    }
+                },
+                State::ReachedEof => (),
+                State::Finished => return None,
+            }
+
+            match self.parse_buffer() {
+                None => {
+                    self.last_error = None;
+
+                    if self.state == State::ReachedEof {
+                        self.state = State::Finished;
+                    }
+                },
+                Some(error) => {
+                    // More input may complete the item, unless there is no
+                    // more input or it didn't help already
+                    if self.state == State::ReachedEof || self.last_error.as_ref() == Some(&error) {
+                        self.last_error = None;
+                        self.skip_failed_item(error);
+                    } else {
+                        self.last_error = Some(error);
+                    }
+                },
+            }
+        }
+    }
+}
+
+// Whether `line` ends with `;`, ignoring a single-line comment after it.
+fn ends_with_semicolon(line: &str) -> bool {
+    let code = match line.find("//") {
+        Some(i) => &line[..i],
+        None => line,
+    };
+
+    code.trim_end().ends_with(';')
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor as IoCursor;
+
+    use super::*;
+    use spanned::Spanned;
+
+    fn read_items(input: &str) -> Vec<io::Result<Item<'static>>> {
+        ItemReader::new(IoCursor::new(input.as_bytes()), IdValidation::Lenient).collect()
+    }
+
+    #[test]
+    fn items() {
+        let input = "\
+            // LAYER 78\n\
+            \n\
+            //@description A boolean\n\
+            boolFalse#bc799737 = Bool;\n\
+            boolTrue#997275b5 = Bool;\n\
+            \n\
+            foo a:int\n\
+            \n\
+            \x20   b:int = Foo;\n\
+            \n\
+            ---functions---\n\
+        ";
+        let items = read_items(input).into_iter().map(Result::unwrap).collect::<Vec<_>>();
+
+        assert_eq!(items.len(), 5);
+        match items[1] {
+            Item::Combinator(ref combinator) => assert_eq!(combinator.leading_comments.len(), 1),
+            _ => panic!("expected a combinator"),
+        }
+        match items[3] {
+            Item::Combinator(ref combinator) => assert_eq!(combinator.params.len(), 2),
+            _ => panic!("expected a combinator"),
+        }
+
+        let span = items[2].span();
+        assert_eq!(&input[span.begin() - 1..span.end() - 1], "boolTrue#997275b5 = Bool;");
+        let span = items[4].span();
+        assert_eq!(&input[span.begin() - 1..span.end() - 1], "---functions---");
+    }
+
+    fn item_texts(input: &str, items: &[Item]) -> Vec<String> {
+        items.iter()
+            .map(|item| {
+                let span = item.span();
+                input[span.begin() - 1..span.end() - 1].to_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn recovers_from_errors() {
+        let input = "a = A;\n\nfoo#zz = Foo;\nb = ;\n\nc = C;";
+        let mut reader = ItemReader::new(IoCursor::new(input.as_bytes()), IdValidation::Lenient);
+        let items = reader.by_ref().map(Result::unwrap).collect::<Vec<_>>();
+
+        assert_eq!(item_texts(input, &items), ["a = A;", "foo#zz = Foo;", "b = ;", "c = C;"]);
+        match (&items[1], &items[2]) {
+            (&Item::Error(_), &Item::Error(_)) => (),
+            _ => panic!("expected errors"),
+        }
+
+        let errors = reader.take_errors();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].slice_into(input), "zz");
+        assert!(reader.take_errors().is_empty());
+    }
+
+    #[test]
+    fn error_at_eof() {
+        let input = "boolTrue#997275b5 = Bool;\nfoo a:int";
+        let mut reader = ItemReader::new(IoCursor::new(input.as_bytes()), IdValidation::Lenient);
+        let items = reader.by_ref().map(Result::unwrap).collect::<Vec<_>>();
+
+        assert_eq!(item_texts(input, &items), ["boolTrue#997275b5 = Bool;", "foo a:int"]);
+        assert_eq!(reader.take_errors().len(), 1);
+    }
+
+    #[test]
+    fn bounded_blocks() {
+        let input = (0..1000).map(|i| format!("foo{} a:int = Foo; // {}\n", i, i)).collect::<String>();
+        let mut reader = ItemReader::new(IoCursor::new(input.as_bytes()), IdValidation::Lenient);
+
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.items.is_empty());
+        assert!(reader.buffer.len() < 100);
+        assert_eq!(reader.count(), 999);
+    }
+
+    #[test]
+    fn bom() {
+        let input = "\u{feff}a = A;\nb = B;\n";
+        let streamed = read_items(input).into_iter()
+            .map(|item| item.unwrap().span())
+            .collect::<Vec<_>>();
+        let parsed = ::parse_file_str(input).unwrap().items.iter()
+            .map(Spanned::span)
+            .collect::<Vec<_>>();
+
+        assert_eq!(streamed, parsed);
+        assert_eq!(streamed[0], ::span::Span::new(1, 7));
+    }
+}
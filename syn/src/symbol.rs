@@ -0,0 +1,124 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{OnceLock, RwLock};
+
+
+/// An interned string.
+///
+/// Two symbols are equal if and only if their strings are equal.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Symbol(u32);
+
+#[derive(Default)]
+struct Interner {
+    symbols: HashMap<&'static str, Symbol>,
+    strings: Vec<&'static str>,
+}
+
+thread_local! {
+    static CACHE: RefCell<HashMap<&'static str, Symbol>> = RefCell::new(HashMap::new());
+}
+
+
+impl Symbol {
+    /// Return the symbol of `string`, interning it if it's seen for the first
+    /// time. Interned strings are never freed.
+    pub fn intern(string: &str) -> Symbol {
+        CACHE.with(|cache| {
+            if let Some(&symbol) = cache.borrow().get(string) {
+                return symbol;
+            }
+
+            let (string, symbol) = Interner::intern(string);
+            cache.borrow_mut().insert(string, symbol);
+
+            symbol
+        })
+    }
+
+    /// Get the string this symbol stands for.
+    pub fn as_str(self) -> &'static str {
+        interner()
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .strings[self.0 as usize]
+    }
+}
+
+impl Interner {
+    fn intern(string: &str) -> (&'static str, Symbol) {
+        if let Some((&string, &symbol)) = interner()
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .symbols
+            .get_key_value(string)
+        {
+            return (string, symbol);
+        }
+
+        let mut interner = interner().write().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        // Another thread may have interned the string in the meantime
+        if let Some((&string, &symbol)) = interner.symbols.get_key_value(string) {
+            return (string, symbol);
+        }
+
+        assert!(interner.strings.len() < u32::MAX as usize);
+        let symbol = Symbol(interner.strings.len() as u32);
+        let string: &'static str = Box::leak(string.to_owned().into_boxed_str());
+
+        interner.symbols.insert(string, symbol);
+        interner.strings.push(string);
+
+        (string, symbol)
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Symbol").field(&self.as_str()).finish()
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+fn interner() -> &'static RwLock<Interner> {
+    static INTERNER: OnceLock<RwLock<Interner>> = OnceLock::new();
+
+    INTERNER.get_or_init(Default::default)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intern() {
+        let flags = Symbol::intern("flags");
+        let vector = Symbol::intern("Vector");
+
+        assert_eq!(flags, Symbol::intern(&String::from("flags")));
+        assert_ne!(flags, vector);
+        assert_eq!(flags.as_str(), "flags");
+        assert_eq!(vector.to_string(), "Vector");
+        assert_eq!(format!("{:?}", vector), "Symbol(\"Vector\")");
+    }
+
+    #[test]
+    fn intern_across_threads() {
+        let symbol = Symbol::intern("channels");
+        let other_thread_symbols = ::std::thread::spawn(|| {
+            (Symbol::intern("channels"), Symbol::intern("messages"))
+        }).join().unwrap();
+
+        assert_eq!(other_thread_symbols.0, symbol);
+        assert_eq!(other_thread_symbols.1, Symbol::intern("messages"));
+        assert_eq!(other_thread_symbols.1.as_str(), "messages");
+    }
+}
@@ -4,7 +4,7 @@ use nom::{self, AtEof};
 
 use cursor::Cursor;
 use error::{ParseError, ParseResult};
-use span::Span;
+use id::IdValidation;
 
 
 pub(crate) mod private {
@@ -20,10 +20,13 @@ pub(crate) mod private {
 /// This trait is sealed and cannot be implemented for types outside of
 /// `tl-lang-syn` to avoid breaking backwards compatibility when adding new
 /// methods or derived traits.
-pub trait Synom: Sized + private::Sealed {
-    fn parse_cursor<'a>(input: Cursor<'a>) -> nom::IResult<Cursor<'a>, Self>;
+///
+/// Parsed syntax tree nodes borrow identifiers and comments from the input,
+/// hence the lifetime `'a` of the input they can be parsed from.
+pub trait Synom<'a>: Sized + private::Sealed {
+    fn parse_cursor(input: Cursor<'a>) -> nom::IResult<Cursor<'a>, Self>;
 
-    fn parse_str(input: &str) -> nom::IResult<&str, Self> {
+    fn parse_str(input: &'a str) -> nom::IResult<&'a str, Self> {
         Self::parse_cursor(Cursor::new(input))
             .map(|(cursor, res)| (cursor.to_str(), res))
             .map_err(nom_err_to_str)
@@ -53,46 +56,44 @@ fn nom_err_to_str<'a, E>(error: nom::Err<Cursor<'a>, E>) -> nom::Err<&'a str, E>
 
 /// Parser that can parse TL language schema string into a particular syntax
 /// tree node.
-pub trait Parser: Sized {
+pub trait Parser<'a>: Sized {
     type Output;
 
-    fn parse_cursor<'a>(self, input: Cursor<'a>) -> ParseResult<Self::Output>;
+    fn parse_cursor(self, input: Cursor<'a>) -> ParseResult<Self::Output>;
 
-    fn parse_str(self, input: &str) -> ParseResult<Self::Output> {
+    fn parse_str(self, input: &'a str) -> ParseResult<Self::Output> {
         self.parse_cursor(Cursor::new(input))
     }
+
+    fn parse_str_with(self, input: &'a str, id_validation: IdValidation) -> ParseResult<Self::Output> {
+        self.parse_cursor(Cursor::with_id_validation(input, id_validation))
+    }
 }
 
 
-impl<F, T> Parser for F
+impl<'a, F, T> Parser<'a> for F
 where
-    F: for<'a> FnOnce(Cursor<'a>) -> nom::IResult<Cursor<'a>, T>,
+    F: FnOnce(Cursor<'a>) -> nom::IResult<Cursor<'a>, T>,
 {
     type Output = T;
 
-    fn parse_cursor<'a>(self, input: Cursor<'a>) -> ParseResult<Self::Output> {
+    fn parse_cursor(self, input: Cursor<'a>) -> ParseResult<Self::Output> {
         let (rest, value) = match self(input) {
-            Err(e) => return Err(ParseError::from_nom_err_cursor(e).unwrap()),
+            Err(e) => {
+                return Err(ParseError::from_nom_err_cursor(e)
+                    .unwrap_or_else(|| ParseError::unexpected_end_of_input(input)));
+            },
             Ok((rest, o)) => (rest, o),
         };
 
         if rest.at_eof() {
             Ok(value)
         } else if rest.to_str() == input.to_str() {  // parsed nothing
-            Err(ParseError {
-                span: input.span(),
-                error_kind: nom::ErrorKind::Custom(100000),
-            })
+            Err(ParseError::unexpected_token(input))
         } else {
             assert_eq!(input.span().end(), rest.span().end());
 
-            let begin = input.span().begin();
-            let end = rest.span().begin();
-
-            Err(ParseError {
-                span: Span::new(begin, end),
-                error_kind: nom::ErrorKind::Custom(200000),
-            })
+            Err(ParseError::trailing_input(rest))
         }
     }
 }
@@ -29,6 +29,7 @@ macro_rules! token_punct_def {
         /// [`Token!`]: index.html
         #[cfg_attr(feature = "clone-impls", derive(Clone))]
         #[cfg_attr(feature = "debug-impls", derive(Debug))]
+        #[cfg_attr(feature = "serde-impls", derive(::serde::Serialize, ::serde::Deserialize))]
         #[derive(Default)]
         pub struct $name(pub $crate::span::Span);
 
@@ -61,8 +62,8 @@ macro_rules! token_punct_def {
         impl $crate::synom::private::Sealed for $name {}
 
         #[cfg(feature = "parsing")]
-        impl $crate::synom::Synom for $name {
-            named!(parse_cursor($crate::cursor::Cursor) -> $name, map!(tag!($punct), |cursor| {
+        impl<'a> $crate::synom::Synom<'a> for $name {
+            named!(parse_cursor($crate::cursor::Cursor<'a>) -> $name, map!(tag!($punct), |cursor| {
                 $name(cursor.span())
             }));
         }
@@ -89,6 +90,7 @@ macro_rules! token_delimiter {
         /// [`Token!`]: index.html
         #[cfg_attr(feature = "clone-impls", derive(Clone))]
         #[cfg_attr(feature = "debug-impls", derive(Debug))]
+        #[cfg_attr(feature = "serde-impls", derive(::serde::Serialize, ::serde::Deserialize))]
         pub struct $name(pub $crate::span::Span);
 
         impl $name {
@@ -170,6 +172,7 @@ macro_rules! token_keyword {
         /// [`Token!`]: index.html
         #[cfg_attr(feature = "clone-impls", derive(Clone))]
         #[cfg_attr(feature = "debug-impls", derive(Debug))]
+        #[cfg_attr(feature = "serde-impls", derive(::serde::Serialize, ::serde::Deserialize))]
         pub struct $name(pub $crate::span::Span);
 
         #[cfg(feature = "eq-impls")]
@@ -201,8 +204,8 @@ macro_rules! token_keyword {
         impl $crate::synom::private::Sealed for $name {}
 
         #[cfg(feature = "parsing")]
-        impl $crate::synom::Synom for $name {
-            named!(parse_cursor($crate::cursor::Cursor) -> $name, map!(tag!($keyword), |cursor| {
+        impl<'a> $crate::synom::Synom<'a> for $name {
+            named!(parse_cursor($crate::cursor::Cursor<'a>) -> $name, map!(tag!($keyword), |cursor| {
                 $name(cursor.span())
             }));
         }
@@ -249,6 +252,7 @@ tokens! {
         // Technically not a keyword since it is a usual ident in source text,
         // but it has a special meaning in single-line comments
         "LAYER"    pub struct Layer           /// `LAYER`
+        "Layer"    pub struct LayerTitleCase  /// `Layer`
     }
 }
 
@@ -279,6 +283,7 @@ macro_rules! tlkeyword {
     ($i:expr, final) => { call!($i, <$crate::token::Final as $crate::synom::Synom>::parse_cursor) };
     ($i:expr, new) => { call!($i, <$crate::token::New as $crate::synom::Synom>::parse_cursor) };
     ($i:expr, LAYER) => { call!($i, <$crate::token::Layer as $crate::synom::Synom>::parse_cursor) };
+    ($i:expr, Layer) => { call!($i, <$crate::token::LayerTitleCase as $crate::synom::Synom>::parse_cursor) };
 }
 
 
@@ -303,4 +308,5 @@ macro_rules! TLToken {
     (Final) => { $crate::token::Final };
     (New) => { $crate::token::New };
     (LAYER) => { $crate::token::Layer };
+    (Layer) => { $crate::token::LayerTitleCase };
 }
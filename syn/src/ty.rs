@@ -1,41 +1,77 @@
-use super::{Ident, SafeParameterizedPath};
+use super::{Ident, ParameterizedPath, Path};
+use token::Paren;
 
 
 macro_attr_many! {
-    // FIXME: handle arbitrary parameterized paths when inside parentheses.
     /// The possible types that can appear in TL declarations.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub enum Type {
+    ///
+    /// Types are parsed in one of two contexts:
+    ///
+    /// - as a type expression (the default one used by `Synom`), where a path
+    ///   may be applied to space-separated arguments: `Tuple X n`,
+    ///   `Vector (Maybe %Foo)`;
+    /// - as a type term, where an application must be enclosed in parentheses
+    ///   or angle brackets. This is how types of parameters are parsed since
+    ///   parameters themselves are separated by spaces.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub enum Type<'a> {
         Int(TypeInt),
-        ParameterizedPath(TypeParameterizedPath),
-        TypeParameter(TypeTypeParameter),
-        Bare(TypeBare),
+        ParameterizedPath(TypeParameterizedPath<'a>),
+        TypeParameter(TypeTypeParameter<'a>),
+        Bare(TypeBare<'a>),
+        Paren(TypeParen<'a>),
     }
 
     /// A special type of integers in range from 0 to 2^31-1 inclusive: `#`.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
     pub struct TypeInt {
         pub hash_token: TLToken![#],
     }
 
-    /// A type represented by a safe parameterized path: `contacts.Link`, `messages.Chats`.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct TypeParameterizedPath {
-        pub safe_parameterized_path: SafeParameterizedPath,
+    /// A type represented by a parameterized path: `contacts.Link`, `Vector<int>`, `Tuple X n`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct TypeParameterizedPath<'a> {
+        pub parameterized_path: ParameterizedPath<'a>,
     }
 
     /// A type parameter: `!X`.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct TypeTypeParameter {
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct TypeTypeParameter<'a> {
         pub excl_token: TLToken![!],
-        pub ident: Ident,
+        pub ident: Ident<'a>,
     }
 
     /// A bare type: `%(Tuple X n)`.
-    #[cfg_derive!(Clone, Debug, Eq, Hash, PartialEq)]
-    pub struct TypeBare {
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct TypeBare<'a> {
         pub percent_token: TLToken![%],
-        pub inner: Box<Type>,
+        pub inner: Box<Type<'a>>,
+    }
+
+    /// A type expression enclosed in parentheses: `(Maybe %Foo)`.
+    #[cfg_derive!(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    pub struct TypeParen<'a> {
+        pub paren_token: Paren,
+        pub inner: Box<Type<'a>>,
+    }
+}
+
+impl Type<'static> {
+    /// Create the `#` type with a zeroed span.
+    pub fn int() -> Type<'static> {
+        Type::Int(TypeInt { hash_token: Default::default() })
+    }
+}
+
+impl<'a> From<ParameterizedPath<'a>> for Type<'a> {
+    fn from(parameterized_path: ParameterizedPath<'a>) -> Type<'a> {
+        Type::ParameterizedPath(TypeParameterizedPath { parameterized_path })
+    }
+}
+
+impl<'a> From<Path<'a>> for Type<'a> {
+    fn from(path: Path<'a>) -> Type<'a> {
+        Type::from(ParameterizedPath::from(path))
     }
 }
 
@@ -46,19 +82,21 @@ mod spanned {
     use spanned::Spanned;
     use spanned::private::Sealed;
 
-    impl Sealed for Type {}
+    impl<'a> Sealed for Type<'a> {}
     impl Sealed for TypeInt {}
-    impl Sealed for TypeParameterizedPath {}
-    impl Sealed for TypeTypeParameter {}
-    impl Sealed for TypeBare {}
+    impl<'a> Sealed for TypeParameterizedPath<'a> {}
+    impl<'a> Sealed for TypeTypeParameter<'a> {}
+    impl<'a> Sealed for TypeBare<'a> {}
+    impl<'a> Sealed for TypeParen<'a> {}
 
-    impl Spanned for Type {
+    impl<'a> Spanned for Type<'a> {
         fn span(&self) -> Span {
             match *self {
                 Type::Int(ref t) => t.span(),
                 Type::ParameterizedPath(ref t) => t.span(),
                 Type::TypeParameter(ref t) => t.span(),
                 Type::Bare(ref t) => t.span(),
+                Type::Paren(ref t) => t.span(),
             }
         }
     }
@@ -69,43 +107,124 @@ mod spanned {
         }
     }
 
-    impl Spanned for TypeParameterizedPath {
+    impl<'a> Spanned for TypeParameterizedPath<'a> {
         fn span(&self) -> Span {
-            self.safe_parameterized_path.span()
+            self.parameterized_path.span()
         }
     }
 
-    impl Spanned for TypeTypeParameter {
+    impl<'a> Spanned for TypeTypeParameter<'a> {
         fn span(&self) -> Span {
             self.excl_token.span()
                 .to(self.ident.span())
         }
     }
 
-    impl Spanned for TypeBare {
+    impl<'a> Spanned for TypeBare<'a> {
         fn span(&self) -> Span {
             self.percent_token.span()
                 .to(self.inner.span())
         }
     }
+
+    impl<'a> Spanned for TypeParen<'a> {
+        fn span(&self) -> Span {
+            self.paren_token.span()
+                .to(self.inner.span())
+        }
+    }
 }
 
 
+mod owned {
+    use super::*;
+    use owned::IntoOwned;
+    use owned::private::Sealed;
+
+    impl_to_owned!(Type, TypeParameterizedPath, TypeTypeParameter, TypeBare, TypeParen);
+
+    impl<'a> Sealed for Type<'a> {}
+    impl<'a> Sealed for TypeParameterizedPath<'a> {}
+    impl<'a> Sealed for TypeTypeParameter<'a> {}
+    impl<'a> Sealed for TypeBare<'a> {}
+    impl<'a> Sealed for TypeParen<'a> {}
+
+    impl<'a> IntoOwned for Type<'a> {
+        type Owned = Type<'static>;
+
+        fn into_owned(self) -> Type<'static> {
+            match self {
+                Type::Int(t) => Type::Int(t),
+                Type::ParameterizedPath(t) => Type::ParameterizedPath(t.into_owned()),
+                Type::TypeParameter(t) => Type::TypeParameter(t.into_owned()),
+                Type::Bare(t) => Type::Bare(t.into_owned()),
+                Type::Paren(t) => Type::Paren(t.into_owned()),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for TypeParameterizedPath<'a> {
+        type Owned = TypeParameterizedPath<'static>;
+
+        fn into_owned(self) -> TypeParameterizedPath<'static> {
+            TypeParameterizedPath {
+                parameterized_path: self.parameterized_path.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for TypeTypeParameter<'a> {
+        type Owned = TypeTypeParameter<'static>;
+
+        fn into_owned(self) -> TypeTypeParameter<'static> {
+            TypeTypeParameter {
+                excl_token: self.excl_token,
+                ident: self.ident.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for TypeBare<'a> {
+        type Owned = TypeBare<'static>;
+
+        fn into_owned(self) -> TypeBare<'static> {
+            TypeBare {
+                percent_token: self.percent_token,
+                inner: self.inner.into_owned(),
+            }
+        }
+    }
+
+    impl<'a> IntoOwned for TypeParen<'a> {
+        type Owned = TypeParen<'static>;
+
+        fn into_owned(self) -> TypeParen<'static> {
+            TypeParen {
+                paren_token: self.paren_token,
+                inner: self.inner.into_owned(),
+            }
+        }
+    }
+}
+
 #[cfg(feature = "parsing")]
 mod parsing {
+    use nom;
+
     use super::*;
     use cursor::Cursor;
     use synom::Synom;
     use synom::private::Sealed;
 
-    impl Sealed for Type {}
+    impl<'a> Sealed for Type<'a> {}
     impl Sealed for TypeInt {}
-    impl Sealed for TypeParameterizedPath {}
-    impl Sealed for TypeTypeParameter {}
-    impl Sealed for TypeBare {}
+    impl<'a> Sealed for TypeParameterizedPath<'a> {}
+    impl<'a> Sealed for TypeTypeParameter<'a> {}
+    impl<'a> Sealed for TypeBare<'a> {}
+    impl<'a> Sealed for TypeParen<'a> {}
 
-    impl Synom for Type {
-        named!(parse_cursor(Cursor) -> Type, alt_complete!(
+    impl<'a> Synom<'a> for Type<'a> {
+        named!(parse_cursor(Cursor<'a>) -> Type<'a>, alt_complete!(
             tlsyn!(TypeInt) => { Type::Int }
             |
             tlsyn!(TypeParameterizedPath) => { Type::ParameterizedPath }
@@ -113,38 +232,86 @@ mod parsing {
             tlsyn!(TypeTypeParameter) => { Type::TypeParameter }
             |
             tlsyn!(TypeBare) => { Type::Bare }
+            |
+            tlsyn!(TypeParen) => { Type::Paren }
+        ));
+    }
+
+    impl<'a> Type<'a> {
+        named!(pub(crate) parse_term(Cursor<'a>) -> Type<'a>, alt_complete!(
+            tlsyn!(TypeInt) => { Type::Int }
+            |
+            call!(TypeParameterizedPath::parse_term) => { Type::ParameterizedPath }
+            |
+            tlsyn!(TypeTypeParameter) => { Type::TypeParameter }
+            |
+            call!(TypeBare::parse_term) => { Type::Bare }
+            |
+            tlsyn!(TypeParen) => { Type::Paren }
         ));
     }
 
-    impl Synom for TypeInt {
-        named!(parse_cursor(Cursor) -> TypeInt, do_parse!(
+    impl<'a> Synom<'a> for TypeInt {
+        named!(parse_cursor(Cursor<'a>) -> TypeInt, do_parse!(
             hash_token: tlpunct!(#) >>
             (TypeInt { hash_token })
         ));
     }
 
-    impl Synom for TypeParameterizedPath {
-        named!(parse_cursor(Cursor) -> TypeParameterizedPath, do_parse!(
-            safe_parameterized_path: tlsyn!(SafeParameterizedPath) >>
-            (TypeParameterizedPath { safe_parameterized_path })
+    impl<'a> Synom<'a> for TypeParameterizedPath<'a> {
+        named!(parse_cursor(Cursor<'a>) -> TypeParameterizedPath<'a>, do_parse!(
+            parameterized_path: tlsyn!(ParameterizedPath) >>
+            (TypeParameterizedPath { parameterized_path })
         ));
     }
 
-    impl Synom for TypeTypeParameter {
-        named!(parse_cursor(Cursor) -> TypeTypeParameter, do_parse!(
+    impl<'a> TypeParameterizedPath<'a> {
+        named!(pub(crate) parse_term(Cursor<'a>) -> TypeParameterizedPath<'a>, do_parse!(
+            parameterized_path: call!(ParameterizedPath::parse_term) >>
+            (TypeParameterizedPath { parameterized_path })
+        ));
+    }
+
+    impl<'a> Synom<'a> for TypeTypeParameter<'a> {
+        named!(parse_cursor(Cursor<'a>) -> TypeTypeParameter<'a>, do_parse!(
             excl_token: tlpunct!(!) >>
             ident: tlsyn!(Ident) >>
             (TypeTypeParameter { excl_token, ident })
         ));
     }
 
-    impl Synom for TypeBare {
-        named!(parse_cursor(Cursor) -> TypeBare, do_parse!(
+    impl<'a> Synom<'a> for TypeBare<'a> {
+        named!(parse_cursor(Cursor<'a>) -> TypeBare<'a>, do_parse!(
             percent_token: tlpunct!(%) >>
             inner: map!(tlsyn!(Type), Box::new) >>
             (TypeBare { percent_token, inner })
         ));
     }
+
+    impl<'a> TypeBare<'a> {
+        named!(pub(crate) parse_term(Cursor<'a>) -> TypeBare<'a>, do_parse!(
+            percent_token: tlpunct!(%) >>
+            inner: map!(call!(Type::parse_term), Box::new) >>
+            (TypeBare { percent_token, inner })
+        ));
+    }
+
+    impl<'a> Synom<'a> for TypeParen<'a> {
+        named!(parse_cursor(Cursor<'a>) -> TypeParen<'a>, do_parse!(
+            inner: parens!(do_parse!(
+                call!(nom::multispace0) >>
+                inner: map!(tlsyn!(Type), Box::new) >>
+                call!(nom::multispace0) >>
+
+                (inner)
+            )) >>
+
+            (TypeParen {
+                paren_token: inner.0,
+                inner: inner.1,
+            })
+        ));
+    }
 }
 
 
@@ -156,19 +323,21 @@ mod printing {
     use print::Print;
     use print::private::Sealed;
 
-    impl Sealed for Type {}
+    impl<'a> Sealed for Type<'a> {}
     impl Sealed for TypeInt {}
-    impl Sealed for TypeParameterizedPath {}
-    impl Sealed for TypeTypeParameter {}
-    impl Sealed for TypeBare {}
+    impl<'a> Sealed for TypeParameterizedPath<'a> {}
+    impl<'a> Sealed for TypeTypeParameter<'a> {}
+    impl<'a> Sealed for TypeBare<'a> {}
+    impl<'a> Sealed for TypeParen<'a> {}
 
-    impl Print for Type {
+    impl<'a> Print for Type<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             match *self {
                 Type::Int(ref t) => t.print(f),
                 Type::ParameterizedPath(ref t) => t.print(f),
                 Type::TypeParameter(ref t) => t.print(f),
                 Type::Bare(ref t) => t.print(f),
+                Type::Paren(ref t) => t.print(f),
             }
         }
     }
@@ -179,13 +348,13 @@ mod printing {
         }
     }
 
-    impl Print for TypeParameterizedPath {
+    impl<'a> Print for TypeParameterizedPath<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            self.safe_parameterized_path.print(f)
+            self.parameterized_path.print(f)
         }
     }
 
-    impl Print for TypeTypeParameter {
+    impl<'a> Print for TypeTypeParameter<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             self.excl_token.print(f)?;
             self.ident.print(f)?;
@@ -194,7 +363,7 @@ mod printing {
         }
     }
 
-    impl Print for TypeBare {
+    impl<'a> Print for TypeBare<'a> {
         fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
             self.percent_token.print(f)?;
             self.inner.print(f)?;
@@ -202,4 +371,12 @@ mod printing {
             Ok(())
         }
     }
+
+    impl<'a> Print for TypeParen<'a> {
+        fn print(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            Paren::print(f, |f| {
+                self.inner.print(f)
+            })
+        }
+    }
 }
@@ -21,13 +21,13 @@ macro_rules! macro_attr_many {
     (
         $(
             $(#[$($attrs:tt)*])*
-            pub $item_type:tt $name:ident { $($it:tt)* }
+            pub $item_type:tt $name:ident $(<$lt:lifetime>)* { $($it:tt)* }
         )*
     ) => {
         $(
             macro_attr! {
                 $(#[$($attrs)*])*
-                pub $item_type $name { $($it)* }
+                pub $item_type $name $(<$lt>)* { $($it)* }
             }
         )*
     };
@@ -78,6 +78,21 @@ macro_rules! cfg_derive {
         }
     };
 
+    (@expand
+        (Deserialize $(, $args:ident)*),
+        then $cb:tt,
+        $(#[$($attrs:tt)*])*
+        [$it_first_tt:tt] $($it:tt)*
+    ) => {
+        cfg_derive! { @expand
+            ($($args),*),
+            then $cb,
+            $(#[$($attrs)*])*
+            #[cfg_attr(feature = "serde-impls", derive(::serde::Deserialize))]
+            [$it_first_tt] $($it)*
+        }
+    };
+
     (@expand
         (Eq $(, $args:ident)*),
         then $cb:tt,
@@ -123,6 +138,21 @@ macro_rules! cfg_derive {
         }
     };
 
+    (@expand
+        (Serialize $(, $args:ident)*),
+        then $cb:tt,
+        $(#[$($attrs:tt)*])*
+        [$it_first_tt:tt] $($it:tt)*
+    ) => {
+        cfg_derive! { @expand
+            ($($args),*),
+            then $cb,
+            $(#[$($attrs)*])*
+            #[cfg_attr(feature = "serde-impls", derive(::serde::Serialize))]
+            [$it_first_tt] $($it)*
+        }
+    };
+
     (@expand
         ($($args:ident),*),
         then $cb:tt,
@@ -139,6 +169,23 @@ macro_rules! cfg_derive {
 }
 
 
+/// Implement `to_owned()` for syntax tree nodes which borrow from the source
+/// text as a shortcut for cloning a node and converting it with `IntoOwned`.
+macro_rules! impl_to_owned {
+    ($($name:ident),* $(,)*) => {
+        $(
+            #[cfg(feature = "clone-impls")]
+            impl<'a> $name<'a> {
+                /// Copy this syntax tree node into one which owns all of its
+                /// data.
+                pub fn to_owned(&self) -> $name<'static> {
+                    $crate::owned::IntoOwned::into_owned(self.clone())
+                }
+            }
+        )*
+    };
+}
+
 #[cfg(test)]
 #[macro_use]
 pub(crate) mod tests {
@@ -248,7 +295,7 @@ pub(crate) mod tests {
             Span::zeroed(),
             Span::new(1, 1),
             Span::new(283, 82374),
-            Span::new(usize::max_value(),  usize::max_value()),
+            Span::new(usize::MAX,  usize::MAX),
         ];
 
         for span1 in &spans {
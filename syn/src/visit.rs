@@ -0,0 +1,756 @@
+//! Syntax tree traversal to walk a shared borrow of a syntax tree.
+//!
+//! Each method of the [`Visit`] trait is a hook that can be overridden to
+//! customize the behavior when visiting the corresponding type of node. By
+//! default, every method recursively visits the substructure of the input by
+//! invoking the right visitor method of each of its fields.
+//!
+//! Spans of tokens and nodes are visited with [`Visit::visit_span`].
+
+use punctuated::Pair;
+use span::Span;
+use {
+    AngleBracketedGenericArguments, BitIndex, BitSelector, CombinatorId, Comment, CommentMultiLine,
+    CommentSingleLine, ConditionalParamDef, Delimiter, DelimiterFunctions, DelimiterTypes,
+    DocComment, DocCommentTag, File, GenericArgument, GenericArguments, Id, Ident, Item,
+    ItemBuiltin, ItemCombinator, ItemComment, ItemDelimiter, ItemError, ItemLayer,
+    ItemTypeDeclaration, LayerKeyword, LayerMarker, LayerMarkerEquals, LayerMarkerMultiLine,
+    LayerMarkerSingleLine, Multiplicity, NatExpr, NatExprAdd, NatExprConst, NatExprIdent,
+    NatExprParen, OptParam, Param, ParamComment, ParamConditional, ParamRepeated,
+    ParamRepeatedIdent, ParamTypeOnly, ParamWithParen, ParameterizedPath, Path,
+    SpaceSeparatedGenericArguments, Type, TypeBare, TypeDeclarationKind, TypeInt,
+    TypeParameterizedPath, TypeParen, TypeTypeParameter,
+};
+
+
+/// Syntax tree traversal to walk a shared borrow of a syntax tree.
+///
+/// See the [module documentation] for details.
+///
+/// [module documentation]: index.html
+pub trait Visit<'ast> {
+    fn visit_angle_bracketed_generic_arguments(
+        &mut self,
+        node: &'ast AngleBracketedGenericArguments<'ast>,
+    ) {
+        visit_angle_bracketed_generic_arguments(self, node)
+    }
+
+    fn visit_bit_index(&mut self, node: &'ast BitIndex) {
+        visit_bit_index(self, node)
+    }
+
+    fn visit_bit_selector(&mut self, node: &'ast BitSelector) {
+        visit_bit_selector(self, node)
+    }
+
+    fn visit_combinator_id(&mut self, node: &'ast CombinatorId) {
+        visit_combinator_id(self, node)
+    }
+
+    fn visit_comment(&mut self, node: &'ast Comment<'ast>) {
+        visit_comment(self, node)
+    }
+
+    fn visit_comment_multi_line(&mut self, node: &'ast CommentMultiLine<'ast>) {
+        visit_comment_multi_line(self, node)
+    }
+
+    fn visit_comment_single_line(&mut self, node: &'ast CommentSingleLine<'ast>) {
+        visit_comment_single_line(self, node)
+    }
+
+    fn visit_conditional_param_def(&mut self, node: &'ast ConditionalParamDef<'ast>) {
+        visit_conditional_param_def(self, node)
+    }
+
+    fn visit_delimiter(&mut self, node: &'ast Delimiter) {
+        visit_delimiter(self, node)
+    }
+
+    fn visit_delimiter_functions(&mut self, node: &'ast DelimiterFunctions) {
+        visit_delimiter_functions(self, node)
+    }
+
+    fn visit_delimiter_types(&mut self, node: &'ast DelimiterTypes) {
+        visit_delimiter_types(self, node)
+    }
+
+    fn visit_doc_comment(&mut self, node: &'ast DocComment<'ast>) {
+        visit_doc_comment(self, node)
+    }
+
+    fn visit_doc_comment_tag(&mut self, node: &'ast DocCommentTag<'ast>) {
+        visit_doc_comment_tag(self, node)
+    }
+
+    fn visit_file(&mut self, node: &'ast File<'ast>) {
+        visit_file(self, node)
+    }
+
+    fn visit_generic_argument(&mut self, node: &'ast GenericArgument<'ast>) {
+        visit_generic_argument(self, node)
+    }
+
+    fn visit_generic_arguments(&mut self, node: &'ast GenericArguments<'ast>) {
+        visit_generic_arguments(self, node)
+    }
+
+    fn visit_id(&mut self, node: &'ast Id) {
+        visit_id(self, node)
+    }
+
+    fn visit_ident(&mut self, node: &'ast Ident<'ast>) {
+        visit_ident(self, node)
+    }
+
+    fn visit_item(&mut self, node: &'ast Item<'ast>) {
+        visit_item(self, node)
+    }
+
+    fn visit_item_builtin(&mut self, node: &'ast ItemBuiltin<'ast>) {
+        visit_item_builtin(self, node)
+    }
+
+    fn visit_item_combinator(&mut self, node: &'ast ItemCombinator<'ast>) {
+        visit_item_combinator(self, node)
+    }
+
+    fn visit_item_comment(&mut self, node: &'ast ItemComment<'ast>) {
+        visit_item_comment(self, node)
+    }
+
+    fn visit_item_delimiter(&mut self, node: &'ast ItemDelimiter) {
+        visit_item_delimiter(self, node)
+    }
+
+    fn visit_item_error(&mut self, node: &'ast ItemError<'ast>) {
+        visit_item_error(self, node)
+    }
+
+    fn visit_item_layer(&mut self, node: &'ast ItemLayer) {
+        visit_item_layer(self, node)
+    }
+
+    fn visit_item_type_declaration(&mut self, node: &'ast ItemTypeDeclaration<'ast>) {
+        visit_item_type_declaration(self, node)
+    }
+
+    fn visit_layer_keyword(&mut self, node: &'ast LayerKeyword) {
+        visit_layer_keyword(self, node)
+    }
+
+    fn visit_layer_marker(&mut self, node: &'ast LayerMarker) {
+        visit_layer_marker(self, node)
+    }
+
+    fn visit_layer_marker_equals(&mut self, node: &'ast LayerMarkerEquals) {
+        visit_layer_marker_equals(self, node)
+    }
+
+    fn visit_layer_marker_multi_line(&mut self, node: &'ast LayerMarkerMultiLine) {
+        visit_layer_marker_multi_line(self, node)
+    }
+
+    fn visit_layer_marker_single_line(&mut self, node: &'ast LayerMarkerSingleLine) {
+        visit_layer_marker_single_line(self, node)
+    }
+
+    fn visit_multiplicity(&mut self, node: &'ast Multiplicity<'ast>) {
+        visit_multiplicity(self, node)
+    }
+
+    fn visit_nat_expr(&mut self, node: &'ast NatExpr<'ast>) {
+        visit_nat_expr(self, node)
+    }
+
+    fn visit_nat_expr_add(&mut self, node: &'ast NatExprAdd<'ast>) {
+        visit_nat_expr_add(self, node)
+    }
+
+    fn visit_nat_expr_const(&mut self, node: &'ast NatExprConst) {
+        visit_nat_expr_const(self, node)
+    }
+
+    fn visit_nat_expr_ident(&mut self, node: &'ast NatExprIdent<'ast>) {
+        visit_nat_expr_ident(self, node)
+    }
+
+    fn visit_nat_expr_paren(&mut self, node: &'ast NatExprParen<'ast>) {
+        visit_nat_expr_paren(self, node)
+    }
+
+    fn visit_opt_param(&mut self, node: &'ast OptParam<'ast>) {
+        visit_opt_param(self, node)
+    }
+
+    fn visit_param(&mut self, node: &'ast Param<'ast>) {
+        visit_param(self, node)
+    }
+
+    fn visit_param_comment(&mut self, node: &'ast ParamComment<'ast>) {
+        visit_param_comment(self, node)
+    }
+
+    fn visit_param_conditional(&mut self, node: &'ast ParamConditional<'ast>) {
+        visit_param_conditional(self, node)
+    }
+
+    fn visit_param_repeated(&mut self, node: &'ast ParamRepeated<'ast>) {
+        visit_param_repeated(self, node)
+    }
+
+    fn visit_param_repeated_ident(&mut self, node: &'ast ParamRepeatedIdent<'ast>) {
+        visit_param_repeated_ident(self, node)
+    }
+
+    fn visit_param_type_only(&mut self, node: &'ast ParamTypeOnly<'ast>) {
+        visit_param_type_only(self, node)
+    }
+
+    fn visit_param_with_paren(&mut self, node: &'ast ParamWithParen<'ast>) {
+        visit_param_with_paren(self, node)
+    }
+
+    fn visit_parameterized_path(&mut self, node: &'ast ParameterizedPath<'ast>) {
+        visit_parameterized_path(self, node)
+    }
+
+    fn visit_path(&mut self, node: &'ast Path<'ast>) {
+        visit_path(self, node)
+    }
+
+    fn visit_space_separated_generic_arguments(
+        &mut self,
+        node: &'ast SpaceSeparatedGenericArguments<'ast>,
+    ) {
+        visit_space_separated_generic_arguments(self, node)
+    }
+
+    fn visit_type(&mut self, node: &'ast Type<'ast>) {
+        visit_type(self, node)
+    }
+
+    fn visit_type_bare(&mut self, node: &'ast TypeBare<'ast>) {
+        visit_type_bare(self, node)
+    }
+
+    fn visit_type_declaration_kind(&mut self, node: &'ast TypeDeclarationKind) {
+        visit_type_declaration_kind(self, node)
+    }
+
+    fn visit_type_int(&mut self, node: &'ast TypeInt) {
+        visit_type_int(self, node)
+    }
+
+    fn visit_type_parameterized_path(&mut self, node: &'ast TypeParameterizedPath<'ast>) {
+        visit_type_parameterized_path(self, node)
+    }
+
+    fn visit_type_paren(&mut self, node: &'ast TypeParen<'ast>) {
+        visit_type_paren(self, node)
+    }
+
+    fn visit_type_type_parameter(&mut self, node: &'ast TypeTypeParameter<'ast>) {
+        visit_type_type_parameter(self, node)
+    }
+
+    fn visit_span(&mut self, _span: &'ast Span) {}
+}
+
+
+pub fn visit_angle_bracketed_generic_arguments<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast AngleBracketedGenericArguments<'ast>,
+) {
+    v.visit_span(&node.langle_token.0);
+    for pair in node.args.pairs() {
+        match pair {
+            Pair::Punctuated(value, punct) => {
+                v.visit_generic_argument(value);
+                v.visit_span(&punct.0);
+            },
+            Pair::End(value) => v.visit_generic_argument(value),
+        }
+    }
+    v.visit_span(&node.rangle_token.0);
+}
+
+pub fn visit_bit_index<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast BitIndex) {
+    v.visit_span(&node.span);
+}
+
+pub fn visit_bit_selector<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast BitSelector) {
+    v.visit_span(&node.dot_token.0);
+    v.visit_bit_index(&node.bit_index);
+}
+
+pub fn visit_combinator_id<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast CombinatorId) {
+    v.visit_span(&node.hash_token.0);
+    v.visit_id(&node.id);
+}
+
+pub fn visit_comment<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Comment<'ast>) {
+    match *node {
+        Comment::SingleLine(ref inner) => v.visit_comment_single_line(inner),
+        Comment::MultiLine(ref inner) => v.visit_comment_multi_line(inner),
+    }
+}
+
+pub fn visit_comment_multi_line<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast CommentMultiLine<'ast>,
+) {
+    v.visit_span(&node.slash_asterisk_token.0);
+    v.visit_span(&node.content_span);
+}
+
+pub fn visit_comment_single_line<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast CommentSingleLine<'ast>,
+) {
+    v.visit_span(&node.slash_slash_token.0);
+    v.visit_span(&node.content_span);
+}
+
+pub fn visit_conditional_param_def<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast ConditionalParamDef<'ast>,
+) {
+    v.visit_ident(&node.var_ident);
+    if let Some(ref it) = node.bit_selector {
+        v.visit_bit_selector(it);
+    }
+    v.visit_span(&node.question_token.0);
+}
+
+pub fn visit_delimiter<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Delimiter) {
+    match *node {
+        Delimiter::Types(ref inner) => v.visit_delimiter_types(inner),
+        Delimiter::Functions(ref inner) => v.visit_delimiter_functions(inner),
+    }
+}
+
+pub fn visit_delimiter_functions<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast DelimiterFunctions,
+) {
+    v.visit_span(&node.span);
+}
+
+pub fn visit_delimiter_types<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast DelimiterTypes) {
+    v.visit_span(&node.span);
+}
+
+pub fn visit_doc_comment<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast DocComment<'ast>) {
+    for it in &node.tags {
+        v.visit_doc_comment_tag(it);
+    }
+}
+
+pub fn visit_doc_comment_tag<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast DocCommentTag<'ast>,
+) {
+    v.visit_span(&node.name_span);
+    v.visit_span(&node.text_span);
+}
+
+pub fn visit_file<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast File<'ast>) {
+    for it in &node.items {
+        v.visit_item(it);
+    }
+}
+
+pub fn visit_generic_argument<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast GenericArgument<'ast>,
+) {
+    match *node {
+        GenericArgument::Type(ref inner) => v.visit_type(inner),
+        GenericArgument::Nat(ref inner) => v.visit_nat_expr(inner),
+    }
+}
+
+pub fn visit_generic_arguments<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast GenericArguments<'ast>,
+) {
+    match *node {
+        GenericArguments::AngleBracketed(ref inner) => v.visit_angle_bracketed_generic_arguments(inner),
+        GenericArguments::SpaceSeparated(ref inner) => v.visit_space_separated_generic_arguments(inner),
+    }
+}
+
+pub fn visit_id<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Id) {
+    v.visit_span(&node.span);
+}
+
+pub fn visit_ident<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Ident<'ast>) {
+    v.visit_span(&node.span);
+}
+
+pub fn visit_item<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Item<'ast>) {
+    match *node {
+        Item::Combinator(ref inner) => v.visit_item_combinator(inner),
+        Item::Builtin(ref inner) => v.visit_item_builtin(inner),
+        Item::TypeDeclaration(ref inner) => v.visit_item_type_declaration(inner),
+        Item::Delimiter(ref inner) => v.visit_item_delimiter(inner),
+        Item::Layer(ref inner) => v.visit_item_layer(inner),
+        Item::Comment(ref inner) => v.visit_item_comment(inner),
+        Item::Error(ref inner) => v.visit_item_error(inner),
+    }
+}
+
+pub fn visit_item_builtin<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemBuiltin<'ast>) {
+    v.visit_path(&node.name);
+    if let Some(ref it) = node.combinator_id {
+        v.visit_combinator_id(it);
+    }
+    v.visit_span(&node.question_token.0);
+    v.visit_span(&node.equals_token.0);
+    v.visit_path(&node.result_type);
+    v.visit_span(&node.semicolon_token.0);
+}
+
+pub fn visit_item_combinator<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast ItemCombinator<'ast>,
+) {
+    for it in &node.leading_comments {
+        v.visit_comment(it);
+    }
+    if let Some(ref it) = node.doc_comment {
+        v.visit_doc_comment(it);
+    }
+    v.visit_path(&node.name);
+    if let Some(ref it) = node.combinator_id {
+        v.visit_combinator_id(it);
+    }
+    for it in &node.opt_params {
+        v.visit_opt_param(it);
+    }
+    for it in &node.opt_param_comments {
+        v.visit_param_comment(it);
+    }
+    for it in &node.params {
+        v.visit_param(it);
+    }
+    for it in &node.param_comments {
+        v.visit_param_comment(it);
+    }
+    v.visit_span(&node.equals_token.0);
+    for it in &node.result_comments {
+        v.visit_comment(it);
+    }
+    v.visit_parameterized_path(&node.result_type);
+    v.visit_span(&node.semicolon_token.0);
+    if let Some(ref it) = node.trailing_comment {
+        v.visit_comment(it);
+    }
+}
+
+pub fn visit_item_comment<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemComment<'ast>) {
+    v.visit_comment(&node.comment);
+}
+
+pub fn visit_item_delimiter<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemDelimiter) {
+    v.visit_delimiter(&node.delimiter);
+}
+
+pub fn visit_item_error<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemError<'ast>) {
+    v.visit_span(&node.span);
+}
+
+pub fn visit_item_layer<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemLayer) {
+    v.visit_layer_marker(&node.marker);
+    v.visit_span(&node.layer_span);
+}
+
+pub fn visit_item_type_declaration<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast ItemTypeDeclaration<'ast>,
+) {
+    v.visit_type_declaration_kind(&node.kind);
+    v.visit_path(&node.name);
+    v.visit_span(&node.semicolon_token.0);
+}
+
+pub fn visit_layer_keyword<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast LayerKeyword) {
+    match *node {
+        LayerKeyword::Upper(ref token) => v.visit_span(&token.0),
+        LayerKeyword::TitleCase(ref token) => v.visit_span(&token.0),
+    }
+}
+
+pub fn visit_layer_marker<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast LayerMarker) {
+    match *node {
+        LayerMarker::SingleLine(ref inner) => v.visit_layer_marker_single_line(inner),
+        LayerMarker::MultiLine(ref inner) => v.visit_layer_marker_multi_line(inner),
+        LayerMarker::Equals(ref inner) => v.visit_layer_marker_equals(inner),
+    }
+}
+
+pub fn visit_layer_marker_equals<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast LayerMarkerEquals,
+) {
+    v.visit_span(&node.left_span);
+    v.visit_span(&node.right_span);
+}
+
+pub fn visit_layer_marker_multi_line<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast LayerMarkerMultiLine,
+) {
+    v.visit_span(&node.slash_asterisk_token.0);
+    v.visit_layer_keyword(&node.layer_keyword);
+}
+
+pub fn visit_layer_marker_single_line<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast LayerMarkerSingleLine,
+) {
+    v.visit_span(&node.slash_slash_token.0);
+    v.visit_layer_keyword(&node.layer_keyword);
+}
+
+pub fn visit_multiplicity<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast Multiplicity<'ast>,
+) {
+    v.visit_nat_expr(&node.term);
+    v.visit_span(&node.asterisk_token.0);
+}
+
+pub fn visit_nat_expr<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast NatExpr<'ast>) {
+    match *node {
+        NatExpr::Const(ref inner) => v.visit_nat_expr_const(inner),
+        NatExpr::Ident(ref inner) => v.visit_nat_expr_ident(inner),
+        NatExpr::Add(ref inner) => v.visit_nat_expr_add(inner),
+        NatExpr::Paren(ref inner) => v.visit_nat_expr_paren(inner),
+    }
+}
+
+pub fn visit_nat_expr_add<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast NatExprAdd<'ast>) {
+    v.visit_nat_expr(&node.left);
+    v.visit_span(&node.plus_token.0);
+    v.visit_nat_expr(&node.right);
+}
+
+pub fn visit_nat_expr_const<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast NatExprConst) {
+    v.visit_span(&node.span);
+}
+
+pub fn visit_nat_expr_ident<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast NatExprIdent<'ast>,
+) {
+    v.visit_ident(&node.ident);
+}
+
+pub fn visit_nat_expr_paren<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast NatExprParen<'ast>,
+) {
+    v.visit_span(&node.paren_token.0);
+    v.visit_nat_expr(&node.expr);
+}
+
+pub fn visit_opt_param<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast OptParam<'ast>) {
+    v.visit_span(&node.brace_token.0);
+    for it in &node.var_idents {
+        v.visit_ident(it);
+    }
+    v.visit_span(&node.colon_token.0);
+    v.visit_type(&node.ty);
+}
+
+pub fn visit_param<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Param<'ast>) {
+    match *node {
+        Param::Conditional(ref inner) => v.visit_param_conditional(inner),
+        Param::Repeated(ref inner) => v.visit_param_repeated(inner),
+        Param::WithParen(ref inner) => v.visit_param_with_paren(inner),
+        Param::TypeOnly(ref inner) => v.visit_param_type_only(inner),
+    }
+}
+
+pub fn visit_param_comment<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast ParamComment<'ast>,
+) {
+    v.visit_comment(&node.comment);
+}
+
+pub fn visit_param_conditional<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast ParamConditional<'ast>,
+) {
+    v.visit_ident(&node.var_ident);
+    v.visit_span(&node.colon_token.0);
+    if let Some(ref it) = node.conditional_param_def {
+        v.visit_conditional_param_def(it);
+    }
+    v.visit_type(&node.ty);
+}
+
+pub fn visit_param_repeated<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast ParamRepeated<'ast>,
+) {
+    if let Some(ref it) = node.param_repeated_ident {
+        v.visit_param_repeated_ident(it);
+    }
+    if let Some(ref it) = node.multiplicity {
+        v.visit_multiplicity(it);
+    }
+    v.visit_span(&node.bracket_token.0);
+    for it in &node.params {
+        v.visit_param(it);
+    }
+}
+
+pub fn visit_param_repeated_ident<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast ParamRepeatedIdent<'ast>,
+) {
+    v.visit_ident(&node.var_ident);
+    v.visit_span(&node.colon_token.0);
+}
+
+pub fn visit_param_type_only<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast ParamTypeOnly<'ast>,
+) {
+    v.visit_type(&node.ty);
+}
+
+pub fn visit_param_with_paren<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast ParamWithParen<'ast>,
+) {
+    v.visit_span(&node.paren_token.0);
+    for it in &node.var_idents {
+        v.visit_ident(it);
+    }
+    v.visit_span(&node.colon_token.0);
+    v.visit_type(&node.ty);
+}
+
+pub fn visit_parameterized_path<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast ParameterizedPath<'ast>,
+) {
+    v.visit_path(&node.path);
+    if let Some(ref it) = node.args {
+        v.visit_generic_arguments(it);
+    }
+}
+
+pub fn visit_path<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Path<'ast>) {
+    for pair in node.segments.pairs() {
+        match pair {
+            Pair::Punctuated(value, punct) => {
+                v.visit_ident(value);
+                v.visit_span(&punct.0);
+            },
+            Pair::End(value) => v.visit_ident(value),
+        }
+    }
+}
+
+pub fn visit_space_separated_generic_arguments<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast SpaceSeparatedGenericArguments<'ast>,
+) {
+    for it in &node.args {
+        v.visit_generic_argument(it);
+    }
+}
+
+pub fn visit_type<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Type<'ast>) {
+    match *node {
+        Type::Int(ref inner) => v.visit_type_int(inner),
+        Type::ParameterizedPath(ref inner) => v.visit_type_parameterized_path(inner),
+        Type::TypeParameter(ref inner) => v.visit_type_type_parameter(inner),
+        Type::Bare(ref inner) => v.visit_type_bare(inner),
+        Type::Paren(ref inner) => v.visit_type_paren(inner),
+    }
+}
+
+pub fn visit_type_bare<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TypeBare<'ast>) {
+    v.visit_span(&node.percent_token.0);
+    v.visit_type(&node.inner);
+}
+
+pub fn visit_type_declaration_kind<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast TypeDeclarationKind,
+) {
+    match *node {
+        TypeDeclarationKind::New(ref token) => v.visit_span(&token.0),
+        TypeDeclarationKind::Final(ref token) => v.visit_span(&token.0),
+        TypeDeclarationKind::Empty(ref token) => v.visit_span(&token.0),
+    }
+}
+
+pub fn visit_type_int<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TypeInt) {
+    v.visit_span(&node.hash_token.0);
+}
+
+pub fn visit_type_parameterized_path<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast TypeParameterizedPath<'ast>,
+) {
+    v.visit_parameterized_path(&node.parameterized_path);
+}
+
+pub fn visit_type_paren<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TypeParen<'ast>) {
+    v.visit_span(&node.paren_token.0);
+    v.visit_type(&node.inner);
+}
+
+pub fn visit_type_type_parameter<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast TypeTypeParameter<'ast>,
+) {
+    v.visit_span(&node.excl_token.0);
+    v.visit_ident(&node.ident);
+}
+
+
+#[cfg(all(test, feature = "parsing"))]
+mod tests {
+    use super::*;
+
+    struct IdentCollector<'ast> {
+        idents: Vec<&'ast str>,
+        spans: usize,
+    }
+
+    impl<'ast> Visit<'ast> for IdentCollector<'ast> {
+        fn visit_ident(&mut self, node: &'ast Ident<'ast>) {
+            self.idents.push(node.as_str());
+            visit_ident(self, node);
+        }
+
+        fn visit_span(&mut self, _span: &'ast Span) {
+            self.spans += 1;
+        }
+    }
+
+    #[test]
+    fn collect_idents() {
+        let input = "messages.sendMessage#fa88427a flags:# peer:InputPeer silent:flags.5?true = Updates;";
+        let file = ::parse_file_str(input).unwrap();
+        let mut collector = IdentCollector { idents: Vec::new(), spans: 0 };
+
+        collector.visit_file(&file);
+
+        assert_eq!(collector.idents, [
+            "messages", "sendMessage", "flags", "peer", "InputPeer", "silent", "flags", "true",
+            "Updates",
+        ]);
+        assert!(collector.spans > collector.idents.len());
+    }
+}
@@ -0,0 +1,760 @@
+//! Syntax tree traversal to mutate an exclusive borrow of a syntax tree in
+//! place.
+//!
+//! Each method of the [`VisitMut`] trait is a hook that can be overridden to
+//! customize the behavior when mutating the corresponding type of node. By
+//! default, every method recursively visits the substructure of the input by
+//! invoking the right visitor method of each of its fields.
+//!
+//! Spans of tokens and nodes are visited with [`VisitMut::visit_span_mut`].
+
+use punctuated::Pair;
+use span::Span;
+use {
+    AngleBracketedGenericArguments, BitIndex, BitSelector, CombinatorId, Comment, CommentMultiLine,
+    CommentSingleLine, ConditionalParamDef, Delimiter, DelimiterFunctions, DelimiterTypes,
+    DocComment, DocCommentTag, File, GenericArgument, GenericArguments, Id, Ident, Item,
+    ItemBuiltin, ItemCombinator, ItemComment, ItemDelimiter, ItemError, ItemLayer,
+    ItemTypeDeclaration, LayerKeyword, LayerMarker, LayerMarkerEquals, LayerMarkerMultiLine,
+    LayerMarkerSingleLine, Multiplicity, NatExpr, NatExprAdd, NatExprConst, NatExprIdent,
+    NatExprParen, OptParam, Param, ParamComment, ParamConditional, ParamRepeated,
+    ParamRepeatedIdent, ParamTypeOnly, ParamWithParen, ParameterizedPath, Path,
+    SpaceSeparatedGenericArguments, Type, TypeBare, TypeDeclarationKind, TypeInt,
+    TypeParameterizedPath, TypeParen, TypeTypeParameter,
+};
+
+
+/// Syntax tree traversal to mutate an exclusive borrow of a syntax tree in
+/// place.
+///
+/// See the [module documentation] for details.
+///
+/// [module documentation]: index.html
+pub trait VisitMut<'a> {
+    fn visit_angle_bracketed_generic_arguments_mut(
+        &mut self,
+        node: &mut AngleBracketedGenericArguments<'a>,
+    ) {
+        visit_angle_bracketed_generic_arguments_mut(self, node)
+    }
+
+    fn visit_bit_index_mut(&mut self, node: &mut BitIndex) {
+        visit_bit_index_mut(self, node)
+    }
+
+    fn visit_bit_selector_mut(&mut self, node: &mut BitSelector) {
+        visit_bit_selector_mut(self, node)
+    }
+
+    fn visit_combinator_id_mut(&mut self, node: &mut CombinatorId) {
+        visit_combinator_id_mut(self, node)
+    }
+
+    fn visit_comment_mut(&mut self, node: &mut Comment<'a>) {
+        visit_comment_mut(self, node)
+    }
+
+    fn visit_comment_multi_line_mut(&mut self, node: &mut CommentMultiLine<'a>) {
+        visit_comment_multi_line_mut(self, node)
+    }
+
+    fn visit_comment_single_line_mut(&mut self, node: &mut CommentSingleLine<'a>) {
+        visit_comment_single_line_mut(self, node)
+    }
+
+    fn visit_conditional_param_def_mut(&mut self, node: &mut ConditionalParamDef<'a>) {
+        visit_conditional_param_def_mut(self, node)
+    }
+
+    fn visit_delimiter_mut(&mut self, node: &mut Delimiter) {
+        visit_delimiter_mut(self, node)
+    }
+
+    fn visit_delimiter_functions_mut(&mut self, node: &mut DelimiterFunctions) {
+        visit_delimiter_functions_mut(self, node)
+    }
+
+    fn visit_delimiter_types_mut(&mut self, node: &mut DelimiterTypes) {
+        visit_delimiter_types_mut(self, node)
+    }
+
+    fn visit_doc_comment_mut(&mut self, node: &mut DocComment<'a>) {
+        visit_doc_comment_mut(self, node)
+    }
+
+    fn visit_doc_comment_tag_mut(&mut self, node: &mut DocCommentTag<'a>) {
+        visit_doc_comment_tag_mut(self, node)
+    }
+
+    fn visit_file_mut(&mut self, node: &mut File<'a>) {
+        visit_file_mut(self, node)
+    }
+
+    fn visit_generic_argument_mut(&mut self, node: &mut GenericArgument<'a>) {
+        visit_generic_argument_mut(self, node)
+    }
+
+    fn visit_generic_arguments_mut(&mut self, node: &mut GenericArguments<'a>) {
+        visit_generic_arguments_mut(self, node)
+    }
+
+    fn visit_id_mut(&mut self, node: &mut Id) {
+        visit_id_mut(self, node)
+    }
+
+    fn visit_ident_mut(&mut self, node: &mut Ident<'a>) {
+        visit_ident_mut(self, node)
+    }
+
+    fn visit_item_mut(&mut self, node: &mut Item<'a>) {
+        visit_item_mut(self, node)
+    }
+
+    fn visit_item_builtin_mut(&mut self, node: &mut ItemBuiltin<'a>) {
+        visit_item_builtin_mut(self, node)
+    }
+
+    fn visit_item_combinator_mut(&mut self, node: &mut ItemCombinator<'a>) {
+        visit_item_combinator_mut(self, node)
+    }
+
+    fn visit_item_comment_mut(&mut self, node: &mut ItemComment<'a>) {
+        visit_item_comment_mut(self, node)
+    }
+
+    fn visit_item_delimiter_mut(&mut self, node: &mut ItemDelimiter) {
+        visit_item_delimiter_mut(self, node)
+    }
+
+    fn visit_item_error_mut(&mut self, node: &mut ItemError<'a>) {
+        visit_item_error_mut(self, node)
+    }
+
+    fn visit_item_layer_mut(&mut self, node: &mut ItemLayer) {
+        visit_item_layer_mut(self, node)
+    }
+
+    fn visit_item_type_declaration_mut(&mut self, node: &mut ItemTypeDeclaration<'a>) {
+        visit_item_type_declaration_mut(self, node)
+    }
+
+    fn visit_layer_keyword_mut(&mut self, node: &mut LayerKeyword) {
+        visit_layer_keyword_mut(self, node)
+    }
+
+    fn visit_layer_marker_mut(&mut self, node: &mut LayerMarker) {
+        visit_layer_marker_mut(self, node)
+    }
+
+    fn visit_layer_marker_equals_mut(&mut self, node: &mut LayerMarkerEquals) {
+        visit_layer_marker_equals_mut(self, node)
+    }
+
+    fn visit_layer_marker_multi_line_mut(&mut self, node: &mut LayerMarkerMultiLine) {
+        visit_layer_marker_multi_line_mut(self, node)
+    }
+
+    fn visit_layer_marker_single_line_mut(&mut self, node: &mut LayerMarkerSingleLine) {
+        visit_layer_marker_single_line_mut(self, node)
+    }
+
+    fn visit_multiplicity_mut(&mut self, node: &mut Multiplicity<'a>) {
+        visit_multiplicity_mut(self, node)
+    }
+
+    fn visit_nat_expr_mut(&mut self, node: &mut NatExpr<'a>) {
+        visit_nat_expr_mut(self, node)
+    }
+
+    fn visit_nat_expr_add_mut(&mut self, node: &mut NatExprAdd<'a>) {
+        visit_nat_expr_add_mut(self, node)
+    }
+
+    fn visit_nat_expr_const_mut(&mut self, node: &mut NatExprConst) {
+        visit_nat_expr_const_mut(self, node)
+    }
+
+    fn visit_nat_expr_ident_mut(&mut self, node: &mut NatExprIdent<'a>) {
+        visit_nat_expr_ident_mut(self, node)
+    }
+
+    fn visit_nat_expr_paren_mut(&mut self, node: &mut NatExprParen<'a>) {
+        visit_nat_expr_paren_mut(self, node)
+    }
+
+    fn visit_opt_param_mut(&mut self, node: &mut OptParam<'a>) {
+        visit_opt_param_mut(self, node)
+    }
+
+    fn visit_param_mut(&mut self, node: &mut Param<'a>) {
+        visit_param_mut(self, node)
+    }
+
+    fn visit_param_comment_mut(&mut self, node: &mut ParamComment<'a>) {
+        visit_param_comment_mut(self, node)
+    }
+
+    fn visit_param_conditional_mut(&mut self, node: &mut ParamConditional<'a>) {
+        visit_param_conditional_mut(self, node)
+    }
+
+    fn visit_param_repeated_mut(&mut self, node: &mut ParamRepeated<'a>) {
+        visit_param_repeated_mut(self, node)
+    }
+
+    fn visit_param_repeated_ident_mut(&mut self, node: &mut ParamRepeatedIdent<'a>) {
+        visit_param_repeated_ident_mut(self, node)
+    }
+
+    fn visit_param_type_only_mut(&mut self, node: &mut ParamTypeOnly<'a>) {
+        visit_param_type_only_mut(self, node)
+    }
+
+    fn visit_param_with_paren_mut(&mut self, node: &mut ParamWithParen<'a>) {
+        visit_param_with_paren_mut(self, node)
+    }
+
+    fn visit_parameterized_path_mut(&mut self, node: &mut ParameterizedPath<'a>) {
+        visit_parameterized_path_mut(self, node)
+    }
+
+    fn visit_path_mut(&mut self, node: &mut Path<'a>) {
+        visit_path_mut(self, node)
+    }
+
+    fn visit_space_separated_generic_arguments_mut(
+        &mut self,
+        node: &mut SpaceSeparatedGenericArguments<'a>,
+    ) {
+        visit_space_separated_generic_arguments_mut(self, node)
+    }
+
+    fn visit_type_mut(&mut self, node: &mut Type<'a>) {
+        visit_type_mut(self, node)
+    }
+
+    fn visit_type_bare_mut(&mut self, node: &mut TypeBare<'a>) {
+        visit_type_bare_mut(self, node)
+    }
+
+    fn visit_type_declaration_kind_mut(&mut self, node: &mut TypeDeclarationKind) {
+        visit_type_declaration_kind_mut(self, node)
+    }
+
+    fn visit_type_int_mut(&mut self, node: &mut TypeInt) {
+        visit_type_int_mut(self, node)
+    }
+
+    fn visit_type_parameterized_path_mut(&mut self, node: &mut TypeParameterizedPath<'a>) {
+        visit_type_parameterized_path_mut(self, node)
+    }
+
+    fn visit_type_paren_mut(&mut self, node: &mut TypeParen<'a>) {
+        visit_type_paren_mut(self, node)
+    }
+
+    fn visit_type_type_parameter_mut(&mut self, node: &mut TypeTypeParameter<'a>) {
+        visit_type_type_parameter_mut(self, node)
+    }
+
+    fn visit_span_mut(&mut self, _span: &mut Span) {}
+}
+
+
+pub fn visit_angle_bracketed_generic_arguments_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut AngleBracketedGenericArguments<'a>,
+) {
+    v.visit_span_mut(&mut node.langle_token.0);
+    for pair in node.args.pairs_mut() {
+        match pair {
+            Pair::Punctuated(value, punct) => {
+                v.visit_generic_argument_mut(value);
+                v.visit_span_mut(&mut punct.0);
+            },
+            Pair::End(value) => v.visit_generic_argument_mut(value),
+        }
+    }
+    v.visit_span_mut(&mut node.rangle_token.0);
+}
+
+pub fn visit_bit_index_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut BitIndex) {
+    v.visit_span_mut(&mut node.span);
+}
+
+pub fn visit_bit_selector_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut BitSelector) {
+    v.visit_span_mut(&mut node.dot_token.0);
+    v.visit_bit_index_mut(&mut node.bit_index);
+}
+
+pub fn visit_combinator_id_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut CombinatorId) {
+    v.visit_span_mut(&mut node.hash_token.0);
+    v.visit_id_mut(&mut node.id);
+}
+
+pub fn visit_comment_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut Comment<'a>) {
+    match *node {
+        Comment::SingleLine(ref mut inner) => v.visit_comment_single_line_mut(inner),
+        Comment::MultiLine(ref mut inner) => v.visit_comment_multi_line_mut(inner),
+    }
+}
+
+pub fn visit_comment_multi_line_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut CommentMultiLine<'a>,
+) {
+    v.visit_span_mut(&mut node.slash_asterisk_token.0);
+    v.visit_span_mut(&mut node.content_span);
+}
+
+pub fn visit_comment_single_line_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut CommentSingleLine<'a>,
+) {
+    v.visit_span_mut(&mut node.slash_slash_token.0);
+    v.visit_span_mut(&mut node.content_span);
+}
+
+pub fn visit_conditional_param_def_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut ConditionalParamDef<'a>,
+) {
+    v.visit_ident_mut(&mut node.var_ident);
+    if let Some(ref mut it) = node.bit_selector {
+        v.visit_bit_selector_mut(it);
+    }
+    v.visit_span_mut(&mut node.question_token.0);
+}
+
+pub fn visit_delimiter_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut Delimiter) {
+    match *node {
+        Delimiter::Types(ref mut inner) => v.visit_delimiter_types_mut(inner),
+        Delimiter::Functions(ref mut inner) => v.visit_delimiter_functions_mut(inner),
+    }
+}
+
+pub fn visit_delimiter_functions_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut DelimiterFunctions,
+) {
+    v.visit_span_mut(&mut node.span);
+}
+
+pub fn visit_delimiter_types_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut DelimiterTypes,
+) {
+    v.visit_span_mut(&mut node.span);
+}
+
+pub fn visit_doc_comment_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut DocComment<'a>) {
+    for it in &mut node.tags {
+        v.visit_doc_comment_tag_mut(it);
+    }
+}
+
+pub fn visit_doc_comment_tag_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut DocCommentTag<'a>,
+) {
+    v.visit_span_mut(&mut node.name_span);
+    v.visit_span_mut(&mut node.text_span);
+}
+
+pub fn visit_file_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut File<'a>) {
+    for it in &mut node.items {
+        v.visit_item_mut(it);
+    }
+}
+
+pub fn visit_generic_argument_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut GenericArgument<'a>,
+) {
+    match *node {
+        GenericArgument::Type(ref mut inner) => v.visit_type_mut(inner),
+        GenericArgument::Nat(ref mut inner) => v.visit_nat_expr_mut(inner),
+    }
+}
+
+pub fn visit_generic_arguments_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut GenericArguments<'a>,
+) {
+    match *node {
+        GenericArguments::AngleBracketed(ref mut inner) => v.visit_angle_bracketed_generic_arguments_mut(inner),
+        GenericArguments::SpaceSeparated(ref mut inner) => v.visit_space_separated_generic_arguments_mut(inner),
+    }
+}
+
+pub fn visit_id_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut Id) {
+    v.visit_span_mut(&mut node.span);
+}
+
+pub fn visit_ident_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut Ident<'a>) {
+    v.visit_span_mut(&mut node.span);
+}
+
+pub fn visit_item_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut Item<'a>) {
+    match *node {
+        Item::Combinator(ref mut inner) => v.visit_item_combinator_mut(inner),
+        Item::Builtin(ref mut inner) => v.visit_item_builtin_mut(inner),
+        Item::TypeDeclaration(ref mut inner) => v.visit_item_type_declaration_mut(inner),
+        Item::Delimiter(ref mut inner) => v.visit_item_delimiter_mut(inner),
+        Item::Layer(ref mut inner) => v.visit_item_layer_mut(inner),
+        Item::Comment(ref mut inner) => v.visit_item_comment_mut(inner),
+        Item::Error(ref mut inner) => v.visit_item_error_mut(inner),
+    }
+}
+
+pub fn visit_item_builtin_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut ItemBuiltin<'a>) {
+    v.visit_path_mut(&mut node.name);
+    if let Some(ref mut it) = node.combinator_id {
+        v.visit_combinator_id_mut(it);
+    }
+    v.visit_span_mut(&mut node.question_token.0);
+    v.visit_span_mut(&mut node.equals_token.0);
+    v.visit_path_mut(&mut node.result_type);
+    v.visit_span_mut(&mut node.semicolon_token.0);
+}
+
+pub fn visit_item_combinator_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut ItemCombinator<'a>,
+) {
+    for it in &mut node.leading_comments {
+        v.visit_comment_mut(it);
+    }
+    if let Some(ref mut it) = node.doc_comment {
+        v.visit_doc_comment_mut(it);
+    }
+    v.visit_path_mut(&mut node.name);
+    if let Some(ref mut it) = node.combinator_id {
+        v.visit_combinator_id_mut(it);
+    }
+    for it in &mut node.opt_params {
+        v.visit_opt_param_mut(it);
+    }
+    for it in &mut node.opt_param_comments {
+        v.visit_param_comment_mut(it);
+    }
+    for it in &mut node.params {
+        v.visit_param_mut(it);
+    }
+    for it in &mut node.param_comments {
+        v.visit_param_comment_mut(it);
+    }
+    v.visit_span_mut(&mut node.equals_token.0);
+    for it in &mut node.result_comments {
+        v.visit_comment_mut(it);
+    }
+    v.visit_parameterized_path_mut(&mut node.result_type);
+    v.visit_span_mut(&mut node.semicolon_token.0);
+    if let Some(ref mut it) = node.trailing_comment {
+        v.visit_comment_mut(it);
+    }
+}
+
+pub fn visit_item_comment_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut ItemComment<'a>) {
+    v.visit_comment_mut(&mut node.comment);
+}
+
+pub fn visit_item_delimiter_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut ItemDelimiter) {
+    v.visit_delimiter_mut(&mut node.delimiter);
+}
+
+pub fn visit_item_error_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut ItemError<'a>) {
+    v.visit_span_mut(&mut node.span);
+}
+
+pub fn visit_item_layer_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut ItemLayer) {
+    v.visit_layer_marker_mut(&mut node.marker);
+    v.visit_span_mut(&mut node.layer_span);
+}
+
+pub fn visit_item_type_declaration_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut ItemTypeDeclaration<'a>,
+) {
+    v.visit_type_declaration_kind_mut(&mut node.kind);
+    v.visit_path_mut(&mut node.name);
+    v.visit_span_mut(&mut node.semicolon_token.0);
+}
+
+pub fn visit_layer_keyword_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut LayerKeyword) {
+    match *node {
+        LayerKeyword::Upper(ref mut token) => v.visit_span_mut(&mut token.0),
+        LayerKeyword::TitleCase(ref mut token) => v.visit_span_mut(&mut token.0),
+    }
+}
+
+pub fn visit_layer_marker_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut LayerMarker) {
+    match *node {
+        LayerMarker::SingleLine(ref mut inner) => v.visit_layer_marker_single_line_mut(inner),
+        LayerMarker::MultiLine(ref mut inner) => v.visit_layer_marker_multi_line_mut(inner),
+        LayerMarker::Equals(ref mut inner) => v.visit_layer_marker_equals_mut(inner),
+    }
+}
+
+pub fn visit_layer_marker_equals_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut LayerMarkerEquals,
+) {
+    v.visit_span_mut(&mut node.left_span);
+    v.visit_span_mut(&mut node.right_span);
+}
+
+pub fn visit_layer_marker_multi_line_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut LayerMarkerMultiLine,
+) {
+    v.visit_span_mut(&mut node.slash_asterisk_token.0);
+    v.visit_layer_keyword_mut(&mut node.layer_keyword);
+}
+
+pub fn visit_layer_marker_single_line_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut LayerMarkerSingleLine,
+) {
+    v.visit_span_mut(&mut node.slash_slash_token.0);
+    v.visit_layer_keyword_mut(&mut node.layer_keyword);
+}
+
+pub fn visit_multiplicity_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut Multiplicity<'a>,
+) {
+    v.visit_nat_expr_mut(&mut node.term);
+    v.visit_span_mut(&mut node.asterisk_token.0);
+}
+
+pub fn visit_nat_expr_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut NatExpr<'a>) {
+    match *node {
+        NatExpr::Const(ref mut inner) => v.visit_nat_expr_const_mut(inner),
+        NatExpr::Ident(ref mut inner) => v.visit_nat_expr_ident_mut(inner),
+        NatExpr::Add(ref mut inner) => v.visit_nat_expr_add_mut(inner),
+        NatExpr::Paren(ref mut inner) => v.visit_nat_expr_paren_mut(inner),
+    }
+}
+
+pub fn visit_nat_expr_add_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut NatExprAdd<'a>) {
+    v.visit_nat_expr_mut(&mut node.left);
+    v.visit_span_mut(&mut node.plus_token.0);
+    v.visit_nat_expr_mut(&mut node.right);
+}
+
+pub fn visit_nat_expr_const_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut NatExprConst) {
+    v.visit_span_mut(&mut node.span);
+}
+
+pub fn visit_nat_expr_ident_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut NatExprIdent<'a>,
+) {
+    v.visit_ident_mut(&mut node.ident);
+}
+
+pub fn visit_nat_expr_paren_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut NatExprParen<'a>,
+) {
+    v.visit_span_mut(&mut node.paren_token.0);
+    v.visit_nat_expr_mut(&mut node.expr);
+}
+
+pub fn visit_opt_param_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut OptParam<'a>) {
+    v.visit_span_mut(&mut node.brace_token.0);
+    for it in &mut node.var_idents {
+        v.visit_ident_mut(it);
+    }
+    v.visit_span_mut(&mut node.colon_token.0);
+    v.visit_type_mut(&mut node.ty);
+}
+
+pub fn visit_param_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut Param<'a>) {
+    match *node {
+        Param::Conditional(ref mut inner) => v.visit_param_conditional_mut(inner),
+        Param::Repeated(ref mut inner) => v.visit_param_repeated_mut(inner),
+        Param::WithParen(ref mut inner) => v.visit_param_with_paren_mut(inner),
+        Param::TypeOnly(ref mut inner) => v.visit_param_type_only_mut(inner),
+    }
+}
+
+pub fn visit_param_comment_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut ParamComment<'a>,
+) {
+    v.visit_comment_mut(&mut node.comment);
+}
+
+pub fn visit_param_conditional_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut ParamConditional<'a>,
+) {
+    v.visit_ident_mut(&mut node.var_ident);
+    v.visit_span_mut(&mut node.colon_token.0);
+    if let Some(ref mut it) = node.conditional_param_def {
+        v.visit_conditional_param_def_mut(it);
+    }
+    v.visit_type_mut(&mut node.ty);
+}
+
+pub fn visit_param_repeated_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut ParamRepeated<'a>,
+) {
+    if let Some(ref mut it) = node.param_repeated_ident {
+        v.visit_param_repeated_ident_mut(it);
+    }
+    if let Some(ref mut it) = node.multiplicity {
+        v.visit_multiplicity_mut(it);
+    }
+    v.visit_span_mut(&mut node.bracket_token.0);
+    for it in &mut node.params {
+        v.visit_param_mut(it);
+    }
+}
+
+pub fn visit_param_repeated_ident_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut ParamRepeatedIdent<'a>,
+) {
+    v.visit_ident_mut(&mut node.var_ident);
+    v.visit_span_mut(&mut node.colon_token.0);
+}
+
+pub fn visit_param_type_only_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut ParamTypeOnly<'a>,
+) {
+    v.visit_type_mut(&mut node.ty);
+}
+
+pub fn visit_param_with_paren_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut ParamWithParen<'a>,
+) {
+    v.visit_span_mut(&mut node.paren_token.0);
+    for it in &mut node.var_idents {
+        v.visit_ident_mut(it);
+    }
+    v.visit_span_mut(&mut node.colon_token.0);
+    v.visit_type_mut(&mut node.ty);
+}
+
+pub fn visit_parameterized_path_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut ParameterizedPath<'a>,
+) {
+    v.visit_path_mut(&mut node.path);
+    if let Some(ref mut it) = node.args {
+        v.visit_generic_arguments_mut(it);
+    }
+}
+
+pub fn visit_path_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut Path<'a>) {
+    for pair in node.segments.pairs_mut() {
+        match pair {
+            Pair::Punctuated(value, punct) => {
+                v.visit_ident_mut(value);
+                v.visit_span_mut(&mut punct.0);
+            },
+            Pair::End(value) => v.visit_ident_mut(value),
+        }
+    }
+}
+
+pub fn visit_space_separated_generic_arguments_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut SpaceSeparatedGenericArguments<'a>,
+) {
+    for it in &mut node.args {
+        v.visit_generic_argument_mut(it);
+    }
+}
+
+pub fn visit_type_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut Type<'a>) {
+    match *node {
+        Type::Int(ref mut inner) => v.visit_type_int_mut(inner),
+        Type::ParameterizedPath(ref mut inner) => v.visit_type_parameterized_path_mut(inner),
+        Type::TypeParameter(ref mut inner) => v.visit_type_type_parameter_mut(inner),
+        Type::Bare(ref mut inner) => v.visit_type_bare_mut(inner),
+        Type::Paren(ref mut inner) => v.visit_type_paren_mut(inner),
+    }
+}
+
+pub fn visit_type_bare_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut TypeBare<'a>) {
+    v.visit_span_mut(&mut node.percent_token.0);
+    v.visit_type_mut(&mut node.inner);
+}
+
+pub fn visit_type_declaration_kind_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut TypeDeclarationKind,
+) {
+    match *node {
+        TypeDeclarationKind::New(ref mut token) => v.visit_span_mut(&mut token.0),
+        TypeDeclarationKind::Final(ref mut token) => v.visit_span_mut(&mut token.0),
+        TypeDeclarationKind::Empty(ref mut token) => v.visit_span_mut(&mut token.0),
+    }
+}
+
+pub fn visit_type_int_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut TypeInt) {
+    v.visit_span_mut(&mut node.hash_token.0);
+}
+
+pub fn visit_type_parameterized_path_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut TypeParameterizedPath<'a>,
+) {
+    v.visit_parameterized_path_mut(&mut node.parameterized_path);
+}
+
+pub fn visit_type_paren_mut<'a, V: VisitMut<'a> + ?Sized>(v: &mut V, node: &mut TypeParen<'a>) {
+    v.visit_span_mut(&mut node.paren_token.0);
+    v.visit_type_mut(&mut node.inner);
+}
+
+pub fn visit_type_type_parameter_mut<'a, V: VisitMut<'a> + ?Sized>(
+    v: &mut V,
+    node: &mut TypeTypeParameter<'a>,
+) {
+    v.visit_span_mut(&mut node.excl_token.0);
+    v.visit_ident_mut(&mut node.ident);
+}
+
+
+#[cfg(all(test, feature = "parsing"))]
+mod tests {
+    use super::*;
+
+    struct Rename;
+
+    impl<'a> VisitMut<'a> for Rename {
+        fn visit_ident_mut(&mut self, node: &mut Ident<'a>) {
+            if node.as_str() == "InputPeer" {
+                *node = Ident::new(node.span, "InputUser").unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn rename_idents() {
+        let mut file = ::parse_file_str("users.getFullUser#ca30a5b1 id:InputPeer = UserFull;").unwrap();
+
+        Rename.visit_file_mut(&mut file);
+
+        match file.items[0] {
+            Item::Combinator(ref combinator) => match combinator.params[0] {
+                Param::Conditional(ref param) => match param.ty {
+                    Type::ParameterizedPath(ref ty) => {
+                        assert_eq!(ty.parameterized_path.path.segments[0].as_str(), "InputUser");
+                    },
+                    _ => panic!("expected a path type"),
+                },
+                _ => panic!("expected a conditional param"),
+            },
+            _ => panic!("expected a combinator"),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+extern crate tl_lang_syn;
+
+
+use tl_lang_syn::IdValidation;
+use tl_lang_syn::error::{ParseError, ParseErrorKind};
+
+
+// Syntax tree nodes implement `Debug` only with "debug-impls" enabled, which
+// `Result::unwrap_err` would require
+fn expect_error<T>(result: Result<T, ParseError>) -> ParseError {
+    match result {
+        Ok(_) => panic!("parsing must fail"),
+        Err(error) => error,
+    }
+}
+
+fn parse_file_error(input: &str) -> ParseError {
+    expect_error(tl_lang_syn::parse_file_str(input))
+}
+
+fn parse_combinator_error(input: &str) -> ParseError {
+    expect_error(tl_lang_syn::parse_str::<tl_lang_syn::ItemCombinator>(input))
+}
+
+fn found(s: &str) -> Option<String> {
+    Some(s.to_owned())
+}
+
+#[test]
+fn unexpected_token() {
+    let input = "foo = Bar; @";
+    let error = parse_combinator_error("@foo = Bar;");
+
+    assert_eq!(*error.kind(), ParseErrorKind::UnexpectedToken { found: found("@") });
+    assert_eq!(error.to_string(), "unexpected `@`");
+
+    let error = parse_combinator_error(input);
+
+    assert_eq!(*error.kind(), ParseErrorKind::TrailingInput { found: "@".to_owned() });
+    assert_eq!(error.slice_into(input), "@");
+    assert_eq!(error.to_string(), "expected end of input, found `@`");
+
+    let error = parse_file_error(input);
+
+    assert_eq!(error.slice_into(input), "@");
+    assert_eq!(error.to_string(), "expected one of an identifier, a comment or `---`, found `@`");
+}
+
+#[test]
+fn expected_one_of() {
+    let input = "foo a:int = Bar\nbaz = Qux;\n";
+    let error = parse_file_error(input);
+
+    assert_eq!(*error.kind(), ParseErrorKind::ExpectedOneOf { expected: vec!["`;`"], found: found("=") });
+    assert_eq!(error.slice_into(input), "=");
+    assert_eq!(error.to_string(), "expected `;`, found `=`");
+
+    let error = parse_file_error("foo a:int = ;");
+
+    assert_eq!(error.to_string(), "expected a result type, found `;`");
+
+    let input = "boolTrue = Bool;\n---typos---\n";
+    let error = parse_file_error(input);
+
+    assert_eq!(error.slice_into(input), "typos");
+    assert_eq!(error.to_string(), "expected one of `types` or `functions`, found `typos`");
+
+    let error = parse_file_error("int ? Int;");
+
+    assert_eq!(error.to_string(), "expected `=`, found `Int`");
+
+    let input = "foo a:int @ = Bar;";
+    let error = parse_file_error(input);
+
+    assert_eq!(*error.kind(), ParseErrorKind::ExpectedOneOf { expected: vec!["a field", "`=`"], found: found("@") });
+    assert_eq!(error.slice_into(input), "@");
+    assert_eq!(error.to_string(), "expected one of a field or `=`, found `@`");
+
+    let error = parse_file_error("foo a:m*[ int @ ] = Bar;");
+
+    assert_eq!(error.to_string(), "expected one of a field or `]`, found `@`");
+
+    let error = parse_file_error("foo {X:} a: = Bar;");
+
+    assert_eq!(error.to_string(), "expected a type, found `}`");
+
+    let input = "foo a:flags.0? = Bar;";
+    let error = parse_file_error(input);
+
+    assert_eq!(error.slice_into(input), "=");
+    assert_eq!(error.to_string(), "expected a type, found `=`");
+}
+
+#[test]
+fn found_line_break() {
+    let input = "foo a:\n  int = Bar;";
+    let error = parse_file_error(input);
+
+    assert_eq!(*error.kind(), ParseErrorKind::ExpectedOneOf { expected: vec!["a type"], found: found("\n") });
+    assert_eq!(error.slice_into(input), "\n");
+    assert_eq!(error.to_string(), "expected a type, found end of line");
+}
+
+#[test]
+fn invalid_id() {
+    let input = "foo#0e16a8a5f = Foo;";
+    let error = parse_file_error(input);
+
+    assert_eq!(*error.kind(), ParseErrorKind::InvalidId { id: "0e16a8a5f".to_owned(), strict: false });
+    assert_eq!(error.slice_into(input), "0e16a8a5f");
+    assert_eq!(error.to_string(), "invalid combinator id `0e16a8a5f`, expected at most 8 hexadecimal digits");
+
+    let error = parse_file_error("foo#zz = Foo;");
+
+    assert_eq!(error.to_string(), "invalid combinator id `zz`, expected at most 8 hexadecimal digits");
+
+    let error = expect_error(tl_lang_syn::parse_file_str_with("foo#7efe0e = Foo;", IdValidation::Strict));
+
+    assert_eq!(error.to_string(), "invalid combinator id `7efe0e`, expected exactly 8 hexadecimal digits");
+}
+
+#[test]
+fn bit_index_out_of_range() {
+    let input = "foo flags:# bar:flags.32?int = Foo;";
+    let error = parse_file_error(input);
+
+    assert_eq!(*error.kind(), ParseErrorKind::BitIndexOutOfRange { index: "32".to_owned() });
+    assert_eq!(error.slice_into(input), "32");
+    assert_eq!(error.to_string(), "bit index `32` is out of range, expected a number from 0 to 31");
+
+    let error = parse_file_error("foo flags:# bar:flags.1000?int = Foo;");
+
+    assert_eq!(*error.kind(), ParseErrorKind::BitIndexOutOfRange { index: "1000".to_owned() });
+}
+
+#[test]
+fn unterminated_comment() {
+    let input = "foo = Bar;\n/* never closed\nbaz = Qux;\n";
+    let error = parse_file_error(input);
+
+    assert_eq!(*error.kind(), ParseErrorKind::UnterminatedComment);
+    assert_eq!(error.slice_into(input), "/* never closed\nbaz = Qux;\n");
+    assert_eq!(error.to_string(), "unterminated multi-line comment, expected `*/`");
+}
+
+#[test]
+fn unexpected_end_of_input() {
+    let error = parse_combinator_error("foo a:int = Bar");
+
+    assert_eq!(*error.kind(), ParseErrorKind::ExpectedOneOf { expected: vec!["`;`"], found: None });
+    assert_eq!(error.to_string(), "expected `;`, found end of input");
+}
+
+#[test]
+fn render_diagnostic() {
+    use tl_lang_syn::diagnostic::{ColorChoice, Diagnostic};
+    use tl_lang_syn::line_index::LineIndex;
+
+    let input = "foo a:int = Bar\nbaz = Qux;\n";
+    let error = parse_file_error(input);
+    let rendered = Diagnostic::from(&error).render("schema.tl", &LineIndex::new(input), ColorChoice::Plain);
+
+    assert_eq!(rendered, "\
+error: expected `;`, found `=`
+ --> schema.tl:2:5
+  |
+2 | baz = Qux;
+  |     ^ expected `;`
+");
+}
+
+#[test]
+fn recovering() {
+    let input = "\
+        boolFalse#bc799737 = Bool;\n\
+        boolTrue#997275b5 = Bool\n\
+        true#3fedd339 = True;\n\
+        foo#zz = Foo; bar = Bar;\n\
+        baz flags:# a:flags.32?int = Baz;\n\
+        vector {t:Type} # [ t ] = Vector t;\n\
+    ";
+    let (file, errors) = tl_lang_syn::parse_file_str_recovering(input);
+
+    let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert_eq!(messages, [
+        "expected `;`, found `=`",
+        "invalid combinator id `zz`, expected at most 8 hexadecimal digits",
+        "bit index `32` is out of range, expected a number from 0 to 31",
+    ]);
+
+    let items = file.items.iter()
+        .map(|item| match *item {
+            tl_lang_syn::Item::Combinator(ref combinator) => {
+                combinator.name.segments.iter().next().unwrap().as_str().to_owned()
+            },
+            tl_lang_syn::Item::Error(ref error) => {
+                assert_eq!(&input[error.span.begin() - 1..error.span.end() - 1], error.text);
+                format!("error: {}", error.text)
+            },
+            _ => panic!("unexpected item"),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(items, [
+        "boolFalse",
+        "error: boolTrue#997275b5 = Bool",
+        "true",
+        "error: foo#zz = Foo;",
+        "bar",
+        "error: baz flags:# a:flags.32?int = Baz;",
+        "vector",
+    ]);
+}
+
+#[test]
+fn recovering_without_errors() {
+    let input = "boolFalse#bc799737 = Bool;\nboolTrue#997275b5 = Bool;\n";
+    let (file, errors) = tl_lang_syn::parse_file_str_recovering(input);
+
+    assert!(errors.is_empty());
+    assert_eq!(file.items.len(), 2);
+}
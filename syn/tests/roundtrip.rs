@@ -1,8 +1,11 @@
 extern crate tl_lang_syn;
 #[macro_use]
 extern crate pretty_assertions;
+#[cfg(feature = "serde-impls")]
+extern crate serde_json;
 
 
+use tl_lang_syn::IdValidation;
 use tl_lang_syn::print::Print;
 
 
@@ -52,7 +55,8 @@ macro_rules! roundtrip_tests {
                 let original_string = include_str!($file_name);
 
                 // Do a syntax tree-based roundtrip instead of a string-based one
-                // because the string->tree->string conversion is lossy.
+                // because the string->tree->string conversion is lossy (see the
+                // lossless tests below for a string-based one).
                 let parsed_tree = tl_lang_syn::parse_file_str(original_string).unwrap();
                 let generated_string = parsed_tree.display_wrapper().to_string();
                 let parsed_tree2 = tl_lang_syn::parse_file_str(&generated_string).unwrap();
@@ -65,4 +69,142 @@ macro_rules! roundtrip_tests {
 
 roundtrip_tests! {
     roundtrip_small => "small.tl";
+    roundtrip_repeated => "repeated.tl";
+    roundtrip_multiline => "multiline.tl";
+    roundtrip_builtin => "builtin.tl";
+    roundtrip_layers => "layers.tl";
+}
+
+
+#[test]
+fn angle_bracketed_whitespace() {
+    let expected = tl_lang_syn::parse_str::<tl_lang_syn::Type>("Tuple<t,n+1>").unwrap();
+
+    // The pretty printer writes a space after each comma
+    for input in &["Tuple<t, n+1>", "Tuple< t , n+1 >", "Tuple<t,\n    n+1>"] {
+        let ty = tl_lang_syn::parse_str::<tl_lang_syn::Type>(input).unwrap();
+
+        any_debug_assert_eq!(&ty, &expected, "input: {:?}", input);
+    }
+}
+
+#[test]
+fn roundtrip_id_format() {
+    // Ids are printed with their original digit count and letter case
+    let original_string = "\
+        storage.fileJpeg#7EFE0E = storage.FileType;\n\
+        vector#1cb5c415 {t:Type} # [t] = Vector t;\n\
+        foo#0000beef = Foo;\n\
+        bar#3fEDd339 x:int = Bar;\n\
+    ";
+
+    let parsed_tree = tl_lang_syn::parse_file_str(original_string).unwrap();
+    let generated_string = parsed_tree.display_wrapper().to_string();
+
+    any_debug_assert_eq!(original_string.trim_end(), generated_string.trim_end());
+}
+
+#[test]
+fn strict_id_validation() {
+    let canonical = "storage.fileGif#cae1aadf = storage.FileType;\n";
+    let short = "storage.fileJpeg#7efe0e = storage.FileType;\n";
+
+    assert!(tl_lang_syn::parse_file_str_with(canonical, IdValidation::Strict).is_ok());
+    assert!(tl_lang_syn::parse_file_str_with(short, IdValidation::Lenient).is_ok());
+    assert!(tl_lang_syn::parse_file_str_with(short, IdValidation::Strict).is_err());
+}
+
+#[test]
+fn streaming_matches_whole_file() {
+    let original_strings = [
+        include_str!("small.tl"),
+        include_str!("repeated.tl"),
+        include_str!("multiline.tl"),
+        include_str!("builtin.tl"),
+        include_str!("layers.tl"),
+    ];
+
+    for original_string in &original_strings {
+        let parsed_tree = tl_lang_syn::parse_file_str(original_string).unwrap();
+        let streamed_items = tl_lang_syn::parse_items(original_string.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        any_debug_assert_eq!(parsed_tree.items, streamed_items);
+    }
+}
+
+macro_rules! lossless_roundtrip_tests {
+    ($($test_name:ident => $file_name:expr;)+) => {
+        $(
+            #[test]
+            fn $test_name() {
+                let original_string = include_str!($file_name);
+
+                let parsed_tree = tl_lang_syn::parse_file_str_lossless(original_string).unwrap();
+                let generated_string = parsed_tree.display_wrapper().to_string();
+
+                assert_eq!(original_string, generated_string);
+            }
+        )+
+    };
+}
+
+lossless_roundtrip_tests! {
+    lossless_roundtrip_small => "small.tl";
+    lossless_roundtrip_repeated => "repeated.tl";
+    lossless_roundtrip_multiline => "multiline.tl";
+    lossless_roundtrip_builtin => "builtin.tl";
+    lossless_roundtrip_layers => "layers.tl";
+}
+
+macro_rules! pretty_roundtrip_tests {
+    ($($test_name:ident => $file_name:expr;)+) => {
+        $(
+            #[test]
+            fn $test_name() {
+                let original_string = include_str!($file_name);
+
+                let printer = tl_lang_syn::pretty::PrettyPrinter::new();
+
+                let parsed_tree = tl_lang_syn::parse_file_str(original_string).unwrap();
+                let formatted_string = printer.print_file(&parsed_tree);
+                let parsed_tree2 = tl_lang_syn::parse_file_str(&formatted_string).unwrap();
+                let reformatted_string = printer.print_file(&parsed_tree2);
+
+                any_debug_assert_eq!(parsed_tree, parsed_tree2);
+                // Formatting is idempotent
+                assert_eq!(formatted_string, reformatted_string);
+            }
+        )+
+    };
+}
+
+pretty_roundtrip_tests! {
+    pretty_roundtrip_small => "small.tl";
+    pretty_roundtrip_repeated => "repeated.tl";
+    pretty_roundtrip_multiline => "multiline.tl";
+    pretty_roundtrip_builtin => "builtin.tl";
+    pretty_roundtrip_layers => "layers.tl";
+}
+
+#[cfg(feature = "serde-impls")]
+#[test]
+fn serde_roundtrip() {
+    let original_strings = [
+        include_str!("small.tl"),
+        include_str!("repeated.tl"),
+        include_str!("multiline.tl"),
+        include_str!("builtin.tl"),
+        include_str!("layers.tl"),
+    ];
+
+    for original_string in &original_strings {
+        let parsed_tree = tl_lang_syn::parse_file_str(original_string).unwrap();
+        let json = serde_json::to_string(&parsed_tree).unwrap();
+        let deserialized_tree: tl_lang_syn::File = serde_json::from_str(&json).unwrap();
+
+        any_debug_assert_eq!(parsed_tree, deserialized_tree);
+        assert_eq!(json, serde_json::to_string(&deserialized_tree).unwrap());
+    }
 }
@@ -0,0 +1,81 @@
+extern crate tl_lang_syn;
+
+
+use tl_lang_syn::SectionKind;
+
+
+fn combinator_names(sections: &tl_lang_syn::Sections, kind: SectionKind) -> Vec<String> {
+    sections.combinators(kind)
+        .map(|combinator| combinator.name.segments.iter().map(|s| s.as_str()).collect::<Vec<_>>().join("."))
+        .collect()
+}
+
+#[test]
+fn sections() {
+    let file = tl_lang_syn::parse_file_str("\
+        int ? = Int;\n\
+        boolFalse#bc799737 = Bool;\n\
+        ---functions---\n\
+        help.getConfig#c4f9186b = Config;\n\
+        ---types---\n\
+        true#3fedd339 = True;\n\
+        ---functions---\n\
+        help.getNearestDc#1fb33026 = NearestDc;\n\
+    ").unwrap();
+
+    let sections = file.sections();
+    let kinds = sections.sections.iter().map(|section| section.kind).collect::<Vec<_>>();
+
+    assert_eq!(kinds, [
+        SectionKind::Types,
+        SectionKind::Functions,
+        SectionKind::Types,
+        SectionKind::Functions,
+    ]);
+    assert!(sections.sections[0].delimiter.is_none());
+    assert_eq!(sections.sections[0].items.len(), 2);
+    assert_eq!(combinator_names(&sections, SectionKind::Types), ["boolFalse", "true"]);
+    assert_eq!(combinator_names(&sections, SectionKind::Functions), ["help.getConfig", "help.getNearestDc"]);
+    assert!(sections.diagnostics.is_empty());
+}
+
+#[test]
+fn leading_delimiter_is_not_redundant() {
+    let file = tl_lang_syn::parse_file_str("---types---\nfoo = Foo;\n").unwrap();
+    let sections = file.sections();
+
+    assert_eq!(sections.sections.len(), 1);
+    assert!(sections.sections[0].delimiter.is_some());
+    assert!(sections.diagnostics.is_empty());
+}
+
+#[test]
+fn redundant_delimiters() {
+    let original_string = "\
+        ---types---\n\
+        foo = Foo;\n\
+        ---types---\n\
+        bar = Bar;\n\
+        ---functions---\n\
+        ---functions---\n\
+        baz = Baz;\n\
+    ";
+
+    let file = tl_lang_syn::parse_file_str(original_string).unwrap();
+    let sections = file.sections();
+
+    assert_eq!(sections.sections.len(), 4);
+    assert_eq!(combinator_names(&sections, SectionKind::Types), ["foo", "bar"]);
+    assert_eq!(combinator_names(&sections, SectionKind::Functions), ["baz"]);
+
+    let diagnostics = &sections.diagnostics;
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].kind, SectionKind::Types);
+    assert_eq!(diagnostics[0].span.begin(), original_string.find("---types---\nbar").unwrap() + 1);
+    assert_eq!(diagnostics[0].previous_span.begin(), 1);
+    assert_eq!(diagnostics[1].kind, SectionKind::Functions);
+    assert_eq!(
+        diagnostics[1].to_string(),
+        "redundant `---functions---` delimiter in a functions section",
+    );
+}